@@ -4,6 +4,7 @@ use criterion::{criterion_group, criterion_main, Criterion, Throughput};
 
 use packedtime_rs::{
     date_add_month_timestamp_millis, date_add_month_timestamp_millis_float,
+    date_add_year_timestamp_millis,
     date_diff_month_timestamp_millis, date_diff_month_timestamp_millis_float,
     date_diff_year_timestamp_millis, date_diff_year_timestamp_millis_float,
 };
@@ -32,6 +33,17 @@ fn bench_date_add_month_float(input: &[f64], output: &mut [f64], months: i32) {
         });
 }
 
+#[inline(never)]
+fn bench_date_add_year(input: &[i64], output: &mut [i64], years: i32) {
+    assert_eq!(input.len(), output.len());
+    output
+        .iter_mut()
+        .zip(input.iter().copied())
+        .for_each(|(output, input)| {
+            *output = date_add_year_timestamp_millis(input, years);
+        });
+}
+
 #[inline(never)]
 fn bench_date_diff_month(start: &[i64], end: &[i64], output: &mut [i32]) {
     assert_eq!(start.len(), end.len());
@@ -100,19 +112,19 @@ pub fn bench_date_add(c: &mut Criterion) {
     let mut rng = StdRng::seed_from_u64(42);
 
     let input = (0..BATCH_SIZE)
-        .map(|_| rng.gen_range(0..4102444800_000_i64))
+        .map(|_| rng.gen_range(0..4_102_444_800_000_i64))
         .collect::<Vec<_>>();
 
     let input2 = (0..BATCH_SIZE)
-        .map(|_| rng.gen_range(0..4102444800_000_i64))
+        .map(|_| rng.gen_range(0..4_102_444_800_000_i64))
         .collect::<Vec<_>>();
 
     let input_float = (0..BATCH_SIZE)
-        .map(|_| rng.gen_range(0..4102444800_000_i64) as f64)
+        .map(|_| rng.gen_range(0..4_102_444_800_000_i64) as f64)
         .collect::<Vec<_>>();
 
     let input_float2 = (0..BATCH_SIZE)
-        .map(|_| rng.gen_range(0..4102444800_000_i64) as f64)
+        .map(|_| rng.gen_range(0..4_102_444_800_000_i64) as f64)
         .collect::<Vec<_>>();
 
     let mut output = vec![0_i64; BATCH_SIZE];
@@ -131,6 +143,9 @@ pub fn bench_date_add(c: &mut Criterion) {
         })
         .bench_function("date_add_month_chronoutil", |b| {
             b.iter(|| bench_date_add_month_chronoutil(&input, &mut output, 1))
+        })
+        .bench_function("date_add_year", |b| {
+            b.iter(|| bench_date_add_year(&input, &mut output, 1))
         });
 
     c.benchmark_group("date_diff_month")
@@ -1,10 +1,12 @@
 use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime};
 use criterion::{criterion_group, criterion_main, Criterion, Throughput};
 use packedtime_rs::{
-    date_part_month_timestamp_millis, date_part_year_timestamp_millis,
+    date_bin_timestamp_millis, date_part_month_timestamp_millis, date_part_year_timestamp_millis,
+    date_trunc_hour_timestamp_millis,
+    date_trunc_day_timestamp_millis_in_zone, date_trunc_day_timestamp_millis_with_offset,
     date_trunc_month_timestamp_millis, date_trunc_month_timestamp_millis_float,
     date_trunc_year_timestamp_millis, date_trunc_year_timestamp_millis_float,
-    days_in_month_timestamp_millis,
+    days_in_month_timestamp_millis, TimeZone,
 };
 use std::hint::unreachable_unchecked;
 
@@ -31,6 +33,46 @@ fn bench_date_trunc_month(input: &[i64], output: &mut [i64]) {
         });
 }
 
+#[inline(never)]
+fn bench_date_trunc_hour(input: &[i64], output: &mut [i64]) {
+    output
+        .iter_mut()
+        .zip(input.iter().copied())
+        .for_each(|(output, input)| {
+            *output = date_trunc_hour_timestamp_millis(input);
+        });
+}
+
+#[inline(never)]
+fn bench_date_bin(input: &[i64], output: &mut [i64], stride: i64, origin: i64) {
+    output
+        .iter_mut()
+        .zip(input.iter().copied())
+        .for_each(|(output, input)| {
+            *output = date_bin_timestamp_millis(input, stride, origin);
+        });
+}
+
+#[inline(never)]
+fn bench_date_trunc_day_with_offset(input: &[i64], output: &mut [i64], offset_minutes: i32) {
+    output
+        .iter_mut()
+        .zip(input.iter().copied())
+        .for_each(|(output, input)| {
+            *output = date_trunc_day_timestamp_millis_with_offset(input, offset_minutes);
+        });
+}
+
+#[inline(never)]
+fn bench_date_trunc_day_in_zone(input: &[i64], output: &mut [i64], zone: &TimeZone) {
+    output
+        .iter_mut()
+        .zip(input.iter().copied())
+        .for_each(|(output, input)| {
+            *output = date_trunc_day_timestamp_millis_in_zone(input, zone);
+        });
+}
+
 #[inline(never)]
 fn bench_date_trunc_year_float(input: &[f64], output: &mut [f64]) {
     output
@@ -137,7 +179,7 @@ fn bench_days_in_month(input: &[i64], output: &mut [i32]) {
 fn bench_days_in_month_chrono(input: &[i64], output: &mut [i32]) {
     #[inline]
     fn is_leap_year(year: i32) -> bool {
-        return year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
     }
     output
         .iter_mut()
@@ -165,7 +207,7 @@ pub fn bench_date_trunc(c: &mut Criterion) {
     let mut rng = StdRng::seed_from_u64(42);
 
     let input = (0..BATCH_SIZE)
-        .map(|_| rng.gen_range(0..4102444800_000_i64))
+        .map(|_| rng.gen_range(0..4_102_444_800_000_i64))
         .collect::<Vec<_>>();
 
     let input_float = input.iter().map(|i| *i as f64).collect::<Vec<_>>();
@@ -184,6 +226,19 @@ pub fn bench_date_trunc(c: &mut Criterion) {
         .bench_function("date_trunc_month", |b| {
             b.iter(|| bench_date_trunc_month(&input, &mut output))
         })
+        .bench_function("date_trunc_hour", |b| {
+            b.iter(|| bench_date_trunc_hour(&input, &mut output))
+        })
+        .bench_function("date_bin", |b| {
+            b.iter(|| bench_date_bin(&input, &mut output, 15 * 60 * 1000, 150_000))
+        })
+        .bench_function("date_trunc_day_with_offset", |b| {
+            b.iter(|| bench_date_trunc_day_with_offset(&input, &mut output, 120))
+        })
+        .bench_function("date_trunc_day_in_zone", |b| {
+            let zone = TimeZone::from_tzif_bytes(include_bytes!("../testdata/zoneinfo/Europe/Berlin")).unwrap();
+            b.iter(|| bench_date_trunc_day_in_zone(&input, &mut output, &zone))
+        })
         .bench_function("date_trunc_year_float", |b| {
             b.iter(|| bench_date_trunc_year_float(&input_float, &mut output_float))
         })
@@ -89,7 +89,7 @@ fn bench_timestamp_simd(input: &[i64], output: &mut [u8]) {
             unsafe {
                 packedtime_rs::format_simd_mul_to_slice(
                     out,
-                    ts.year(),
+                    ts.year() as u32,
                     ts.month(),
                     ts.day(),
                     ts.hour(),
@@ -128,7 +128,7 @@ fn bench_timestamp_scalar(input: &[i64], output: &mut [u8]) {
             let ts = PackedTimestamp::from_timestamp_millis(*inp);
             packedtime_rs::format_scalar_to_slice(
                 out,
-                ts.year(),
+                ts.year() as u32,
                 ts.month(),
                 ts.day(),
                 ts.hour(),
@@ -173,7 +173,7 @@ pub fn bench_format(c: &mut Criterion) {
     let mut rng = StdRng::seed_from_u64(42);
 
     let inputs = (0..BATCH_SIZE)
-        .map(|_| rng.gen_range(0..4102444800_000_i64))
+        .map(|_| rng.gen_range(0..4_102_444_800_000_i64))
         .collect::<Vec<i64>>();
 
     {
@@ -193,6 +193,25 @@ pub fn bench_format(c: &mut Criterion) {
         group.bench_function("format_scalar", |b| {
             b.iter(|| bench_timestamp_scalar(&inputs, &mut output));
         });
+        group.bench_function("format_batch", |b| {
+            let mut values = Vec::with_capacity(24 * BATCH_SIZE);
+            let mut offsets = Vec::with_capacity(BATCH_SIZE + 1);
+            b.iter(|| {
+                values.clear();
+                offsets.clear();
+                packedtime_rs::format_timestamp_millis_batch(&inputs, &mut values, &mut offsets);
+            });
+        });
+        group.bench_function("format_pattern_batch", |b| {
+            let format = packedtime_rs::Format::compile("%Y-%m-%dT%H:%M:%S%.3fZ").unwrap();
+            let mut values = Vec::with_capacity(24 * BATCH_SIZE);
+            let mut offsets = Vec::with_capacity(BATCH_SIZE + 1);
+            b.iter(|| {
+                values.clear();
+                offsets.clear();
+                format.format_timestamp_millis_batch(&inputs, &mut values, &mut offsets);
+            });
+        });
         group.bench_function("format_chrono", |b| {
             b.iter(|| bench_timestamp_chrono(&inputs, &mut output));
         });
@@ -19,10 +19,9 @@ fn bench_parse_scalar(input: &[u8], output: &mut [PackedTimestamp], date_len: us
         });
 }
 
-#[cfg(all(
-    target_arch = "x86_64",
-    target_feature = "sse2",
-    target_feature = "ssse3"
+#[cfg(any(
+    all(target_arch = "x86_64", target_feature = "ssse3"),
+    all(target_arch = "aarch64", target_feature = "neon")
 ))]
 #[inline(never)]
 fn bench_parse_simd(input: &[u8], output: &mut [PackedTimestamp], date_len: usize) {
@@ -84,7 +83,7 @@ pub fn bench_parse(c: &mut Criterion) {
     const DATE_LEN_WITH_OFFSET: usize = 29;
 
     const BATCH_SIZE: usize = 512;
-    const TS_RANGE: Range<i64> = 0..4102444800_000_i64;
+    const TS_RANGE: Range<i64> = 0..4_102_444_800_000_i64;
 
     let mut rng = StdRng::seed_from_u64(42);
 
@@ -115,16 +114,20 @@ pub fn bench_parse(c: &mut Criterion) {
     assert_eq!(input_with_offset.len(), DATE_LEN_WITH_OFFSET * BATCH_SIZE);
 
     let mut output = vec![PackedTimestamp::from_value(0); BATCH_SIZE];
+    let mut output_millis = vec![0_i64; BATCH_SIZE];
+    let mut validity = vec![0_u8; BATCH_SIZE / 8];
+    let offsets_utc = (0..=BATCH_SIZE)
+        .map(|i| (i * DATE_LEN_UTC) as i32)
+        .collect::<Vec<_>>();
 
     {
         let mut group = c.benchmark_group("parse_utc");
         let group = group.throughput(Throughput::Bytes(
             (input_utc.len() + BATCH_SIZE * std::mem::size_of::<i64>()) as u64,
         ));
-        #[cfg(all(
-            target_arch = "x86_64",
-            target_feature = "sse2",
-            target_feature = "ssse3"
+        #[cfg(any(
+            all(target_arch = "x86_64", target_feature = "ssse3"),
+            all(target_arch = "aarch64", target_feature = "neon")
         ))]
         group.bench_function("parse_simd", |b| {
             b.iter(|| bench_parse_simd(input_utc.as_bytes(), &mut output, DATE_LEN_UTC))
@@ -132,6 +135,29 @@ pub fn bench_parse(c: &mut Criterion) {
         group.bench_function("parse_scalar", |b| {
             b.iter(|| bench_parse_scalar(input_utc.as_bytes(), &mut output, DATE_LEN_UTC))
         });
+        group.bench_function("parse_batch", |b| {
+            b.iter(|| {
+                packedtime_rs::parse_to_timestamp_millis_batch_offsets(
+                    &offsets_utc,
+                    input_utc.as_bytes(),
+                    None,
+                    &mut output_millis,
+                    &mut validity,
+                )
+            })
+        });
+        group.bench_function("parse_pattern_batch", |b| {
+            let format = packedtime_rs::Format::compile("%Y-%m-%dT%H:%M:%S%.3fZ").unwrap();
+            b.iter(|| {
+                format.parse_timestamp_millis_batch_offsets(
+                    &offsets_utc,
+                    input_utc.as_bytes(),
+                    None,
+                    &mut output_millis,
+                    &mut validity,
+                )
+            })
+        });
         group.bench_function("parse_chrono", |b| {
             b.iter(|| bench_parse_chrono(input_utc.as_bytes(), &mut output, DATE_LEN_UTC))
         });
@@ -146,10 +172,9 @@ pub fn bench_parse(c: &mut Criterion) {
         let group = group.throughput(Throughput::Bytes(
             (input_with_offset.len() + BATCH_SIZE * std::mem::size_of::<i64>()) as u64,
         ));
-        #[cfg(all(
-            target_arch = "x86_64",
-            target_feature = "sse2",
-            target_feature = "ssse3"
+        #[cfg(any(
+            all(target_arch = "x86_64", target_feature = "ssse3"),
+            all(target_arch = "aarch64", target_feature = "neon")
         ))]
         group.bench_function("parse_simd", |b| {
             b.iter(|| bench_parse_simd(input_utc.as_bytes(), &mut output, DATE_LEN_UTC))
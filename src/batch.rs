@@ -0,0 +1,722 @@
+use crate::datetime::DateTimeComponents;
+use crate::dispatch::{format_utc_to_slice, select_parse_date_fn, select_parse_fn};
+use crate::error::{ParseResult, RowParseError};
+use crate::format::{
+    format_date_scalar_to_slice, format_offset_scalar_to_slice, format_to_iso_date_bytes, format_to_rfc3339_bytes,
+    format_to_rfc3339_bytes_with_options, FormatOptions,
+};
+use crate::parse::ts_to_epoch_millis;
+use crate::{AddInterval, EpochDays, Interval, PackedTimestamp};
+
+#[inline]
+fn get_bit(bitmap: &[u8], index: usize) -> bool {
+    bitmap[index / 8] & (1 << (index % 8)) != 0
+}
+
+#[inline]
+fn set_bit(bitmap: &mut [u8], index: usize, value: bool) {
+    let mask = 1 << (index % 8);
+    if value {
+        bitmap[index / 8] |= mask;
+    } else {
+        bitmap[index / 8] &= !mask;
+    }
+}
+
+/// Parses a batch of rfc 3339 timestamps into milliseconds since the unix epoch.
+///
+/// The validity bitmap uses the same layout as arrow, with the least significant bit first,
+/// and needs to have room for one bit per row, bits after the last row are left unchanged.
+/// The bit of each row that could not be parsed is cleared, its output is set to zero,
+/// and the error is returned together with the row index.
+pub fn parse_to_timestamp_millis_batch(input: &[&str], output: &mut [i64], validity: &mut [u8]) -> Vec<RowParseError> {
+    assert_eq!(input.len(), output.len());
+    parse_rows(|i| Some(input[i].as_bytes()), output, validity)
+}
+
+/// Parses a batch of rfc 3339 timestamps stored in a single buffer with separate offsets,
+/// using the same layout as an arrow `StringArray` or `LargeStringArray`.
+///
+/// Rows whose bit is not set in the optional `nulls` bitmap are treated as null, their output is set to zero
+/// and their validity bit is cleared without reporting an error. Otherwise this behaves like `parse_to_timestamp_millis_batch`.
+pub fn parse_to_timestamp_millis_batch_offsets<O: Copy + Into<i64>>(
+    offsets: &[O],
+    values: &[u8],
+    nulls: Option<&[u8]>,
+    output: &mut [i64],
+    validity: &mut [u8],
+) -> Vec<RowParseError> {
+    parse_rows(offset_rows(offsets, values, nulls, output.len()), output, validity)
+}
+
+/// Parses a batch of `yyyy-mm-dd` dates into days since the unix epoch, as used by an arrow `Date32Array`.
+/// The handling of errors and the validity bitmap is the same as for `parse_to_timestamp_millis_batch`.
+pub fn parse_to_epoch_days_batch(input: &[&str], output: &mut [i32], validity: &mut [u8]) -> Vec<RowParseError> {
+    assert_eq!(input.len(), output.len());
+    parse_date_rows(|i| Some(input[i].as_bytes()), output, validity)
+}
+
+/// Parses a batch of `yyyy-mm-dd` dates stored in a single buffer with separate offsets.
+/// The handling of nulls is the same as for `parse_to_timestamp_millis_batch_offsets`.
+pub fn parse_to_epoch_days_batch_offsets<O: Copy + Into<i64>>(
+    offsets: &[O],
+    values: &[u8],
+    nulls: Option<&[u8]>,
+    output: &mut [i32],
+    validity: &mut [u8],
+) -> Vec<RowParseError> {
+    parse_date_rows(offset_rows(offsets, values, nulls, output.len()), output, validity)
+}
+
+pub(crate) fn offset_rows<'a, O: Copy + Into<i64>>(
+    offsets: &'a [O],
+    values: &'a [u8],
+    nulls: Option<&'a [u8]>,
+    len: usize,
+) -> impl Fn(usize) -> Option<&'a [u8]> {
+    assert_eq!(offsets.len(), len + 1);
+    if let Some(nulls) = nulls {
+        assert!(nulls.len() * 8 >= len);
+    }
+    move |i| {
+        if nulls.is_some_and(|nulls| !get_bit(nulls, i)) {
+            return None;
+        }
+        let start = offsets[i].into() as usize;
+        let end = offsets[i + 1].into() as usize;
+        Some(&values[start..end])
+    }
+}
+
+/// Writes the parse result of each row into the output and validity bitmap, collecting the errors.
+/// Rows that are null or could not be parsed are set to the default value of zero.
+pub(crate) struct RowWriter<'a, T> {
+    output: &'a mut [T],
+    validity: &'a mut [u8],
+    pub(crate) errors: Vec<RowParseError>,
+}
+
+impl<'a, T: Default> RowWriter<'a, T> {
+    pub(crate) fn new(output: &'a mut [T], validity: &'a mut [u8]) -> Self {
+        assert!(validity.len() * 8 >= output.len());
+        Self {
+            output,
+            validity,
+            errors: vec![],
+        }
+    }
+
+    #[inline]
+    pub(crate) fn set(&mut self, i: usize, result: Option<ParseResult<T>>) {
+        match result {
+            Some(Ok(value)) => {
+                self.output[i] = value;
+                set_bit(self.validity, i, true);
+            }
+            Some(Err(error)) => {
+                self.output[i] = T::default();
+                set_bit(self.validity, i, false);
+                self.errors.push(RowParseError { row: i, error });
+            }
+            None => {
+                self.output[i] = T::default();
+                set_bit(self.validity, i, false);
+            }
+        }
+    }
+}
+
+fn parse_date_rows<'a>(row: impl Fn(usize) -> Option<&'a [u8]>, output: &mut [i32], validity: &mut [u8]) -> Vec<RowParseError> {
+    let len = output.len();
+    let parse = select_parse_date_fn();
+    let mut writer = RowWriter::new(output, validity);
+    let mut set_result =
+        |i: usize, result: Option<ParseResult<EpochDays>>| writer.set(i, result.map(|result| result.map(|date| date.days())));
+
+    let mut i = 0;
+
+    #[cfg(target_arch = "x86_64")]
+    if crate::dispatch::simd_level() == crate::dispatch::SimdLevel::Avx2 {
+        while i + 2 <= len {
+            match (row(i), row(i + 1)) {
+                (Some(first), Some(second)) => {
+                    // safety: avx2 support was checked at runtime
+                    let (first, second) = unsafe { crate::parse::parse_date_simd_x2_unchecked(first, second) };
+                    set_result(i, Some(first));
+                    set_result(i + 1, Some(second));
+                }
+                (first, second) => {
+                    set_result(i, first.map(parse));
+                    set_result(i + 1, second.map(parse));
+                }
+            }
+            i += 2;
+        }
+    }
+
+    while i < len {
+        set_result(i, row(i).map(parse));
+        i += 1;
+    }
+
+    writer.errors
+}
+
+fn parse_rows<'a>(row: impl Fn(usize) -> Option<&'a [u8]>, output: &mut [i64], validity: &mut [u8]) -> Vec<RowParseError> {
+    let len = output.len();
+    let parse = select_parse_fn();
+    let mut writer = RowWriter::new(output, validity);
+    let mut set_result = |i: usize, result: Option<ParseResult<DateTimeComponents>>| {
+        writer.set(i, result.map(|result| result.map(|ts| ts_to_epoch_millis(&ts))))
+    };
+
+    let mut i = 0;
+
+    #[cfg(target_arch = "x86_64")]
+    if crate::dispatch::simd_level() == crate::dispatch::SimdLevel::Avx2 {
+        while i + 2 <= len {
+            match (row(i), row(i + 1)) {
+                (Some(first), Some(second)) => {
+                    // safety: avx2 support was checked at runtime
+                    let (first, second) = unsafe { crate::parse::parse_simd_x2_unchecked(first, second) };
+                    set_result(i, Some(first));
+                    set_result(i + 1, Some(second));
+                }
+                (first, second) => {
+                    set_result(i, first.map(parse));
+                    set_result(i + 1, second.map(parse));
+                }
+            }
+            i += 2;
+        }
+    }
+
+    while i < len {
+        set_result(i, row(i).map(parse));
+        i += 1;
+    }
+
+    writer.errors
+}
+
+/// Formats a batch of timestamps in milliseconds since the unix epoch as rfc 3339 strings in utc.
+///
+/// The formatted strings are appended to `values` without any separator, and for each row the end offset
+/// is appended to `offsets`, using the same layout as an arrow `StringArray`.
+/// If `offsets` is empty, the current length of `values` is appended first as the start offset.
+pub fn format_timestamp_millis_batch(input: &[i64], values: &mut Vec<u8>, offsets: &mut Vec<i32>) {
+    format_rows(
+        input.len(),
+        |i| DateTimeComponents::from_timestamp_millis(input[i]),
+        values,
+        offsets,
+    )
+}
+
+/// Formats a batch of timestamps in milliseconds since the unix epoch as rfc 3339 strings in utc according to the given options.
+/// The output layout is the same as for `format_timestamp_millis_batch`.
+pub fn format_timestamp_millis_batch_with_options(
+    input: &[i64],
+    options: &FormatOptions,
+    values: &mut Vec<u8>,
+    offsets: &mut Vec<i32>,
+) {
+    if offsets.is_empty() {
+        offsets.push(values.len().try_into().expect("offset overflow"));
+    }
+    offsets.reserve(input.len());
+    values.reserve(input.len() * 24);
+
+    for ts in input {
+        let ts = DateTimeComponents::from_timestamp_millis(*ts);
+        let formatted = format_to_rfc3339_bytes_with_options(
+            ts.year,
+            ts.month as u32,
+            ts.day as u32,
+            ts.hour as u32,
+            ts.minute as u32,
+            ts.second as u32,
+            ts.nanosecond,
+            ts.offset_minute,
+            options,
+        );
+        values.extend_from_slice(formatted.as_bytes());
+        offsets.push(values.len().try_into().expect("offset overflow"));
+    }
+}
+
+/// Formats a batch of packed timestamps as rfc 3339 strings including their timezone offsets.
+/// The output layout is the same as for `format_timestamp_millis_batch`.
+pub fn format_packed_timestamp_batch(input: &[PackedTimestamp], values: &mut Vec<u8>, offsets: &mut Vec<i32>) {
+    format_rows(
+        input.len(),
+        |i| {
+            let ts = input[i];
+            DateTimeComponents::new_with_offset_minute(
+                ts.year(),
+                ts.month() as u8,
+                ts.day() as u8,
+                ts.hour() as u8,
+                ts.minute() as u8,
+                ts.second() as u8,
+                ts.millisecond(),
+                ts.offset_minutes(),
+            )
+        },
+        values,
+        offsets,
+    )
+}
+
+/// Formats a batch of days since the unix epoch as `yyyy-mm-dd` dates.
+/// The output layout is the same as for `format_timestamp_millis_batch`.
+pub fn format_epoch_days_batch(input: &[i32], values: &mut Vec<u8>, offsets: &mut Vec<i32>) {
+    if offsets.is_empty() {
+        offsets.push(values.len().try_into().expect("offset overflow"));
+    }
+    offsets.reserve(input.len());
+    values.reserve(input.len() * 10);
+
+    for days in input {
+        let (year, month, day) = EpochDays::new(*days).to_ymd();
+        if has_four_digit_year(year) {
+            let mut buffer = [0_u8; 10];
+            format_date_scalar_to_slice(&mut buffer, year as u32, month as u32, day as u32);
+            values.extend_from_slice(&buffer);
+        } else {
+            values.extend_from_slice(format_to_iso_date_bytes(year, month as u32, day as u32).as_bytes());
+        }
+        offsets.push(values.len().try_into().expect("offset overflow"));
+    }
+}
+
+/// Whether the year can be formatted without the expanded representation, using the fixed-width fast paths.
+#[inline]
+fn has_four_digit_year(year: i32) -> bool {
+    year >= 0 && year <= 9999
+}
+
+/// Appends the offset if it is not zero, replacing the `Z` at the end of the 24 byte utc representation.
+#[inline]
+fn push_formatted_utc(values: &mut Vec<u8>, offsets: &mut Vec<i32>, formatted: &[u8; 24], offset_minutes: i32) {
+    values.extend_from_slice(&formatted[..23]);
+    let mut buffer = [0_u8; 6];
+    let len = format_offset_scalar_to_slice(&mut buffer, offset_minutes);
+    values.extend_from_slice(&buffer[..len]);
+    offsets.push(values.len().try_into().expect("offset overflow"));
+}
+
+#[inline]
+fn push_formatted(values: &mut Vec<u8>, offsets: &mut Vec<i32>, ts: &DateTimeComponents) {
+    if has_four_digit_year(ts.year) {
+        let mut buffer = [0_u8; 24];
+        format_utc_to_slice(
+            &mut buffer,
+            ts.year as u32,
+            ts.month as u32,
+            ts.day as u32,
+            ts.hour as u32,
+            ts.minute as u32,
+            ts.second as u32,
+            ts.millisecond(),
+        );
+        push_formatted_utc(values, offsets, &buffer, ts.offset_minute);
+    } else {
+        let formatted = format_to_rfc3339_bytes(
+            ts.year,
+            ts.month as u32,
+            ts.day as u32,
+            ts.hour as u32,
+            ts.minute as u32,
+            ts.second as u32,
+            ts.millisecond(),
+            ts.offset_minute,
+        );
+        values.extend_from_slice(formatted.as_bytes());
+        offsets.push(values.len().try_into().expect("offset overflow"));
+    }
+}
+
+fn format_rows(len: usize, row: impl Fn(usize) -> DateTimeComponents, values: &mut Vec<u8>, offsets: &mut Vec<i32>) {
+    if offsets.is_empty() {
+        offsets.push(values.len().try_into().expect("offset overflow"));
+    }
+    offsets.reserve(len);
+    values.reserve(len * 24);
+
+    let mut i = 0;
+
+    #[cfg(target_arch = "x86_64")]
+    if crate::dispatch::simd_level() == crate::dispatch::SimdLevel::Avx2 {
+        while i + 2 <= len {
+            let first = row(i);
+            let second = row(i + 1);
+            if has_four_digit_year(first.year) && has_four_digit_year(second.year) {
+                let mut buffer = [0_u8; 48];
+                // safety: avx2 support was checked at runtime
+                unsafe { crate::format::format_simd_dd_x2_to_slice(&mut buffer, &first, &second) };
+                let (first_formatted, second_formatted) = buffer.split_at(24);
+                push_formatted_utc(values, offsets, first_formatted.try_into().unwrap(), first.offset_minute);
+                push_formatted_utc(values, offsets, second_formatted.try_into().unwrap(), second.offset_minute);
+            } else {
+                push_formatted(values, offsets, &first);
+                push_formatted(values, offsets, &second);
+            }
+            i += 2;
+        }
+    }
+
+    while i < len {
+        push_formatted(values, offsets, &row(i));
+        i += 1;
+    }
+}
+
+/// Adds a separate interval to each timestamp of a column of milliseconds since the unix epoch,
+/// for example from an arrow `IntervalMonthDayNanoArray`. See `Interval::add_to` for the order in which the components are applied.
+pub fn date_add_interval_timestamp_millis_batch(input: &[i64], intervals: &[Interval], output: &mut [i64]) {
+    assert_eq!(input.len(), intervals.len());
+    assert_eq!(input.len(), output.len());
+    output
+        .iter_mut()
+        .zip(input.iter().copied().zip(intervals.iter()))
+        .for_each(|(output, (ts, interval))| *output = ts.add_interval(interval));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dispatch::lock_force_scalar;
+    use crate::{
+        date_add_interval_timestamp_millis_batch, force_scalar, format_epoch_days_batch, format_packed_timestamp_batch,
+        format_timestamp_millis_batch, format_timestamp_millis_batch_with_options, parse_to_epoch_days_batch,
+        parse_to_epoch_days_batch_offsets, parse_to_timestamp_millis, parse_to_timestamp_millis_batch,
+        parse_to_timestamp_millis_batch_offsets, DateTimeField, EpochDays, Expected, FormatOptions, FractionalDigits, Interval,
+        PackedTimestamp, ParseError, RowParseError,
+    };
+
+    const INPUT: &[&str] = &[
+        "2022-08-21T17:30:15.250Z",
+        "2022-08-21 17:30:15.25+02:00",
+        "2022-08-2X",
+        "1970-01-01T00:00Z",
+        "-0044-03-15T12:00:00Z",
+        "2023-02-30T00:00:00Z",
+        "2022-08-21T17:30:15.123456789-05:30",
+        "2022-08-21T17:30:15.250Z",
+        "X022-08-21T17:30:15.250Z",
+        "2022-08-21T17:30:15.250Zjunk",
+    ];
+
+    fn expected_errors() -> Vec<RowParseError> {
+        vec![
+            RowParseError {
+                row: 2,
+                error: ParseError::InvalidLen(10),
+            },
+            RowParseError {
+                row: 5,
+                error: ParseError::InvalidValue(DateTimeField::Day),
+            },
+            RowParseError {
+                row: 8,
+                error: ParseError::InvalidChar {
+                    position: 0,
+                    byte: b'X',
+                    expected: Expected::Digit,
+                },
+            },
+            RowParseError {
+                row: 9,
+                error: ParseError::TrailingChar {
+                    position: 24,
+                    byte: b'j',
+                },
+            },
+        ]
+    }
+
+    fn expected_output() -> Vec<i64> {
+        INPUT
+            .iter()
+            .map(|s| parse_to_timestamp_millis(s.as_bytes()).unwrap_or(0))
+            .collect()
+    }
+
+    fn assert_formatted_batch(values: &[u8], offsets: &[i32], expected: &[String]) {
+        assert_eq!(offsets.len(), expected.len() + 1);
+        assert_eq!(offsets[0], 0);
+        for (i, expected) in expected.iter().enumerate() {
+            let actual = &values[offsets[i] as usize..offsets[i + 1] as usize];
+            assert_eq!(std::str::from_utf8(actual).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_format_timestamp_millis_batch() {
+        let _guard = lock_force_scalar();
+        let input = [
+            1661103015250,
+            0,
+            -1,
+            253402300799999,
+            -62167219200001,
+            951782400123,
+            1661103015250,
+            -377705116800000,
+            4102444800000,
+        ];
+        let expected = input
+            .iter()
+            .map(|ts| PackedTimestamp::from_timestamp_millis(*ts).to_rfc3339_string())
+            .collect::<Vec<_>>();
+        assert_eq!(expected[0], "2022-08-21T17:30:15.250Z");
+        assert_eq!(expected[4], "-0001-12-31T23:59:59.999Z");
+
+        let mut values = vec![];
+        let mut offsets = vec![];
+        format_timestamp_millis_batch(&input, &mut values, &mut offsets);
+        assert_formatted_batch(&values, &offsets, &expected);
+
+        force_scalar(true);
+        let mut values = vec![];
+        let mut offsets = vec![];
+        format_timestamp_millis_batch(&input, &mut values, &mut offsets);
+        force_scalar(false);
+        assert_formatted_batch(&values, &offsets, &expected);
+    }
+
+    #[test]
+    fn test_format_timestamp_millis_batch_with_options() {
+        let _guard = lock_force_scalar();
+        let input = [1661103015250, 0, -1, 1661103015001];
+        let options = FormatOptions::new()
+            .with_fractional_digits(FractionalDigits::Shortest)
+            .with_space_separator(true)
+            .with_zero_offset_as_z(false);
+
+        let mut values = vec![];
+        let mut offsets = vec![];
+        format_timestamp_millis_batch_with_options(&input, &options, &mut values, &mut offsets);
+        assert_formatted_batch(
+            &values,
+            &offsets,
+            &[
+                "2022-08-21 17:30:15.25+00:00".to_owned(),
+                "1970-01-01 00:00:00+00:00".to_owned(),
+                "1969-12-31 23:59:59.999+00:00".to_owned(),
+                "2022-08-21 17:30:15.001+00:00".to_owned(),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_format_packed_timestamp_batch() {
+        let _guard = lock_force_scalar();
+        let input = (0..37)
+            .map(|i| {
+                PackedTimestamp::new(
+                    1900 + i * 7,
+                    (i % 12 + 1) as u32,
+                    (i % 28 + 1) as u32,
+                    (i % 24) as u32,
+                    (i * 7 % 60) as u32,
+                    (i * 13 % 61) as u32,
+                    (i * 97 % 1000) as u32,
+                    (i - 18) * 60,
+                )
+            })
+            .chain([PackedTimestamp::new_utc(-44, 3, 15, 12, 0, 0, 0)])
+            .collect::<Vec<_>>();
+        let expected = input.iter().map(|ts| ts.to_rfc3339_string()).collect::<Vec<_>>();
+        assert_eq!(expected[18], "2026-07-19T18:06:51.746Z");
+        assert_eq!(expected[19], "2033-08-20T19:13:03.843+01:00");
+
+        let mut values = b"prefix".to_vec();
+        let mut offsets = vec![];
+        format_packed_timestamp_batch(&input, &mut values, &mut offsets);
+        assert_eq!(offsets[0], 6);
+        assert_formatted_batch(&values[6..], &offsets.iter().map(|o| o - 6).collect::<Vec<_>>(), &expected);
+    }
+
+    #[test]
+    fn test_parse_batch() {
+        let _guard = lock_force_scalar();
+        let mut output = vec![-1; INPUT.len()];
+        let mut validity = vec![0; 2];
+
+        let errors = parse_to_timestamp_millis_batch(INPUT, &mut output, &mut validity);
+
+        assert_eq!(errors, expected_errors());
+        assert_eq!(output, expected_output());
+        assert_eq!(output[0], 1661103015250);
+        assert_eq!(validity, vec![0b1101_1011, 0b0000_0000]);
+    }
+
+    #[test]
+    fn test_parse_batch_scalar() {
+        let _guard = lock_force_scalar();
+        force_scalar(true);
+        let mut output = vec![-1; INPUT.len()];
+        let mut validity = vec![0; 2];
+        let errors = parse_to_timestamp_millis_batch(INPUT, &mut output, &mut validity);
+        force_scalar(false);
+
+        assert_eq!(errors, expected_errors());
+        assert_eq!(output, expected_output());
+        assert_eq!(validity, vec![0b1101_1011, 0b0000_0000]);
+    }
+
+    #[test]
+    fn test_parse_batch_offsets() {
+        let _guard = lock_force_scalar();
+        let values = INPUT.concat();
+        let mut offsets = vec![0_i32];
+        for s in INPUT {
+            offsets.push(offsets.last().unwrap() + s.len() as i32);
+        }
+        let nulls = [0b1111_1110, 0b0000_0011];
+
+        let mut output = vec![-1; INPUT.len()];
+        let mut validity = vec![0xFF; 2];
+
+        let errors = parse_to_timestamp_millis_batch_offsets(&offsets, values.as_bytes(), Some(&nulls), &mut output, &mut validity);
+
+        let mut expected = expected_output();
+        expected[0] = 0;
+        assert_eq!(errors, expected_errors());
+        assert_eq!(output, expected);
+        // bits after the last row are left unchanged
+        assert_eq!(validity, vec![0b1101_1010, 0b1111_1100]);
+
+        let offsets = offsets.iter().map(|o| *o as i64).collect::<Vec<_>>();
+        let errors = parse_to_timestamp_millis_batch_offsets(&offsets, values.as_bytes(), None, &mut output, &mut validity);
+        assert_eq!(errors, expected_errors());
+        assert_eq!(output, expected_output());
+        assert_eq!(validity, vec![0b1101_1011, 0b1111_1100]);
+    }
+
+    const DATE_INPUT: &[&str] = &[
+        "2022-08-21",
+        "1970-01-01",
+        "2022-08-2X",
+        "2023-02-29",
+        "-0044-03-15",
+        "2024-02-29",
+        "2022-08-21T00:00Z",
+        "9999-12-31",
+        "0000-01-01",
+    ];
+
+    fn expected_date_errors() -> Vec<RowParseError> {
+        vec![
+            RowParseError {
+                row: 2,
+                error: ParseError::InvalidChar {
+                    position: 9,
+                    byte: b'X',
+                    expected: Expected::Digit,
+                },
+            },
+            RowParseError {
+                row: 3,
+                error: ParseError::InvalidValue(DateTimeField::Day),
+            },
+            RowParseError {
+                row: 6,
+                error: ParseError::TrailingChar {
+                    position: 10,
+                    byte: b'T',
+                },
+            },
+        ]
+    }
+
+    fn expected_date_output() -> Vec<i32> {
+        DATE_INPUT
+            .iter()
+            .map(|s| EpochDays::from_iso_date_str(s).map(|d| d.days()).unwrap_or(0))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_date_batch() {
+        let _guard = lock_force_scalar();
+        let mut output = vec![-1; DATE_INPUT.len()];
+        let mut validity = vec![0; 2];
+
+        let errors = parse_to_epoch_days_batch(DATE_INPUT, &mut output, &mut validity);
+
+        assert_eq!(errors, expected_date_errors());
+        assert_eq!(output, expected_date_output());
+        assert_eq!(output[0], 19225);
+        assert_eq!(validity, vec![0b1011_0011, 0b0000_0001]);
+
+        force_scalar(true);
+        let mut output = vec![-1; DATE_INPUT.len()];
+        let mut validity = vec![0; 2];
+        let errors = parse_to_epoch_days_batch(DATE_INPUT, &mut output, &mut validity);
+        force_scalar(false);
+
+        assert_eq!(errors, expected_date_errors());
+        assert_eq!(output, expected_date_output());
+        assert_eq!(validity, vec![0b1011_0011, 0b0000_0001]);
+    }
+
+    #[test]
+    fn test_parse_date_batch_offsets() {
+        let _guard = lock_force_scalar();
+        let values = DATE_INPUT.concat();
+        let mut offsets = vec![0_i64];
+        for s in DATE_INPUT {
+            offsets.push(offsets.last().unwrap() + s.len() as i64);
+        }
+        let nulls = [0b1111_1110, 0b0000_0011];
+
+        let mut output = vec![-1; DATE_INPUT.len()];
+        let mut validity = vec![0xFF; 2];
+
+        let errors = parse_to_epoch_days_batch_offsets(&offsets, values.as_bytes(), Some(&nulls), &mut output, &mut validity);
+
+        let mut expected = expected_date_output();
+        expected[0] = 0;
+        assert_eq!(errors, expected_date_errors());
+        assert_eq!(output, expected);
+        assert_eq!(validity, vec![0b1011_0010, 0b1111_1111]);
+    }
+
+    #[test]
+    fn test_format_epoch_days_batch() {
+        let _guard = lock_force_scalar();
+        let input = [0, 19225, -1, 2932896, -719528, -735700, 1000000, -10];
+        let expected = input.iter().map(|days| EpochDays::new(*days).to_string()).collect::<Vec<_>>();
+        assert_eq!(expected[1], "2022-08-21");
+        assert_eq!(expected[3], "9999-12-31");
+        assert_eq!(expected[4], "0000-01-01");
+        assert_eq!(expected[5], "-0045-09-22");
+
+        let mut values = vec![];
+        let mut offsets = vec![];
+        format_epoch_days_batch(&input, &mut values, &mut offsets);
+        assert_formatted_batch(&values, &offsets, &expected);
+    }
+
+    #[test]
+    fn test_date_add_interval_batch() {
+        let input = [
+            parse_to_timestamp_millis(b"2024-01-30T12:00:00Z").unwrap(),
+            parse_to_timestamp_millis(b"2024-03-31T00:00:00Z").unwrap(),
+            parse_to_timestamp_millis(b"1969-12-31T23:59:59.999Z").unwrap(),
+        ];
+        let intervals = [Interval::new(1, 1, 0), -Interval::new(1, 1, 0), Interval::from_millis(1)];
+        let mut output = [0_i64; 3];
+        date_add_interval_timestamp_millis_batch(&input, &intervals, &mut output);
+        assert_eq!(
+            output,
+            [
+                parse_to_timestamp_millis(b"2024-03-01T12:00:00Z").unwrap(),
+                parse_to_timestamp_millis(b"2024-02-28T00:00:00Z").unwrap(),
+                0
+            ]
+        );
+    }
+}
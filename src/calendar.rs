@@ -0,0 +1,147 @@
+use crate::epoch_days::days_per_month;
+
+/// A day of the week.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum Weekday {
+    Monday = 0,
+    Tuesday = 1,
+    Wednesday = 2,
+    Thursday = 3,
+    Friday = 4,
+    Saturday = 5,
+    Sunday = 6,
+}
+
+impl Weekday {
+    const ALL: [Weekday; 7] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ];
+
+    /// The weekday for a number of days since monday, wrapping around for values outside of `0..7`.
+    #[inline]
+    pub fn from_days_since_monday(days: i32) -> Self {
+        Self::ALL[days.rem_euclid(7) as usize]
+    }
+
+    /// The number of days since monday, from 0 to 6.
+    #[inline]
+    pub fn days_since_monday(self) -> i32 {
+        self as i32
+    }
+
+    /// The number of days since sunday, from 0 to 6.
+    #[inline]
+    pub fn days_since_sunday(self) -> i32 {
+        (self as i32 + 1) % 7
+    }
+
+    /// The ISO 8601 number of this weekday, from 1 for monday to 7 for sunday.
+    #[inline]
+    pub fn iso_number(self) -> i32 {
+        self as i32 + 1
+    }
+}
+
+/// A month of the year.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum Month {
+    January = 1,
+    February = 2,
+    March = 3,
+    April = 4,
+    May = 5,
+    June = 6,
+    July = 7,
+    August = 8,
+    September = 9,
+    October = 10,
+    November = 11,
+    December = 12,
+}
+
+impl Month {
+    const ALL: [Month; 12] = [
+        Month::January,
+        Month::February,
+        Month::March,
+        Month::April,
+        Month::May,
+        Month::June,
+        Month::July,
+        Month::August,
+        Month::September,
+        Month::October,
+        Month::November,
+        Month::December,
+    ];
+
+    /// The month for a number from 1 to 12.
+    #[inline]
+    pub fn from_number(month: i32) -> Option<Self> {
+        if month >= 1 && month <= 12 {
+            Some(Self::ALL[month as usize - 1])
+        } else {
+            None
+        }
+    }
+
+    /// The number of this month, from 1 for january to 12 for december.
+    #[inline]
+    pub fn number(self) -> i32 {
+        self as i32
+    }
+
+    /// The number of days of this month in the given year.
+    #[inline]
+    pub fn days_in(self, year: i32) -> i32 {
+        days_per_month(year, self as i32 - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Month, Weekday};
+
+    #[test]
+    fn test_weekday_numbers() {
+        assert_eq!(Weekday::Monday.days_since_monday(), 0);
+        assert_eq!(Weekday::Sunday.days_since_monday(), 6);
+        assert_eq!(Weekday::Sunday.days_since_sunday(), 0);
+        assert_eq!(Weekday::Saturday.days_since_sunday(), 6);
+        assert_eq!(Weekday::Monday.iso_number(), 1);
+        assert_eq!(Weekday::Sunday.iso_number(), 7);
+
+        assert_eq!(Weekday::from_days_since_monday(3), Weekday::Thursday);
+        assert_eq!(Weekday::from_days_since_monday(7), Weekday::Monday);
+        assert_eq!(Weekday::from_days_since_monday(-1), Weekday::Sunday);
+    }
+
+    #[test]
+    fn test_month_numbers() {
+        assert_eq!(Month::from_number(1), Some(Month::January));
+        assert_eq!(Month::from_number(12), Some(Month::December));
+        assert_eq!(Month::from_number(0), None);
+        assert_eq!(Month::from_number(13), None);
+        for number in 1..=12 {
+            assert_eq!(Month::from_number(number).unwrap().number(), number);
+        }
+    }
+
+    #[test]
+    fn test_month_days() {
+        assert_eq!(Month::January.days_in(2023), 31);
+        assert_eq!(Month::February.days_in(2023), 28);
+        assert_eq!(Month::February.days_in(2024), 29);
+        assert_eq!(Month::February.days_in(1900), 28);
+        assert_eq!(Month::April.days_in(2023), 30);
+        assert_eq!(Month::December.days_in(2023), 31);
+    }
+}
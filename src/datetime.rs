@@ -1,4 +1,6 @@
-use crate::{EpochDays, PackedTimestamp, MILLIS_PER_DAY};
+use crate::epoch_days::days_per_month;
+use crate::packed::{MAX_OFFSET_MINUTES, MAX_YEAR, MIN_OFFSET_MINUTES, MIN_YEAR};
+use crate::{DateTimeField, EpochDays, PackedTimestamp, PackedTimestampNanos, RangeError, MILLIS_PER_DAY, NANOS_PER_DAY};
 
 /// Internal struct containing the components of a DateTime as separate fields.
 #[derive(PartialEq, Clone, Debug, Default)]
@@ -9,7 +11,7 @@ pub(crate) struct DateTimeComponents {
     pub hour: u8,
     pub minute: u8,
     pub second: u8,
-    pub millisecond: u32,
+    pub nanosecond: u32,
     pub offset_minute: i32,
 }
 
@@ -34,7 +36,32 @@ impl DateTimeComponents {
             hour: hour_of_day as _,
             minute: minute as _,
             second: second as _,
-            millisecond,
+            nanosecond: millisecond * 1_000_000,
+            offset_minute: 0,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn from_timestamp_nanos(ts: i64) -> Self {
+        let epoch_days = ts.div_euclid(NANOS_PER_DAY) as i32;
+        let nano_of_day = ts.rem_euclid(NANOS_PER_DAY) as u64;
+        let nanosecond = (nano_of_day % 1_000_000_000) as u32;
+        let second_of_day = (nano_of_day / 1_000_000_000) as u32;
+        let second = second_of_day % 60;
+        let minute_of_day = second_of_day / 60;
+        let minute = minute_of_day % 60;
+        let hour_of_day = minute_of_day / 60;
+
+        let (year, month, day) = EpochDays::new(epoch_days).to_ymd();
+
+        Self {
+            year,
+            month: month as _,
+            day: day as _,
+            hour: hour_of_day as _,
+            minute: minute as _,
+            second: second as _,
+            nanosecond,
             offset_minute: 0,
         }
     }
@@ -48,7 +75,7 @@ impl DateTimeComponents {
             hour,
             minute,
             second,
-            millisecond,
+            nanosecond: millisecond * 1_000_000,
             offset_minute: 0,
         }
     }
@@ -63,6 +90,20 @@ impl DateTimeComponents {
         second: u8,
         millisecond: u32,
         offset_minute: i32,
+    ) -> Self {
+        Self::new_with_nanosecond(year, month, day, hour, minute, second, millisecond * 1_000_000, offset_minute)
+    }
+
+    #[inline(always)]
+    pub(crate) fn new_with_nanosecond(
+        year: i32,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        nanosecond: u32,
+        offset_minute: i32,
     ) -> Self {
         Self {
             year,
@@ -71,11 +112,47 @@ impl DateTimeComponents {
             hour,
             minute,
             second,
-            millisecond,
+            nanosecond,
             offset_minute,
         }
     }
 
+    /// Checks that all components are within their valid range, including the number of days of the month.
+    /// A second value of 60 is allowed to represent leap seconds.
+    #[inline(always)]
+    pub(crate) fn validate(&self) -> Result<(), RangeError> {
+        if self.year < MIN_YEAR || self.year > MAX_YEAR {
+            return Err(RangeError(DateTimeField::Year));
+        }
+        if self.month < 1 || self.month > 12 {
+            return Err(RangeError(DateTimeField::Month));
+        }
+        if self.day < 1 || self.day as i32 > days_per_month(self.year, self.month as i32 - 1) {
+            return Err(RangeError(DateTimeField::Day));
+        }
+        if self.hour > 23 {
+            return Err(RangeError(DateTimeField::Hour));
+        }
+        if self.minute > 59 {
+            return Err(RangeError(DateTimeField::Minute));
+        }
+        if self.second > 60 {
+            return Err(RangeError(DateTimeField::Second));
+        }
+        if self.nanosecond > 999_999_999 {
+            return Err(RangeError(DateTimeField::Nanosecond));
+        }
+        if self.offset_minute < MIN_OFFSET_MINUTES || self.offset_minute > MAX_OFFSET_MINUTES {
+            return Err(RangeError(DateTimeField::OffsetMinutes));
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub(crate) fn millisecond(&self) -> u32 {
+        self.nanosecond / 1_000_000
+    }
+
     #[inline(always)]
     pub(crate) fn to_packed(&self) -> PackedTimestamp {
         PackedTimestamp::new(
@@ -85,7 +162,21 @@ impl DateTimeComponents {
             self.hour as _,
             self.minute as _,
             self.second as _,
-            self.millisecond as _,
+            self.millisecond() as _,
+            self.offset_minute,
+        )
+    }
+
+    #[inline(always)]
+    pub(crate) fn to_packed_nanos(&self) -> PackedTimestampNanos {
+        PackedTimestampNanos::new(
+            self.year as _,
+            self.month as _,
+            self.day as _,
+            self.hour as _,
+            self.minute as _,
+            self.second as _,
+            self.nanosecond,
             self.offset_minute,
         )
     }
@@ -0,0 +1,497 @@
+//! Runtime selection of the SIMD or scalar implementations of parsing and formatting.
+//!
+//! The selected implementations are cached as function pointers on first use,
+//! so binaries compiled for a generic target can still make use of SSE4.1, AVX2 or NEON where available.
+//! If the target features are already enabled at compile time, for example using `-C target-cpu=native`,
+//! the SIMD implementations are called directly so that they can be inlined.
+
+use crate::datetime::DateTimeComponents;
+use crate::error::ParseResult;
+use crate::format::{format_scalar_to_slice, format_scalar_with_offset_to_slice};
+use crate::parse::{parse_date_scalar, parse_scalar};
+use crate::EpochDays;
+use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicU8, Ordering};
+
+/// Environment variable that forces the scalar implementations when set to a value other than `0`.
+/// Like `force_scalar`, it does not affect single value parsing and formatting if SSE4.1 or NEON is enabled at compile time.
+pub const FORCE_SCALAR_ENV_VAR: &str = "PACKEDTIME_FORCE_SCALAR";
+
+/// Instruction set extensions used by the parsing and formatting functions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SimdLevel {
+    /// Portable scalar implementation.
+    Scalar,
+    /// SSE2, SSSE3 and SSE4.1 instructions.
+    Sse41,
+    /// AVX2 instructions in addition to SSE4.1.
+    Avx2,
+    /// AArch64 NEON instructions.
+    Neon,
+}
+
+impl SimdLevel {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => SimdLevel::Sse41,
+            2 => SimdLevel::Avx2,
+            3 => SimdLevel::Neon,
+            _ => SimdLevel::Scalar,
+        }
+    }
+}
+
+const LEVEL_UNKNOWN: u8 = u8::MAX;
+
+static DETECTED_LEVEL: AtomicU8 = AtomicU8::new(LEVEL_UNKNOWN);
+static FORCE_SCALAR: AtomicBool = AtomicBool::new(false);
+
+pub(crate) type ParseFn = fn(&[u8]) -> ParseResult<DateTimeComponents>;
+pub(crate) type ParseDateFn = fn(&[u8]) -> ParseResult<EpochDays>;
+type FormatFn = fn(&mut [u8], u32, u32, u32, u32, u32, u32, u32);
+type FormatWithOffsetFn = fn(&mut [u8], u32, u32, u32, u32, u32, u32, u32, i32) -> usize;
+
+static PARSE_FN: AtomicPtr<()> = AtomicPtr::new(parse_detect as *mut ());
+static PARSE_DATE_FN: AtomicPtr<()> = AtomicPtr::new(parse_date_detect as *mut ());
+static FORMAT_FN: AtomicPtr<()> = AtomicPtr::new(format_detect as *mut ());
+static FORMAT_WITH_OFFSET_FN: AtomicPtr<()> = AtomicPtr::new(format_with_offset_detect as *mut ());
+
+/// Returns the instruction set extensions supported by the current cpu,
+/// or `SimdLevel::Scalar` if the scalar implementation was forced.
+#[inline]
+pub fn simd_level() -> SimdLevel {
+    if FORCE_SCALAR.load(Ordering::SeqCst) {
+        return SimdLevel::Scalar;
+    }
+    match DETECTED_LEVEL.load(Ordering::Relaxed) {
+        LEVEL_UNKNOWN => {
+            let level = detect_simd_level();
+            DETECTED_LEVEL.store(level as u8, Ordering::Relaxed);
+            level
+        }
+        level => SimdLevel::from_u8(level),
+    }
+}
+
+/// Serializes tests that toggle `force_scalar` or depend on the selected implementation, since the setting is global.
+#[cfg(test)]
+static FORCE_SCALAR_TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Takes the `force_scalar` test lock, ignoring poisoning so that one failed test does not fail all others.
+#[cfg(test)]
+pub(crate) fn lock_force_scalar() -> std::sync::MutexGuard<'static, ()> {
+    FORCE_SCALAR_TEST_LOCK.lock().unwrap_or_else(|error| error.into_inner())
+}
+
+/// Forces the use of the scalar implementations, regardless of the detected cpu features.
+/// This is mostly useful for testing that both implementations produce the same results.
+/// Single value parsing and formatting is not affected if SSE4.1 or NEON is enabled at compile time.
+///
+/// Calls that are running concurrently with this function may still use the previously selected implementation,
+/// all calls starting after it returned use the new setting.
+pub fn force_scalar(force: bool) {
+    FORCE_SCALAR.store(force, Ordering::SeqCst);
+    PARSE_FN.store(parse_detect as *mut (), Ordering::SeqCst);
+    PARSE_DATE_FN.store(parse_date_detect as *mut (), Ordering::SeqCst);
+    FORMAT_FN.store(format_detect as *mut (), Ordering::SeqCst);
+    FORMAT_WITH_OFFSET_FN.store(format_with_offset_detect as *mut (), Ordering::SeqCst);
+}
+
+/// Caches the implementation selected by a `*_detect` trampoline.
+///
+/// A concurrent `force_scalar` could have reset the pointer between the selection and this store,
+/// so if the setting changed since `forced` was read the trampoline is stored again to select on the next call.
+fn store_selected(ptr: &AtomicPtr<()>, selected: *mut (), detect: *mut (), forced: bool) {
+    ptr.store(selected, Ordering::SeqCst);
+    if FORCE_SCALAR.load(Ordering::SeqCst) != forced {
+        ptr.store(detect, Ordering::SeqCst);
+    }
+}
+
+fn detect_simd_level() -> SimdLevel {
+    if std::env::var_os(FORCE_SCALAR_ENV_VAR).is_some_and(|value| !value.is_empty() && value != "0") {
+        return SimdLevel::Scalar;
+    }
+    #[cfg(all(not(miri), target_arch = "x86_64"))]
+    {
+        if is_x86_feature_detected!("sse2") && is_x86_feature_detected!("ssse3") && is_x86_feature_detected!("sse4.1") {
+            if is_x86_feature_detected!("avx2") {
+                return SimdLevel::Avx2;
+            }
+            return SimdLevel::Sse41;
+        }
+    }
+    #[cfg(all(not(miri), target_arch = "aarch64"))]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            return SimdLevel::Neon;
+        }
+    }
+    SimdLevel::Scalar
+}
+
+#[inline]
+pub(crate) fn parse_components(bytes: &[u8]) -> ParseResult<DateTimeComponents> {
+    #[cfg(all(not(miri), target_arch = "x86_64", target_feature = "sse4.1"))]
+    {
+        parse_sse41(bytes)
+    }
+    #[cfg(all(not(miri), target_arch = "aarch64", target_feature = "neon"))]
+    {
+        parse_neon(bytes)
+    }
+    #[cfg(not(any(
+        all(not(miri), target_arch = "x86_64", target_feature = "sse4.1"),
+        all(not(miri), target_arch = "aarch64", target_feature = "neon")
+    )))]
+    {
+        let f = unsafe { std::mem::transmute::<*mut (), ParseFn>(PARSE_FN.load(Ordering::Relaxed)) };
+        f(bytes)
+    }
+}
+
+#[inline]
+pub(crate) fn parse_date(bytes: &[u8]) -> ParseResult<EpochDays> {
+    #[cfg(all(not(miri), target_arch = "x86_64", target_feature = "sse4.1"))]
+    {
+        parse_date_sse41(bytes)
+    }
+    #[cfg(all(not(miri), target_arch = "aarch64", target_feature = "neon"))]
+    {
+        parse_date_neon(bytes)
+    }
+    #[cfg(not(any(
+        all(not(miri), target_arch = "x86_64", target_feature = "sse4.1"),
+        all(not(miri), target_arch = "aarch64", target_feature = "neon")
+    )))]
+    {
+        let f = unsafe { std::mem::transmute::<*mut (), ParseDateFn>(PARSE_DATE_FN.load(Ordering::Relaxed)) };
+        f(bytes)
+    }
+}
+
+#[inline]
+pub(crate) fn format_utc_to_slice(
+    slice: &mut [u8],
+    year: u32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    millisecond: u32,
+) {
+    #[cfg(all(not(miri), target_arch = "x86_64", target_feature = "sse4.1"))]
+    {
+        format_sse41(slice, year, month, day, hour, minute, second, millisecond)
+    }
+    #[cfg(all(not(miri), target_arch = "aarch64", target_feature = "neon"))]
+    {
+        format_neon(slice, year, month, day, hour, minute, second, millisecond)
+    }
+    #[cfg(not(any(
+        all(not(miri), target_arch = "x86_64", target_feature = "sse4.1"),
+        all(not(miri), target_arch = "aarch64", target_feature = "neon")
+    )))]
+    {
+        let f = unsafe { std::mem::transmute::<*mut (), FormatFn>(FORMAT_FN.load(Ordering::Relaxed)) };
+        f(slice, year, month, day, hour, minute, second, millisecond)
+    }
+}
+
+#[inline]
+pub(crate) fn format_with_offset_to_slice(
+    slice: &mut [u8],
+    year: u32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    millisecond: u32,
+    offset_minutes: i32,
+) -> usize {
+    #[cfg(all(not(miri), target_arch = "x86_64", target_feature = "sse4.1"))]
+    {
+        format_with_offset_sse41(slice, year, month, day, hour, minute, second, millisecond, offset_minutes)
+    }
+    #[cfg(all(not(miri), target_arch = "aarch64", target_feature = "neon"))]
+    {
+        format_with_offset_neon(slice, year, month, day, hour, minute, second, millisecond, offset_minutes)
+    }
+    #[cfg(not(any(
+        all(not(miri), target_arch = "x86_64", target_feature = "sse4.1"),
+        all(not(miri), target_arch = "aarch64", target_feature = "neon")
+    )))]
+    {
+        let f = unsafe { std::mem::transmute::<*mut (), FormatWithOffsetFn>(FORMAT_WITH_OFFSET_FN.load(Ordering::Relaxed)) };
+        f(slice, year, month, day, hour, minute, second, millisecond, offset_minutes)
+    }
+}
+
+pub(crate) fn select_parse_fn() -> ParseFn {
+    #[cfg(target_arch = "x86_64")]
+    if simd_level() >= SimdLevel::Sse41 {
+        return parse_sse41;
+    }
+    #[cfg(target_arch = "aarch64")]
+    if simd_level() == SimdLevel::Neon {
+        return parse_neon;
+    }
+    parse_scalar
+}
+
+pub(crate) fn select_parse_date_fn() -> ParseDateFn {
+    #[cfg(target_arch = "x86_64")]
+    if simd_level() >= SimdLevel::Sse41 {
+        return parse_date_sse41;
+    }
+    #[cfg(target_arch = "aarch64")]
+    if simd_level() == SimdLevel::Neon {
+        return parse_date_neon;
+    }
+    parse_date_scalar
+}
+
+fn select_format_fn() -> FormatFn {
+    #[cfg(target_arch = "x86_64")]
+    if simd_level() >= SimdLevel::Sse41 {
+        return format_sse41;
+    }
+    #[cfg(target_arch = "aarch64")]
+    if simd_level() == SimdLevel::Neon {
+        return format_neon;
+    }
+    format_scalar_to_slice
+}
+
+fn select_format_with_offset_fn() -> FormatWithOffsetFn {
+    #[cfg(target_arch = "x86_64")]
+    if simd_level() >= SimdLevel::Sse41 {
+        return format_with_offset_sse41;
+    }
+    #[cfg(target_arch = "aarch64")]
+    if simd_level() == SimdLevel::Neon {
+        return format_with_offset_neon;
+    }
+    format_scalar_with_offset_to_slice
+}
+
+fn parse_detect(bytes: &[u8]) -> ParseResult<DateTimeComponents> {
+    let forced = FORCE_SCALAR.load(Ordering::SeqCst);
+    let f = select_parse_fn();
+    store_selected(&PARSE_FN, f as *mut (), parse_detect as *mut (), forced);
+    f(bytes)
+}
+
+fn parse_date_detect(bytes: &[u8]) -> ParseResult<EpochDays> {
+    let forced = FORCE_SCALAR.load(Ordering::SeqCst);
+    let f = select_parse_date_fn();
+    store_selected(&PARSE_DATE_FN, f as *mut (), parse_date_detect as *mut (), forced);
+    f(bytes)
+}
+
+fn format_detect(slice: &mut [u8], year: u32, month: u32, day: u32, hour: u32, minute: u32, second: u32, millisecond: u32) {
+    let forced = FORCE_SCALAR.load(Ordering::SeqCst);
+    let f = select_format_fn();
+    store_selected(&FORMAT_FN, f as *mut (), format_detect as *mut (), forced);
+    f(slice, year, month, day, hour, minute, second, millisecond)
+}
+
+fn format_with_offset_detect(
+    slice: &mut [u8],
+    year: u32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    millisecond: u32,
+    offset_minutes: i32,
+) -> usize {
+    let forced = FORCE_SCALAR.load(Ordering::SeqCst);
+    let f = select_format_with_offset_fn();
+    store_selected(
+        &FORMAT_WITH_OFFSET_FN,
+        f as *mut (),
+        format_with_offset_detect as *mut (),
+        forced,
+    );
+    f(slice, year, month, day, hour, minute, second, millisecond, offset_minutes)
+}
+
+// The following wrappers are only selected after checking the cpu features at runtime.
+
+#[inline]
+#[cfg(target_arch = "x86_64")]
+fn parse_sse41(bytes: &[u8]) -> ParseResult<DateTimeComponents> {
+    unsafe { crate::parse::parse_simd_unchecked(bytes) }
+}
+
+#[inline]
+#[cfg(target_arch = "x86_64")]
+fn parse_date_sse41(bytes: &[u8]) -> ParseResult<EpochDays> {
+    unsafe { crate::parse::parse_date_simd_unchecked(bytes) }
+}
+
+#[inline]
+#[cfg(target_arch = "x86_64")]
+fn format_sse41(slice: &mut [u8], year: u32, month: u32, day: u32, hour: u32, minute: u32, second: u32, millisecond: u32) {
+    unsafe { crate::format::format_simd_mul_to_slice(slice, year, month, day, hour, minute, second, millisecond) }
+}
+
+#[inline]
+#[cfg(target_arch = "x86_64")]
+fn format_with_offset_sse41(
+    slice: &mut [u8],
+    year: u32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    millisecond: u32,
+    offset_minutes: i32,
+) -> usize {
+    unsafe {
+        crate::format::format_simd_mul_with_offset_to_slice(
+            slice,
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            millisecond,
+            offset_minutes,
+        )
+    }
+}
+
+#[inline]
+#[cfg(target_arch = "aarch64")]
+fn parse_neon(bytes: &[u8]) -> ParseResult<DateTimeComponents> {
+    unsafe { crate::parse::parse_simd_unchecked(bytes) }
+}
+
+#[inline]
+#[cfg(target_arch = "aarch64")]
+fn parse_date_neon(bytes: &[u8]) -> ParseResult<EpochDays> {
+    unsafe { crate::parse::parse_date_simd_unchecked(bytes) }
+}
+
+#[inline]
+#[cfg(target_arch = "aarch64")]
+fn format_neon(slice: &mut [u8], year: u32, month: u32, day: u32, hour: u32, minute: u32, second: u32, millisecond: u32) {
+    unsafe { crate::format::format_neon_to_slice(slice, year, month, day, hour, minute, second, millisecond) }
+}
+
+#[inline]
+#[cfg(target_arch = "aarch64")]
+fn format_with_offset_neon(
+    slice: &mut [u8],
+    year: u32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    millisecond: u32,
+    offset_minutes: i32,
+) -> usize {
+    unsafe {
+        crate::format::format_neon_with_offset_to_slice(slice, year, month, day, hour, minute, second, millisecond, offset_minutes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dispatch::{
+        force_scalar, lock_force_scalar, parse_date_scalar, parse_scalar, select_parse_date_fn, select_parse_fn, simd_level,
+        SimdLevel,
+    };
+    use crate::{format_to_rfc3339_bytes, parse_to_epoch_days_batch, PackedTimestamp, RowParseError};
+
+    const INPUTS: &[&str] = &[
+        "1970-01-01T00:00Z",
+        "2022-08-21T17:30:15.250Z",
+        "2022-08-21T17:30:15.250Zjunk",
+        "2022-08-21 17:30:15.25+02:00",
+        "2022-08-21T17:30:15.123456789-05:30",
+        "2022-08-21T17:30:15.250+02:00junk",
+        "2023-02-30T00:00:00Z",
+        "-0044-03-15T12:00:00Z",
+        "2022-08-21X17:30:15Z",
+        "2023-29-0XT09:30:15Z",
+        "2023-09-10T29:00:0XZ",
+        "2022-08",
+    ];
+
+    const DATE_INPUTS: &[&str] = &[
+        "1970-01-01",
+        "2024-02-29",
+        "2023-02-29",
+        "-0044-03-15",
+        "2023-01-01T",
+        "2023/01/01",
+        "2023-29-0X",
+        "2023",
+        "2023-1X-4X",
+        "9999-12-31",
+        "2023-13-01",
+    ];
+
+    #[test]
+    fn test_selected_parse_matches_scalar() {
+        let _guard = lock_force_scalar();
+        let f = select_parse_fn();
+        for input in INPUTS {
+            assert_eq!(f(input.as_bytes()), parse_scalar(input.as_bytes()), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_selected_parse_date_matches_scalar() {
+        let _guard = lock_force_scalar();
+        let f = select_parse_date_fn();
+        for input in DATE_INPUTS {
+            assert_eq!(f(input.as_bytes()), parse_date_scalar(input.as_bytes()), "{}", input);
+        }
+    }
+
+    /// Parses through the batch function, which uses the two row AVX2 path where available.
+    fn parse_dates(inputs: &[&str]) -> (Vec<i32>, Vec<u8>, Vec<RowParseError>) {
+        let mut output = vec![0; inputs.len()];
+        let mut validity = vec![0; inputs.len().div_ceil(8)];
+        let errors = parse_to_epoch_days_batch(inputs, &mut output, &mut validity);
+        (output, validity, errors)
+    }
+
+    #[test]
+    fn test_force_scalar() {
+        let _guard = lock_force_scalar();
+        force_scalar(true);
+        assert_eq!(simd_level(), SimdLevel::Scalar);
+        let scalar = INPUTS
+            .iter()
+            .map(|input| PackedTimestamp::from_rfc3339_str(input))
+            .collect::<Vec<_>>();
+        let formatted_scalar = format_to_rfc3339_bytes(2022, 8, 21, 17, 30, 15, 250, 120);
+        let dates_scalar = parse_dates(DATE_INPUTS);
+        let dates_shifted_scalar = parse_dates(&DATE_INPUTS[1..]);
+
+        force_scalar(false);
+        let detected = INPUTS
+            .iter()
+            .map(|input| PackedTimestamp::from_rfc3339_str(input))
+            .collect::<Vec<_>>();
+        let formatted_detected = format_to_rfc3339_bytes(2022, 8, 21, 17, 30, 15, 250, 120);
+        let dates_detected = parse_dates(DATE_INPUTS);
+        let dates_shifted_detected = parse_dates(&DATE_INPUTS[1..]);
+
+        assert_eq!(scalar, detected);
+        assert_eq!(dates_scalar, dates_detected);
+        assert_eq!(dates_shifted_scalar, dates_shifted_detected);
+        assert_eq!(formatted_scalar, formatted_detected);
+        assert_eq!(formatted_detected.as_str(), "2022-08-21T17:30:15.250+02:00");
+    }
+}
@@ -0,0 +1,465 @@
+use crate::{DurationError, Interval};
+use std::fmt::{Display, Formatter, Write};
+use std::str::FromStr;
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+const NANOS_PER_MINUTE: i128 = 60 * NANOS_PER_SECOND;
+const NANOS_PER_HOUR: i128 = 60 * NANOS_PER_MINUTE;
+
+/// Fractional digits after this are ignored, more are not needed for nanosecond precision of fractional hours.
+const MAX_FRACTIONAL_DIGITS: u32 = 18;
+
+/// How strictly ISO 8601 durations are parsed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DurationMode {
+    /// Only accepts the format described by ISO 8601: upper case designators in the order `Y`, `M`, `W`, `D`,
+    /// then `T` followed by `H`, `M`, `S`, with weeks only on their own and a fraction only on the last time component.
+    #[default]
+    Strict,
+    /// Additionally accepts lower case designators, a leading `+` or `-` sign for the whole duration,
+    /// a `-` sign for individual components like PostgreSQL, weeks combined with other components
+    /// and fractions on all time components.
+    Lenient,
+}
+
+impl Interval {
+    /// Parses an ISO 8601 duration like `P1Y2M10DT2H30M`, `PT0.250S` or `P3W`.
+    ///
+    /// Years are converted to 12 months and weeks to 7 days, so the date part keeps its calendar semantics
+    /// when added with `add_to`, for example `P1Y` added to 2024-02-29 is 2025-02-28. Hours, minutes and
+    /// seconds are converted to nanoseconds, fractions of years, months, weeks and days are not supported.
+    pub fn parse_iso8601_duration(input: &str, mode: DurationMode) -> Result<Self, DurationError> {
+        DurationParser {
+            bytes: input.as_bytes(),
+            position: 0,
+            lenient: mode == DurationMode::Lenient,
+            negative: false,
+        }
+        .duration()
+    }
+
+    /// Writes this interval as an ISO 8601 duration, for example `P1Y2M10DT2H30M` or `PT0.25S`.
+    ///
+    /// Months are written as years and months, and the time as hours, minutes and seconds with as many
+    /// fractional digits as needed. A zero interval is written as `PT0S`. If all components are negative
+    /// the duration is prefixed with `-`, if the components have different signs then each negative component is
+    /// prefixed with `-`. Both are extensions to ISO 8601 that can only be parsed with `DurationMode::Lenient`.
+    pub fn write_iso8601_duration<W: Write>(&self, mut writer: W) -> std::fmt::Result {
+        let (mut months, mut days, mut nanos) = (self.months as i64, self.days as i64, self.nanos as i128);
+        if *self == Interval::ZERO {
+            return writer.write_str("PT0S");
+        }
+        if months <= 0 && days <= 0 && nanos <= 0 {
+            writer.write_char('-')?;
+            (months, days, nanos) = (-months, -days, -nanos);
+        }
+        writer.write_char('P')?;
+        let (years, months) = (months / 12, months % 12);
+        if years != 0 {
+            write!(writer, "{}Y", years)?;
+        }
+        if months != 0 {
+            write!(writer, "{}M", months)?;
+        }
+        if days != 0 {
+            write!(writer, "{}D", days)?;
+        }
+        if nanos != 0 {
+            writer.write_char('T')?;
+            let hours = nanos / NANOS_PER_HOUR;
+            let minutes = nanos % NANOS_PER_HOUR / NANOS_PER_MINUTE;
+            let seconds = nanos % NANOS_PER_MINUTE;
+            if hours != 0 {
+                write!(writer, "{}H", hours)?;
+            }
+            if minutes != 0 {
+                write!(writer, "{}M", minutes)?;
+            }
+            if seconds != 0 {
+                if seconds < 0 {
+                    writer.write_char('-')?;
+                }
+                let seconds = seconds.unsigned_abs();
+                let (whole, fraction) = (seconds / NANOS_PER_SECOND as u128, seconds % NANOS_PER_SECOND as u128);
+                if fraction == 0 {
+                    write!(writer, "{}S", whole)?;
+                } else {
+                    let digits = format!("{:09}", fraction);
+                    write!(writer, "{}.{}S", whole, digits.trim_end_matches('0'))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Formats this interval as an ISO 8601 duration, see `write_iso8601_duration`.
+    pub fn to_iso8601_duration_string(&self) -> String {
+        let mut result = String::new();
+        // writing to a string can not fail
+        self.write_iso8601_duration(&mut result).unwrap();
+        result
+    }
+}
+
+/// Formats the interval as an ISO 8601 duration.
+impl Display for Interval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.write_iso8601_duration(f)
+    }
+}
+
+/// Parses a strict ISO 8601 duration.
+impl FromStr for Interval {
+    type Err = DurationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_iso8601_duration(s, DurationMode::Strict)
+    }
+}
+
+/// A number with an optional fraction, the fraction is stored as the digits and the number of digits.
+struct Number {
+    position: usize,
+    negative: bool,
+    whole: u64,
+    fraction: Option<(u64, u32)>,
+}
+
+impl Number {
+    fn to_nanos(&self, nanos_per_unit: i128) -> Option<i128> {
+        let mut nanos = (self.whole as i128).checked_mul(nanos_per_unit)?;
+        if let Some((digits, len)) = self.fraction {
+            nanos += digits as i128 * nanos_per_unit / 10_i128.pow(len);
+        }
+        Some(if self.negative { -nanos } else { nanos })
+    }
+
+    fn to_units(&self, units: i64) -> Option<i64> {
+        let value = i64::try_from(self.whole).ok()?.checked_mul(units)?;
+        Some(if self.negative { -value } else { value })
+    }
+}
+
+struct DurationParser<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    lenient: bool,
+    /// Whether the whole duration is negated by a leading `-` sign.
+    negative: bool,
+}
+
+impl DurationParser<'_> {
+    fn error(&self) -> DurationError {
+        if self.position < self.bytes.len() {
+            DurationError::InvalidChar { position: self.position }
+        } else {
+            DurationError::UnexpectedEnd
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        let ch = self.bytes.get(self.position).copied();
+        if self.lenient {
+            ch.map(|ch| ch.to_ascii_uppercase())
+        } else {
+            ch
+        }
+    }
+
+    fn expect(&mut self, ch: u8) -> Result<(), DurationError> {
+        if self.peek() != Some(ch) {
+            return Err(self.error());
+        }
+        self.position += 1;
+        Ok(())
+    }
+
+    fn duration(&mut self) -> Result<Interval, DurationError> {
+        // the sign is applied to each component while parsing, so that negating the minimum value is reported as an overflow
+        self.negative = match self.peek() {
+            Some(sign @ (b'+' | b'-')) if self.lenient => {
+                self.position += 1;
+                sign == b'-'
+            }
+            _ => false,
+        };
+        self.expect(b'P')?;
+
+        let mut months = 0_i64;
+        let mut days = 0_i64;
+        let mut nanos = 0_i128;
+        let mut components = 0;
+        let mut weeks = false;
+
+        // index of the next allowed designator, enforcing the order and that each designator is used at most once
+        let mut next = 0;
+        while self.peek().is_some() && self.peek() != Some(b'T') {
+            let number = self.number()?;
+            let index = match self.peek() {
+                Some(b'Y') => 0,
+                Some(b'M') => 1,
+                Some(b'W') => 2,
+                Some(b'D') => 3,
+                _ => return Err(self.error()),
+            };
+            if index < next || (!self.lenient && (weeks || (index == 2 && components > 0))) {
+                return Err(self.error());
+            }
+            if let Some(position) = number.fraction.map(|_| number.position) {
+                return Err(DurationError::InvalidFraction { position });
+            }
+            self.position += 1;
+            next = index + 1;
+            weeks |= index == 2;
+            components += 1;
+
+            let overflow = DurationError::Overflow {
+                position: number.position,
+            };
+            let (total, units) = match index {
+                0 => (&mut months, 12),
+                1 => (&mut months, 1),
+                2 => (&mut days, 7),
+                _ => (&mut days, 1),
+            };
+            *total = number
+                .to_units(units)
+                .and_then(|value| total.checked_add(value))
+                .filter(|total| i32::try_from(*total).is_ok())
+                .ok_or(overflow)?;
+        }
+
+        if self.peek() == Some(b'T') {
+            self.position += 1;
+            if self.peek().is_none() {
+                return Err(self.error());
+            }
+            let mut next = 0;
+            while self.peek().is_some() {
+                let number = self.number()?;
+                let (index, nanos_per_unit) = match self.peek() {
+                    Some(b'H') => (0, NANOS_PER_HOUR),
+                    Some(b'M') => (1, NANOS_PER_MINUTE),
+                    Some(b'S') => (2, NANOS_PER_SECOND),
+                    _ => return Err(self.error()),
+                };
+                if index < next || (!self.lenient && weeks) {
+                    return Err(self.error());
+                }
+                self.position += 1;
+                next = index + 1;
+                components += 1;
+
+                if number.fraction.is_some() && !self.lenient && self.peek().is_some() {
+                    return Err(DurationError::InvalidFraction {
+                        position: number.position,
+                    });
+                }
+                let overflow = DurationError::Overflow {
+                    position: number.position,
+                };
+                nanos += number.to_nanos(nanos_per_unit).ok_or(overflow)?;
+                if i64::try_from(nanos).is_err() {
+                    return Err(overflow);
+                }
+            }
+        }
+
+        if components == 0 {
+            return Err(self.error());
+        }
+
+        Ok(Interval::new(months as i32, days as i32, nanos as i64))
+    }
+
+    /// An unsigned number with an optional fraction separated by `.` or `,`, in lenient mode with an optional `-` sign.
+    /// The sign of the whole duration is already applied to the returned number.
+    fn number(&mut self) -> Result<Number, DurationError> {
+        let position = self.position;
+        let negative = self.lenient && self.peek() == Some(b'-');
+        if negative {
+            self.position += 1;
+        }
+        let negative = negative != self.negative;
+        let whole = self.digits(u32::MAX)?;
+        let fraction = if let Some(b'.' | b',') = self.peek() {
+            self.position += 1;
+            let start = self.position;
+            let digits = self.digits(MAX_FRACTIONAL_DIGITS)?;
+            let len = (self.position - start).min(MAX_FRACTIONAL_DIGITS as usize) as u32;
+            Some((digits, len))
+        } else {
+            None
+        };
+        Ok(Number {
+            position,
+            negative,
+            whole,
+            fraction,
+        })
+    }
+
+    /// At least one digit, digits after `max_digits` are skipped, which truncates fractions.
+    fn digits(&mut self, max_digits: u32) -> Result<u64, DurationError> {
+        let start = self.position;
+        let mut value = 0_u64;
+        while let Some(ch @ b'0'..=b'9') = self.peek() {
+            if self.position - start < max_digits as usize {
+                value = value
+                    .checked_mul(10)
+                    .and_then(|value| value.checked_add((ch - b'0') as u64))
+                    .ok_or(DurationError::Overflow { position: start })?;
+            }
+            self.position += 1;
+        }
+        if self.position == start {
+            return Err(self.error());
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DurationError, DurationMode, EpochDays, Interval};
+
+    const SECOND: i64 = 1_000_000_000;
+    const MINUTE: i64 = 60 * SECOND;
+    const HOUR: i64 = 60 * MINUTE;
+
+    fn strict(input: &str) -> Result<Interval, DurationError> {
+        Interval::parse_iso8601_duration(input, DurationMode::Strict)
+    }
+
+    fn lenient(input: &str) -> Result<Interval, DurationError> {
+        Interval::parse_iso8601_duration(input, DurationMode::Lenient)
+    }
+
+    #[test]
+    fn test_parse_strict() {
+        assert_eq!(strict("P1Y2M10DT2H30M"), Ok(Interval::new(14, 10, 2 * HOUR + 30 * MINUTE)));
+        assert_eq!(strict("PT0.250S"), Ok(Interval::new(0, 0, 250_000_000)));
+        assert_eq!(strict("PT0,5S"), Ok(Interval::new(0, 0, 500_000_000)));
+        assert_eq!(strict("P3W"), Ok(Interval::new(0, 21, 0)));
+        assert_eq!(strict("P1D"), Ok(Interval::new(0, 1, 0)));
+        assert_eq!(strict("PT36H"), Ok(Interval::new(0, 0, 36 * HOUR)));
+        assert_eq!(strict("PT1.5H"), Ok(Interval::new(0, 0, HOUR + 30 * MINUTE)));
+        assert_eq!(strict("PT1M0.000000001S"), Ok(Interval::new(0, 0, MINUTE + 1)));
+        assert_eq!(strict("P0D"), Ok(Interval::ZERO));
+        assert_eq!("P1Y".parse::<Interval>(), Ok(Interval::new(12, 0, 0)));
+    }
+
+    #[test]
+    fn test_parse_strict_errors() {
+        assert_eq!(strict(""), Err(DurationError::UnexpectedEnd));
+        assert_eq!(strict("P"), Err(DurationError::UnexpectedEnd));
+        assert_eq!(strict("PT"), Err(DurationError::UnexpectedEnd));
+        assert_eq!(strict("P1"), Err(DurationError::UnexpectedEnd));
+        assert_eq!(strict("1D"), Err(DurationError::InvalidChar { position: 0 }));
+        assert_eq!(strict("p1d"), Err(DurationError::InvalidChar { position: 0 }));
+        assert_eq!(strict("-P1D"), Err(DurationError::InvalidChar { position: 0 }));
+        assert_eq!(strict("P-1D"), Err(DurationError::InvalidChar { position: 1 }));
+        assert_eq!(strict("P1D2M"), Err(DurationError::InvalidChar { position: 4 }));
+        assert_eq!(strict("P1M1M"), Err(DurationError::InvalidChar { position: 4 }));
+        assert_eq!(strict("P1H"), Err(DurationError::InvalidChar { position: 2 }));
+        assert_eq!(strict("PT1D"), Err(DurationError::InvalidChar { position: 3 }));
+        assert_eq!(strict("P1DT"), Err(DurationError::UnexpectedEnd));
+        assert_eq!(strict("P1W2D"), Err(DurationError::InvalidChar { position: 4 }));
+        assert_eq!(strict("P1Y2W"), Err(DurationError::InvalidChar { position: 4 }));
+        assert_eq!(strict("P3WT1H"), Err(DurationError::InvalidChar { position: 5 }));
+        assert_eq!(strict("P1.5Y"), Err(DurationError::InvalidFraction { position: 1 }));
+        assert_eq!(strict("PT1.5H30M"), Err(DurationError::InvalidFraction { position: 2 }));
+        assert_eq!(strict("PT1.S"), Err(DurationError::InvalidChar { position: 4 }));
+        assert_eq!(strict("PT1S "), Err(DurationError::InvalidChar { position: 4 }));
+        assert_eq!(strict("P2147483648M"), Err(DurationError::Overflow { position: 1 }));
+        assert_eq!(strict("P178956971Y"), Err(DurationError::Overflow { position: 1 }));
+        assert_eq!(strict("PT2562048H"), Err(DurationError::Overflow { position: 2 }));
+        assert_eq!(strict("P99999999999999999999D"), Err(DurationError::Overflow { position: 1 }));
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        assert_eq!(lenient("p1y2m10dt2h30m"), Ok(Interval::new(14, 10, 2 * HOUR + 30 * MINUTE)));
+        assert_eq!(lenient("-P1D"), Ok(Interval::new(0, -1, 0)));
+        assert_eq!(lenient("+P1D"), Ok(Interval::new(0, 1, 0)));
+        assert_eq!(lenient("P1M-1D"), Ok(Interval::new(1, -1, 0)));
+        assert_eq!(lenient("-P1M-1DT-1S"), Ok(Interval::new(-1, 1, SECOND)));
+        assert_eq!(lenient("P1W2D"), Ok(Interval::new(0, 9, 0)));
+        assert_eq!(lenient("P3WT1H"), Ok(Interval::new(0, 21, HOUR)));
+        assert_eq!(lenient("PT1.5H30.5M"), Ok(Interval::new(0, 0, 2 * HOUR + 30 * SECOND)));
+        assert_eq!(lenient("PT0.1234567891234S"), Ok(Interval::new(0, 0, 123_456_789)));
+        assert_eq!(lenient("PT-0.5S"), Ok(Interval::new(0, 0, -SECOND / 2)));
+
+        assert_eq!(lenient("-P2147483647M"), Ok(Interval::new(-i32::MAX, 0, 0)));
+        assert_eq!(lenient("-P-2147483648M"), Err(DurationError::Overflow { position: 2 }));
+        assert_eq!(lenient("-P-2147483648D"), Err(DurationError::Overflow { position: 2 }));
+        assert_eq!(
+            lenient("-PT-9223372036.854775808S"),
+            Err(DurationError::Overflow { position: 3 })
+        );
+        assert_eq!(lenient("P-2147483648M"), Ok(Interval::new(i32::MIN, 0, 0)));
+        assert_eq!(lenient("PT-9223372036.854775808S"), Ok(Interval::new(0, 0, i64::MIN)));
+
+        assert_eq!(lenient("P1.5D"), Err(DurationError::InvalidFraction { position: 1 }));
+        assert_eq!(lenient("P1D1M"), Err(DurationError::InvalidChar { position: 4 }));
+        assert_eq!(lenient("--P1D"), Err(DurationError::InvalidChar { position: 1 }));
+        assert_eq!(lenient("P--1D"), Err(DurationError::InvalidChar { position: 2 }));
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(
+            Interval::new(14, 10, 2 * HOUR + 30 * MINUTE).to_iso8601_duration_string(),
+            "P1Y2M10DT2H30M"
+        );
+        assert_eq!(Interval::new(0, 0, 250_000_000).to_iso8601_duration_string(), "PT0.25S");
+        assert_eq!(Interval::new(0, 21, 0).to_iso8601_duration_string(), "P21D");
+        assert_eq!(
+            Interval::new(0, 0, 36 * HOUR + 1).to_iso8601_duration_string(),
+            "PT36H0.000000001S"
+        );
+        assert_eq!(Interval::new(12, 0, MINUTE).to_string(), "P1YT1M");
+        assert_eq!(Interval::ZERO.to_string(), "PT0S");
+        assert_eq!(Interval::new(-14, -1, -SECOND / 2).to_string(), "-P1Y2M1DT0.5S");
+        assert_eq!(Interval::new(1, -1, -SECOND / 2).to_string(), "P1M-1DT-0.5S");
+        assert_eq!(Interval::new(0, 1, -HOUR - 90 * SECOND).to_string(), "P1DT-1H-1M-30S");
+        assert_eq!(
+            Interval::new(i32::MIN, i32::MIN, i64::MIN).to_string(),
+            "-P178956970Y8M2147483648DT2562047H47M16.854775808S"
+        );
+    }
+
+    #[test]
+    fn test_format_roundtrip() {
+        for interval in [
+            Interval::new(14, 10, 2 * HOUR + 30 * MINUTE),
+            Interval::new(0, 0, 1),
+            Interval::new(-1, 0, 0),
+            Interval::new(1, -1, -SECOND / 2),
+            Interval::new(0, 1, -HOUR - 90 * SECOND),
+            Interval::new(i32::MAX, i32::MAX, i64::MAX),
+            Interval::new(i32::MIN + 1, i32::MIN + 1, i64::MIN + 1),
+            Interval::ZERO,
+        ] {
+            let formatted = interval.to_iso8601_duration_string();
+            assert_eq!(lenient(&formatted), Ok(interval), "{}", formatted);
+        }
+    }
+
+    #[test]
+    fn test_apply_parsed_duration() {
+        let interval = strict("P1Y").unwrap();
+        assert_eq!(
+            interval.add_to(EpochDays::from_ymd(2024, 2, 29)),
+            EpochDays::from_ymd(2025, 2, 28)
+        );
+        assert_eq!(
+            interval.sub_from(EpochDays::from_ymd(2024, 2, 29)),
+            EpochDays::from_ymd(2023, 2, 28)
+        );
+
+        let ts = EpochDays::from_ymd(2024, 1, 31).to_timestamp_millis();
+        let expected = EpochDays::from_ymd(2024, 3, 1).to_timestamp_millis() + 30 * 60 * 1000 + 250;
+        assert_eq!(strict("P1M1DT30M0.250S").unwrap().add_to(ts), expected);
+    }
+}
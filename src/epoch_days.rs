@@ -1,4 +1,8 @@
-use crate::MILLIS_PER_DAY;
+use crate::error::{ParseError, ParseResult};
+use crate::format::{format_to_iso_date_bytes, FormattedTimestamp};
+use crate::{Month, Weekday, MILLIS_PER_DAY};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
 // Conversions from/to number of days since the unix epoch.
 // Ported from <https://github.com/ThreeTen/threetenbp/blob/master/src/main/java/org/threeten/bp/LocalDate.java>
@@ -40,8 +44,6 @@ const SECONDS_PER_DAY: i32 = 86400;
 const DAYS_PER_CYCLE: i32 = 146097;
 const DAYS_0000_TO_1970: i32 = (DAYS_PER_CYCLE * 5) - (30 * 365 + 7);
 
-const SUPPORT_NEGATIVE_YEAR: bool = false;
-
 // stored as i32 instead of smaller types in order to access via vectorized gather instructions
 static DAYS_PER_MONTH: [[i32; 12]; 2] = [
     [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31],
@@ -50,12 +52,12 @@ static DAYS_PER_MONTH: [[i32; 12]; 2] = [
 
 /// branchless calculation of whether the given year is a leap year
 #[inline]
-fn is_leap_year(year: i32) -> bool {
+pub(crate) fn is_leap_year(year: i32) -> bool {
     ((year % 4) == 0) & ((year % 100) != 0) | ((year % 400) == 0)
 }
 
 #[inline]
-fn days_per_month(year: i32, zero_based_month: i32) -> i32 {
+pub(crate) fn days_per_month(year: i32, zero_based_month: i32) -> i32 {
     let is_leap = is_leap_year(year);
     let is_feb = zero_based_month == 1;
     let mut days = 30 + ((zero_based_month % 2) != (zero_based_month <= 6) as i32) as i32;
@@ -86,11 +88,8 @@ impl EpochDays {
         let m = month;
         let mut total = 365 * y;
 
-        total += if y < 0 && SUPPORT_NEGATIVE_YEAR {
-            -(y / -4 - y / -100 + y / -400)
-        } else {
-            (y + 3) / 4 - (y + 99) / 100 + (y + 399) / 400
-        };
+        // number of leap years before the given year, floor division makes this also work for negative years
+        total += (y + 3).div_euclid(4) - (y + 99).div_euclid(100) + (y + 399).div_euclid(400);
 
         total += ((367 * m - 362) / 12);
         total += day - 1;
@@ -109,26 +108,22 @@ impl EpochDays {
         let mut zero_day = epoch_days + DAYS_0000_TO_1970;
         // find the march-based year
         zero_day -= 60; // adjust to 0000-03-01 so leap day is at end of four year cycle
-        let mut adjust = 0;
-        if zero_day < 0 && SUPPORT_NEGATIVE_YEAR {
-            // adjust negative years to positive for calculation
-            let adjust_cycles = (zero_day + 1) / DAYS_PER_CYCLE - 1;
-            adjust = adjust_cycles * 400;
-            zero_day += -adjust_cycles * DAYS_PER_CYCLE;
-        }
+        // adjust negative years to positive for calculation,
+        // using a mask instead of a branch so that the calculation can be autovectorized
+        let negative_mask = 0_i32.wrapping_sub((zero_day < 0) as i32);
+        let adjust_cycles = ((zero_day + 1) / DAYS_PER_CYCLE - 1) & negative_mask;
+        let adjust = adjust_cycles * 400;
+        zero_day -= adjust_cycles * DAYS_PER_CYCLE;
+
+        // zero_day is now positive, masking the sign bit allows cheaper division by constants
         let mut year_est = (400 * zero_day + 591) / DAYS_PER_CYCLE;
-
-        if !SUPPORT_NEGATIVE_YEAR {
-            year_est &= i32::MAX;
-        }
+        year_est &= i32::MAX;
 
         let mut doy_est = zero_day - (365 * year_est + year_est / 4 - year_est / 100 + year_est / 400);
 
         // fix estimate
         year_est -= (doy_est < 0) as i32;
-        if !SUPPORT_NEGATIVE_YEAR {
-            year_est &= i32::MAX;
-        }
+        year_est &= i32::MAX;
 
         doy_est = zero_day - (365 * year_est + year_est / 4 - year_est / 100 + year_est / 400);
 
@@ -232,6 +227,39 @@ impl EpochDays {
         Self::from_ymd(y, (m - 1) / 3 * 3 + 1, 1)
     }
 
+    /// Truncates to the first day of the week, with weeks starting on `week_start`.
+    #[inline]
+    pub fn date_trunc_week(&self, week_start: Weekday) -> Self {
+        let days_since_start = (self.weekday().days_since_monday() - week_start.days_since_monday()).rem_euclid(7);
+        Self(self.0 - days_since_start)
+    }
+
+    /// Truncates to the first day of the ISO 8601 week-based year, which is the monday of week 1.
+    #[inline]
+    pub fn date_trunc_iso_year(&self) -> Self {
+        Self::from_ymd(self.extract_iso_year(), 1, 4).date_trunc_week(Weekday::Monday)
+    }
+
+    /// The thursday of the ISO 8601 week, which determines the week-based year.
+    #[inline]
+    fn iso_week_thursday(&self) -> Self {
+        Self(self.date_trunc_week(Weekday::Monday).0 + 3)
+    }
+
+    /// The ISO 8601 week-based year, which differs from the calendar year for some days around new year.
+    #[inline]
+    pub fn extract_iso_year(&self) -> i32 {
+        self.iso_week_thursday().extract_year()
+    }
+
+    /// The ISO 8601 week number from 1 to 53. Week 1 is the week containing the first thursday of the year.
+    #[inline]
+    pub fn extract_iso_week(&self) -> i32 {
+        let thursday = self.iso_week_thursday();
+        let jan1 = Self::from_ymd(thursday.extract_year(), 1, 1);
+        (thursday.0 - jan1.0) / 7 + 1
+    }
+
     #[inline]
     pub fn extract_year(&self) -> i32 {
         self.to_ymd().0
@@ -252,17 +280,148 @@ impl EpochDays {
         self.to_ymd().2
     }
 
+    /// The day of the year from 1 to 366.
+    #[inline]
+    pub fn extract_day_of_year(&self) -> i32 {
+        self.0 - Self::from_ymd(self.extract_year(), 1, 1).0 + 1
+    }
+
+    /// The day of the week from 0 for sunday to 6 for saturday.
+    #[inline]
+    pub fn extract_day_of_week(&self) -> i32 {
+        self.weekday().days_since_sunday()
+    }
+
+    /// The ISO 8601 day of the week from 1 for monday to 7 for sunday.
+    #[inline]
+    pub fn extract_iso_day_of_week(&self) -> i32 {
+        self.weekday().iso_number()
+    }
+
+    /// The year divided by 10, rounded down.
+    #[inline]
+    pub fn extract_decade(&self) -> i32 {
+        self.extract_year().div_euclid(10)
+    }
+
+    /// The century, where the 21st century starts with the year 2001.
+    /// Years before 1, with year 0 being 1 BC, have negative centuries starting with -1.
+    #[inline]
+    pub fn extract_century(&self) -> i32 {
+        let year = self.extract_year();
+        if year > 0 {
+            (year + 99) / 100
+        } else {
+            -((100 - year) / 100)
+        }
+    }
+
+    /// The millennium, where the 3rd millennium starts with the year 2001.
+    /// Years before 1, with year 0 being 1 BC, have negative millennia starting with -1.
+    #[inline]
+    pub fn extract_millennium(&self) -> i32 {
+        let year = self.extract_year();
+        if year > 0 {
+            (year + 999) / 1000
+        } else {
+            -((1000 - year) / 1000)
+        }
+    }
+
     #[inline]
     pub fn days_in_month(&self) -> i32 {
         let (y, m, _) = self.to_ymd();
-        days_per_month(y, m)
+        days_per_month(y, m - 1)
+    }
+
+    #[inline]
+    pub fn month(&self) -> Month {
+        // to_ymd always returns a month from 1 to 12
+        Month::from_number(self.extract_month()).unwrap()
+    }
+
+    #[inline]
+    pub fn weekday(&self) -> Weekday {
+        // 1970-01-01 was a thursday
+        Weekday::from_days_since_monday(self.0 + 3)
+    }
+
+    /// The first date after this one that falls on the given weekday, between 1 and 7 days later.
+    #[inline]
+    pub fn next_weekday(&self, weekday: Weekday) -> Self {
+        let days = (weekday.days_since_monday() - self.weekday().days_since_monday() - 1).rem_euclid(7) + 1;
+        Self(self.0 + days)
+    }
+
+    /// The last date before this one that falls on the given weekday, between 1 and 7 days earlier.
+    #[inline]
+    pub fn previous_weekday(&self, weekday: Weekday) -> Self {
+        let days = (self.weekday().days_since_monday() - weekday.days_since_monday() - 1).rem_euclid(7) + 1;
+        Self(self.0 - days)
+    }
+
+    /// The `n`-th occurrence of the weekday in the month of this date, with `n` starting at 1,
+    /// or `None` if the month does not have that many occurrences.
+    #[inline]
+    pub fn nth_weekday_of_month(&self, n: u32, weekday: Weekday) -> Option<Self> {
+        if n == 0 || n > 5 {
+            return None;
+        }
+        let first = self.date_trunc_month();
+        let first_occurrence = (weekday.days_since_monday() - first.weekday().days_since_monday()).rem_euclid(7);
+        let day = first_occurrence + (n as i32 - 1) * 7;
+        if day < first.days_in_month() {
+            Some(Self(first.0 + day))
+        } else {
+            None
+        }
+    }
+
+    /// The last occurrence of the weekday in the month of this date.
+    #[inline]
+    pub fn last_weekday_of_month(&self, weekday: Weekday) -> Self {
+        let last = Self(self.date_trunc_month().0 + self.days_in_month() - 1);
+        let days_since = (last.weekday().days_since_monday() - weekday.days_since_monday()).rem_euclid(7);
+        Self(last.0 - days_since)
+    }
+
+    /// Parses a date in the format `yyyy-mm-dd`, the year can also use the ISO 8601 expanded representation with a sign.
+    pub fn from_iso_date_bytes(input: &[u8]) -> ParseResult<Self> {
+        crate::dispatch::parse_date(input)
+    }
+
+    pub fn from_iso_date_str(input: &str) -> ParseResult<Self> {
+        Self::from_iso_date_bytes(input.as_bytes())
+    }
+
+    /// Formats the date as `yyyy-mm-dd`.
+    /// Years outside of the range 0 to 9999 are formatted using the ISO 8601 expanded representation with a sign.
+    #[inline]
+    pub fn to_iso_date_bytes(&self) -> FormattedTimestamp {
+        let (year, month, day) = self.to_ymd();
+        format_to_iso_date_bytes(year, month as u32, day as u32)
+    }
+}
+
+impl Display for EpochDays {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.to_iso_date_bytes().as_str())
+    }
+}
+
+impl FromStr for EpochDays {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        EpochDays::from_iso_date_str(s)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::epoch_days::{days_per_month, is_leap_year, DAYS_PER_MONTH};
-    use crate::EpochDays;
+    use crate::{DateTimeField, EpochDays, Expected, Month, ParseError, Weekday};
+    use chrono::{Datelike, NaiveDate};
 
     #[test]
     fn test_is_leap_year() {
@@ -307,6 +466,38 @@ mod tests {
         assert_eq!(19198, EpochDays::from_ymd(2022, 7, 25).0);
     }
 
+    #[test]
+    fn test_negative_years() {
+        assert_eq!(EpochDays::from_ymd(0, 1, 1).days(), -719528);
+        assert_eq!(EpochDays::from_ymd(-1, 12, 31).days(), -719529);
+        assert_eq!(EpochDays::from_ymd(-44, 3, 15).to_ymd(), (-44, 3, 15));
+        assert_eq!(EpochDays::from_ymd(-4, 2, 29).to_ymd(), (-4, 2, 29));
+        assert_eq!(EpochDays::from_ymd(-4, 2, 29).add_years(1).to_ymd(), (-3, 2, 28));
+        assert_eq!(EpochDays::from_ymd(-1, 12, 31).add_months(2).to_ymd(), (0, 2, 29));
+        assert_eq!(EpochDays::from_ymd(-9999, 1, 1).days(), -4371587);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_from_ymd_to_ymd_negative_years_chrono() {
+        use chrono::Datelike;
+
+        let start = EpochDays::from_ymd(-10000, 1, 1).days();
+        let end = EpochDays::from_ymd(10000, 1, 1).days();
+        let epoch = chrono::NaiveDate::from_ymd(1970, 1, 1);
+
+        for days in (start..end).step_by(7) {
+            let date = epoch + chrono::Duration::days(days as i64);
+            let expected = (date.year(), date.month() as i32, date.day() as i32);
+            assert_eq!(EpochDays::new(days).to_ymd(), expected, "{days}");
+            assert_eq!(
+                EpochDays::from_ymd(expected.0, expected.1, expected.2).days(),
+                days,
+                "{expected:?}"
+            );
+        }
+    }
+
     #[test]
     fn test_date_trunc_year_epoch_days() {
         assert_eq!(18993, EpochDays::new(19198).date_trunc_year().days());
@@ -424,4 +615,233 @@ mod tests {
         assert_eq!(4, EpochDays::from_ymd(2000, 10, 1).extract_quarter());
         assert_eq!(4, EpochDays::from_ymd(2000, 12, 31).extract_quarter());
     }
+
+    #[test]
+    fn test_extract_day_of_year_and_week() {
+        assert_eq!(1, EpochDays::from_ymd(2024, 1, 1).extract_day_of_year());
+        assert_eq!(60, EpochDays::from_ymd(2024, 2, 29).extract_day_of_year());
+        assert_eq!(366, EpochDays::from_ymd(2024, 12, 31).extract_day_of_year());
+        assert_eq!(365, EpochDays::from_ymd(-1, 12, 31).extract_day_of_year());
+
+        // thursday
+        assert_eq!(4, EpochDays::new(0).extract_day_of_week());
+        assert_eq!(4, EpochDays::new(0).extract_iso_day_of_week());
+        // sunday
+        assert_eq!(0, EpochDays::new(-4).extract_day_of_week());
+        assert_eq!(7, EpochDays::new(-4).extract_iso_day_of_week());
+        // monday
+        assert_eq!(1, EpochDays::new(-3).extract_day_of_week());
+        assert_eq!(1, EpochDays::new(-3).extract_iso_day_of_week());
+    }
+
+    #[test]
+    fn test_extract_decade_century_millennium() {
+        for (year, decade, century, millennium) in [
+            (2024, 202, 21, 3),
+            (2001, 200, 21, 3),
+            (2000, 200, 20, 2),
+            (1999, 199, 20, 2),
+            (1, 0, 1, 1),
+            (0, 0, -1, -1),
+            (-1, -1, -1, -1),
+            (-99, -10, -1, -1),
+            (-100, -10, -2, -1),
+            (-999, -100, -10, -1),
+            (-1000, -100, -11, -2),
+        ] {
+            let date = EpochDays::from_ymd(year, 6, 15);
+            assert_eq!(decade, date.extract_decade(), "{}", year);
+            assert_eq!(century, date.extract_century(), "{}", year);
+            assert_eq!(millennium, date.extract_millennium(), "{}", year);
+        }
+    }
+
+    #[test]
+    fn test_days_in_month() {
+        let expected = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+        for month in 1..=12 {
+            assert_eq!(
+                EpochDays::from_ymd(2023, month, 15).days_in_month(),
+                expected[month as usize - 1],
+                "{}",
+                month
+            );
+        }
+        assert_eq!(EpochDays::from_ymd(2024, 2, 1).days_in_month(), 29);
+        assert_eq!(EpochDays::from_ymd(-4, 2, 1).days_in_month(), 29);
+    }
+
+    #[test]
+    fn test_month_and_weekday() {
+        assert_eq!(EpochDays::from_ymd(2022, 8, 24).month(), Month::August);
+        assert_eq!(EpochDays::from_ymd(2022, 8, 24).weekday(), Weekday::Wednesday);
+        assert_eq!(EpochDays::new(0).weekday(), Weekday::Thursday);
+        assert_eq!(EpochDays::new(-4).weekday(), Weekday::Sunday);
+        assert_eq!(EpochDays::from_ymd(-44, 3, 15).weekday(), Weekday::Thursday);
+
+        for days in -1000..1000 {
+            let date = EpochDays::new(days);
+            let (year, month, day) = date.to_ymd();
+            let expected = NaiveDate::from_ymd(year, month as u32, day as u32).weekday();
+            assert_eq!(
+                date.weekday().days_since_monday(),
+                expected.num_days_from_monday() as i32,
+                "{}",
+                date
+            );
+        }
+    }
+
+    #[test]
+    fn test_next_and_previous_weekday() {
+        // wednesday
+        let date = EpochDays::from_ymd(2022, 8, 24);
+        assert_eq!(date.next_weekday(Weekday::Thursday), EpochDays::from_ymd(2022, 8, 25));
+        assert_eq!(date.next_weekday(Weekday::Wednesday), EpochDays::from_ymd(2022, 8, 31));
+        assert_eq!(date.next_weekday(Weekday::Monday), EpochDays::from_ymd(2022, 8, 29));
+        assert_eq!(date.previous_weekday(Weekday::Tuesday), EpochDays::from_ymd(2022, 8, 23));
+        assert_eq!(date.previous_weekday(Weekday::Wednesday), EpochDays::from_ymd(2022, 8, 17));
+        assert_eq!(date.previous_weekday(Weekday::Thursday), EpochDays::from_ymd(2022, 8, 18));
+
+        for days in -100..100 {
+            let date = EpochDays::new(days);
+            for weekday in [Weekday::Monday, Weekday::Wednesday, Weekday::Sunday] {
+                let next = date.next_weekday(weekday);
+                assert_eq!(next.weekday(), weekday);
+                assert!(next.days() > date.days() && next.days() <= date.days() + 7);
+                let previous = date.previous_weekday(weekday);
+                assert_eq!(previous.weekday(), weekday);
+                assert!(previous.days() < date.days() && previous.days() >= date.days() - 7);
+            }
+        }
+    }
+
+    #[test]
+    fn test_nth_and_last_weekday_of_month() {
+        let date = EpochDays::from_ymd(2022, 8, 24);
+        assert_eq!(
+            date.nth_weekday_of_month(3, Weekday::Friday),
+            Some(EpochDays::from_ymd(2022, 8, 19))
+        );
+        assert_eq!(
+            date.nth_weekday_of_month(1, Weekday::Monday),
+            Some(EpochDays::from_ymd(2022, 8, 1))
+        );
+        assert_eq!(
+            date.nth_weekday_of_month(5, Weekday::Monday),
+            Some(EpochDays::from_ymd(2022, 8, 29))
+        );
+        assert_eq!(date.nth_weekday_of_month(5, Weekday::Friday), None);
+        assert_eq!(date.nth_weekday_of_month(0, Weekday::Friday), None);
+        assert_eq!(date.nth_weekday_of_month(6, Weekday::Monday), None);
+        assert_eq!(date.last_weekday_of_month(Weekday::Friday), EpochDays::from_ymd(2022, 8, 26));
+        assert_eq!(
+            date.last_weekday_of_month(Weekday::Wednesday),
+            EpochDays::from_ymd(2022, 8, 31)
+        );
+        // february 2015 started on a sunday and had exactly 4 weeks
+        let date = EpochDays::from_ymd(2015, 2, 10);
+        assert_eq!(
+            date.nth_weekday_of_month(4, Weekday::Saturday),
+            Some(EpochDays::from_ymd(2015, 2, 28))
+        );
+        assert_eq!(date.nth_weekday_of_month(5, Weekday::Sunday), None);
+        assert_eq!(date.last_weekday_of_month(Weekday::Sunday), EpochDays::from_ymd(2015, 2, 22));
+    }
+
+    #[test]
+    fn test_date_trunc_week() {
+        // wednesday
+        let date = EpochDays::from_ymd(2022, 8, 24);
+        assert_eq!(date.date_trunc_week(Weekday::Monday), EpochDays::from_ymd(2022, 8, 22));
+        assert_eq!(date.date_trunc_week(Weekday::Sunday), EpochDays::from_ymd(2022, 8, 21));
+        assert_eq!(date.date_trunc_week(Weekday::Wednesday), date);
+        assert_eq!(date.date_trunc_week(Weekday::Thursday), EpochDays::from_ymd(2022, 8, 18));
+        assert_eq!(EpochDays::new(0).date_trunc_week(Weekday::Monday), EpochDays::new(-3));
+        assert_eq!(EpochDays::new(-1).date_trunc_week(Weekday::Sunday), EpochDays::new(-4));
+    }
+
+    #[test]
+    fn test_extract_iso_week() {
+        // 2021-01-03 belongs to the last week of 2020
+        assert_eq!(EpochDays::from_ymd(2021, 1, 3).extract_iso_week(), 53);
+        assert_eq!(EpochDays::from_ymd(2021, 1, 3).extract_iso_year(), 2020);
+        assert_eq!(EpochDays::from_ymd(2021, 1, 4).extract_iso_week(), 1);
+        assert_eq!(EpochDays::from_ymd(2021, 1, 4).extract_iso_year(), 2021);
+        // 2024-12-30 belongs to the first week of 2025
+        assert_eq!(EpochDays::from_ymd(2024, 12, 30).extract_iso_week(), 1);
+        assert_eq!(EpochDays::from_ymd(2024, 12, 30).extract_iso_year(), 2025);
+
+        assert_eq!(
+            EpochDays::from_ymd(2021, 1, 3).date_trunc_iso_year(),
+            EpochDays::from_ymd(2019, 12, 30)
+        );
+        assert_eq!(
+            EpochDays::from_ymd(2024, 12, 31).date_trunc_iso_year(),
+            EpochDays::from_ymd(2024, 12, 30)
+        );
+        assert_eq!(
+            EpochDays::from_ymd(2024, 12, 29).date_trunc_iso_year(),
+            EpochDays::from_ymd(2024, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_iso_week_chrono() {
+        for days in (-800_000..800_000).step_by(3) {
+            let date = EpochDays::new(days);
+            let (year, month, day) = date.to_ymd();
+            let iso_week = NaiveDate::from_ymd(year, month as u32, day as u32).iso_week();
+            assert_eq!(date.extract_iso_week(), iso_week.week() as i32, "{}", date);
+            assert_eq!(date.extract_iso_year(), iso_week.year(), "{}", date);
+
+            let iso_year_start = date.date_trunc_iso_year();
+            assert_eq!(iso_year_start.extract_iso_week(), 1, "{}", date);
+            assert_eq!(iso_year_start.extract_iso_year(), iso_week.year(), "{}", date);
+            assert_eq!(iso_year_start.date_trunc_week(Weekday::Monday), iso_year_start, "{}", date);
+        }
+    }
+
+    #[test]
+    fn test_from_iso_date_str() {
+        assert_eq!(Ok(EpochDays::new(0)), EpochDays::from_iso_date_str("1970-01-01"));
+        assert_eq!(
+            Ok(EpochDays::from_ymd(2022, 7, 25)),
+            EpochDays::from_iso_date_str("2022-07-25")
+        );
+        assert_eq!(
+            Ok(EpochDays::from_ymd(-44, 3, 15)),
+            EpochDays::from_iso_date_str("-0044-03-15")
+        );
+        assert_eq!(Ok(EpochDays::from_ymd(2024, 2, 29)), "2024-02-29".parse::<EpochDays>());
+        assert_eq!(
+            Err(ParseError::InvalidValue(DateTimeField::Day)),
+            EpochDays::from_iso_date_str("2023-02-29")
+        );
+        assert_eq!(
+            Err(ParseError::InvalidChar {
+                position: 4,
+                byte: b'/',
+                expected: Expected::Char(b'-')
+            }),
+            EpochDays::from_iso_date_str("2023/02/01")
+        );
+    }
+
+    #[test]
+    fn test_to_iso_date_bytes() {
+        assert_eq!("1970-01-01", EpochDays::new(0).to_iso_date_bytes().as_str());
+        assert_eq!("2022-07-25", EpochDays::from_ymd(2022, 7, 25).to_iso_date_bytes().as_str());
+        assert_eq!("-0044-03-15", EpochDays::from_ymd(-44, 3, 15).to_iso_date_bytes().as_str());
+        assert_eq!("+10000-01-01", EpochDays::from_ymd(10000, 1, 1).to_iso_date_bytes().as_str());
+        assert_eq!("2024-02-29", EpochDays::from_ymd(2024, 2, 29).to_string());
+    }
+
+    #[test]
+    fn test_iso_date_roundtrip() {
+        for days in (-800_000..800_000).step_by(37) {
+            let date = EpochDays::new(days);
+            assert_eq!(Ok(date), date.to_string().parse::<EpochDays>());
+        }
+    }
 }
@@ -1,9 +1,270 @@
+use std::fmt::{Display, Formatter};
+
+/// The token that was expected at the position of an invalid character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Expected {
+    /// An ascii digit.
+    Digit,
+    /// A specific separator character like `-`, `:` or `.`.
+    Char(u8),
+    /// Either one of two characters, like `T` or a space between date and time.
+    OneOf(u8, u8),
+    /// `Z` for utc or the sign of a timezone offset.
+    OffsetSign,
+    /// The english name of a month, either abbreviated or in full, depending on the pattern.
+    MonthName,
+    /// The english name of a weekday, either abbreviated or in full, depending on the pattern.
+    WeekdayName,
+    /// `AM` or `PM`, in any case.
+    AmPm,
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expected::Digit => f.write_str("a digit"),
+            Expected::Char(ch) => write!(f, "'{}'", ch.escape_ascii()),
+            Expected::OneOf(first, second) => write!(f, "'{}' or '{}'", first.escape_ascii(), second.escape_ascii()),
+            Expected::OffsetSign => f.write_str("'Z', '+' or '-'"),
+            Expected::MonthName => f.write_str("a month name"),
+            Expected::WeekdayName => f.write_str("a weekday name"),
+            Expected::AmPm => f.write_str("'AM' or 'PM'"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ParseError {
+    /// The input ended at the given byte offset before the timestamp was complete.
     InvalidLen(usize),
-    InvalidChar(usize),
-    TrailingChar(usize),
-    InvalidValue,
+    /// An unexpected character at the given byte offset.
+    #[non_exhaustive]
+    InvalidChar { position: usize, byte: u8, expected: Expected },
+    /// Additional characters after a complete timestamp, starting at the given byte offset.
+    #[non_exhaustive]
+    TrailingChar { position: usize, byte: u8 },
+    /// The syntax was valid, but the value of a component is outside of its valid range.
+    InvalidValue(DateTimeField),
+}
+
+impl ParseError {
+    /// The byte offset in the input where parsing failed, if the error is not caused by an invalid value.
+    pub fn position(&self) -> Option<usize> {
+        match self {
+            ParseError::InvalidLen(position) => Some(*position),
+            ParseError::InvalidChar { position, .. } | ParseError::TrailingChar { position, .. } => Some(*position),
+            ParseError::InvalidValue(_) => None,
+        }
+    }
+
+    /// The component that failed validation, if the error is caused by an invalid value.
+    pub fn field(&self) -> Option<DateTimeField> {
+        match self {
+            ParseError::InvalidValue(field) => Some(*field),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidLen(position) => write!(f, "unexpected end of input at byte {}", position),
+            ParseError::InvalidChar {
+                position,
+                byte,
+                expected,
+            } => write!(
+                f,
+                "invalid character '{}' at byte {}, expected {}",
+                byte.escape_ascii(),
+                position,
+                expected
+            ),
+            ParseError::TrailingChar { position, byte } => {
+                write!(
+                    f,
+                    "unexpected trailing character '{}' at byte {}",
+                    byte.escape_ascii(),
+                    position
+                )
+            }
+            ParseError::InvalidValue(field) => write!(f, "invalid value for {}", field),
+        }
+    }
 }
 
+impl std::error::Error for ParseError {}
+
 pub type ParseResult<T> = std::result::Result<T, ParseError>;
+
+/// Error for a single row when parsing a batch of timestamps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowParseError {
+    pub row: usize,
+    pub error: ParseError,
+}
+
+impl Display for RowParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "row {}: {}", self.row, self.error)
+    }
+}
+
+impl std::error::Error for RowParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Error returned when compiling a strftime pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternError {
+    /// A specifier that is not supported, starting with the `%` at the given byte offset.
+    UnsupportedSpecifier { position: usize, specifier: char },
+    /// The pattern ended after the `%` at the given byte offset, before the specifier was complete.
+    IncompleteSpecifier { position: usize },
+}
+
+impl Display for PatternError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatternError::UnsupportedSpecifier { position, specifier } => {
+                write!(
+                    f,
+                    "unsupported specifier '{}' at byte {}",
+                    specifier.escape_default(),
+                    position
+                )
+            }
+            PatternError::IncompleteSpecifier { position } => write!(f, "incomplete specifier at byte {}", position),
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+/// Error returned when loading a time zone from TZif data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeZoneError {
+    /// The data does not start with the `TZif` magic bytes.
+    InvalidMagic,
+    /// The version byte of the header is not one of the supported versions.
+    UnsupportedVersion(u8),
+    /// The data ended before all records announced in the header were read.
+    UnexpectedEnd,
+    /// The data is structurally invalid, for example because of an out of range index.
+    InvalidData(&'static str),
+    /// The zone name is empty or not a relative path below the zoneinfo directory.
+    InvalidName,
+    /// Reading the zoneinfo file failed.
+    Io(std::io::ErrorKind),
+    /// The POSIX TZ rule string, either given directly or as the footer of TZif data, is invalid at the given byte offset.
+    InvalidRule { position: usize },
+}
+
+impl Display for TimeZoneError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimeZoneError::InvalidMagic => f.write_str("invalid TZif magic"),
+            TimeZoneError::UnsupportedVersion(version) => write!(f, "unsupported TZif version '{}'", version.escape_ascii()),
+            TimeZoneError::UnexpectedEnd => f.write_str("unexpected end of TZif data"),
+            TimeZoneError::InvalidData(reason) => write!(f, "invalid TZif data: {}", reason),
+            TimeZoneError::InvalidName => f.write_str("invalid time zone name"),
+            TimeZoneError::Io(kind) => write!(f, "could not read time zone: {}", kind),
+            TimeZoneError::InvalidRule { position } => write!(f, "invalid POSIX TZ rule at byte {}", position),
+        }
+    }
+}
+
+impl std::error::Error for TimeZoneError {}
+
+impl From<std::io::Error> for TimeZoneError {
+    fn from(error: std::io::Error) -> Self {
+        TimeZoneError::Io(error.kind())
+    }
+}
+
+/// Error returned when parsing an ISO 8601 duration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DurationError {
+    /// An unexpected character at the given byte offset, for example a designator that is out of order.
+    InvalidChar { position: usize },
+    /// The input ended before the duration was complete.
+    UnexpectedEnd,
+    /// A fraction on the number starting at the given byte offset, which is not allowed for this component.
+    InvalidFraction { position: usize },
+    /// The number starting at the given byte offset does not fit into the interval.
+    Overflow { position: usize },
+}
+
+impl Display for DurationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DurationError::InvalidChar { position } => write!(f, "invalid character in duration at byte {}", position),
+            DurationError::UnexpectedEnd => f.write_str("unexpected end of duration"),
+            DurationError::InvalidFraction { position } => write!(f, "fraction not allowed in duration at byte {}", position),
+            DurationError::Overflow { position } => write!(f, "duration out of range at byte {}", position),
+        }
+    }
+}
+
+impl std::error::Error for DurationError {}
+
+/// The component of a date or timestamp that failed validation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DateTimeField {
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    Millisecond,
+    Nanosecond,
+    OffsetMinutes,
+}
+
+impl Display for DateTimeField {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            DateTimeField::Year => "year",
+            DateTimeField::Month => "month",
+            DateTimeField::Day => "day",
+            DateTimeField::Hour => "hour",
+            DateTimeField::Minute => "minute",
+            DateTimeField::Second => "second",
+            DateTimeField::Millisecond => "millisecond",
+            DateTimeField::Nanosecond => "nanosecond",
+            DateTimeField::OffsetMinutes => "timezone offset",
+        })
+    }
+}
+
+/// Error returned by the checked constructors if a component is outside of its valid range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeError(pub DateTimeField);
+
+impl RangeError {
+    #[inline]
+    pub fn field(&self) -> DateTimeField {
+        self.0
+    }
+}
+
+impl Display for RangeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is out of range", self.0)
+    }
+}
+
+impl std::error::Error for RangeError {}
+
+impl From<RangeError> for ParseError {
+    fn from(error: RangeError) -> Self {
+        ParseError::InvalidValue(error.0)
+    }
+}
@@ -0,0 +1,19 @@
+//! Test data shared between modules, TZif files from `testdata/zoneinfo` and helpers for timestamps on given dates.
+
+use crate::EpochDays;
+
+pub(crate) const BERLIN: &[u8] = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/zoneinfo/Europe/Berlin"));
+pub(crate) const NEW_YORK: &[u8] = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/zoneinfo/America/New_York"));
+pub(crate) const LORD_HOWE: &[u8] = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/zoneinfo/Australia/Lord_Howe"));
+pub(crate) const SAO_PAULO: &[u8] = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/zoneinfo/America/Sao_Paulo"));
+pub(crate) const KOLKATA: &[u8] = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/zoneinfo/Asia/Kolkata"));
+
+/// Milliseconds since the unix epoch of the given date at midnight utc.
+pub(crate) fn date_millis(year: i32, month: i32, day: i32) -> i64 {
+    EpochDays::from_ymd(year, month, day).to_timestamp_millis()
+}
+
+/// Milliseconds since the unix epoch of the given date at 12:34:56.789 utc, a time of day with all fields set.
+pub(crate) fn date_time_millis(year: i32, month: i32, day: i32) -> i64 {
+    date_millis(year, month, day) + 45_296_789
+}
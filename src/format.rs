@@ -1,4 +1,7 @@
 #[cfg(target_arch = "x86_64")]
+use crate::datetime::DateTimeComponents;
+use crate::dispatch::{format_utc_to_slice, format_with_offset_to_slice};
+#[cfg(target_arch = "x86_64")]
 use std::arch::x86_64::*;
 
 const PATTERN_COMPLETE: &str = "0000-00-00T00:00:00.000Z00:00:00";
@@ -10,6 +13,76 @@ const _: () = {
     assert!(PATTERN_AFTER_YEAR.len() == 16);
 };
 
+/// Maximum length of a formatted rfc 3339 timestamp with nanoseconds, a `+HH:MM` offset
+/// and an expanded year representation with sign and up to 10 digits.
+const MAX_FORMATTED_LEN: usize = 42;
+
+/// A formatted timestamp, stored inline without requiring an allocation.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct FormattedTimestamp {
+    buffer: [u8; MAX_FORMATTED_LEN],
+    len: usize,
+}
+
+impl FormattedTimestamp {
+    #[inline]
+    pub(crate) fn new(buffer: [u8; MAX_FORMATTED_LEN], len: usize) -> Self {
+        debug_assert!(len <= MAX_FORMATTED_LEN);
+        Self { buffer, len }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer[..self.len]
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        #[cfg(not(debug_assertions))]
+        {
+            unsafe { std::str::from_utf8_unchecked(self.as_bytes()) }
+        }
+        #[cfg(debug_assertions)]
+        {
+            std::str::from_utf8(self.as_bytes()).expect("utf8 string")
+        }
+    }
+}
+
+impl AsRef<[u8]> for FormattedTimestamp {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl AsRef<str> for FormattedTimestamp {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl std::fmt::Display for FormattedTimestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::fmt::Debug for FormattedTimestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
 #[inline]
 #[cfg(target_arch = "x86_64")]
 #[target_feature(enable = "sse2,ssse3,sse4.1")]
@@ -72,6 +145,172 @@ pub unsafe fn format_simd_mul_to_slice(
     //unsafe { asm!("#LLVM-MCA-END format_simd_mul") };
 }
 
+/// Same as `format_simd_mul_to_slice`, but replaces the trailing `Z` with the timezone offset
+/// formatted as `+HH:MM` or `-HH:MM` if it is not zero. The slice needs to have room for 29 bytes.
+/// Returns the number of bytes written.
+#[inline]
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2,ssse3,sse4.1")]
+#[doc(hidden)] // used in benchmarks
+pub unsafe fn format_simd_mul_with_offset_to_slice(
+    slice: &mut [u8],
+    year: u32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    millisecond: u32,
+    offset_minutes: i32,
+) -> usize {
+    format_simd_mul_to_slice(slice, year, month, day, hour, minute, second, millisecond);
+
+    if offset_minutes == 0 {
+        return 24;
+    }
+
+    let slice = &mut slice[23..29];
+    let offset = offset_minutes.unsigned_abs();
+    let sign = if offset_minutes < 0 { b'-' } else { b'+' };
+
+    let input = _mm_setr_epi16((offset / 60) as i16, (offset % 60) as i16, 0, 0, 0, 0, 0, 0);
+
+    // same digit conversion as for the date and time fields above
+    let tens = _mm_mulhi_epu16(input, _mm_set1_epi16(52429_u16 as i16));
+    let tens = _mm_srli_epi16(tens, 3);
+    let tens_times10 = _mm_mullo_epi16(tens, _mm_set1_epi16(10));
+    let ones = _mm_sub_epi16(input, tens_times10);
+    let fmt = _mm_or_si128(_mm_slli_epi16(tens, 8), ones);
+
+    let fmt = _mm_shuffle_epi8(fmt, _mm_setr_epi8(-1, 1, 0, -1, 3, 2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1));
+    let pattern = _mm_setr_epi8(
+        sign as i8, b'0' as i8, b'0' as i8, b':' as i8, b'0' as i8, b'0' as i8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    );
+    let fmt = _mm_or_si128(fmt, pattern);
+
+    let mut buffer = [0_u8; 8];
+    _mm_storel_epi64(buffer.as_mut_ptr() as *mut __m128i, fmt);
+    slice.copy_from_slice(&buffer[0..6]);
+
+    29
+}
+
+/// Converts each 16 bit lane containing a value below 100 into two decimal digits,
+/// with the tens in the high byte and the ones in the low byte.
+#[inline]
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+unsafe fn neon_two_digits(input: std::arch::aarch64::uint16x8_t) -> std::arch::aarch64::uint8x16_t {
+    use std::arch::aarch64::*;
+
+    // divide by 10 by reciprocal multiplication, exact for all values below 179
+    let tens = vshrq_n_u16::<10>(vmulq_n_u16(input, 103));
+    // remainder of division by 10
+    let ones = vmlsq_n_u16(input, tens, 10);
+
+    // merge into bytes
+    vreinterpretq_u8_u16(vorrq_u16(vshlq_n_u16::<8>(tens), ones))
+}
+
+/// NEON port of `format_simd_mul_to_slice`.
+#[inline]
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+#[doc(hidden)] // used in benchmarks
+pub unsafe fn format_neon_to_slice(
+    slice: &mut [u8],
+    year: u32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    millisecond: u32,
+) {
+    use std::arch::aarch64::*;
+
+    const SHUFFLE_HI: [u8; 16] = [15, 14, 13, 12, 0xFF, 11, 10, 0xFF, 9, 8, 0xFF, 7, 6, 0xFF, 5, 4];
+    const SHUFFLE_LO: [u8; 16] = [
+        0xFF, 3, 2, 0xFF, 1, 0, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+    ];
+
+    let slice = &mut slice[0..24];
+    let year = year as u16;
+
+    let input: [u16; 8] = [
+        (millisecond / 10) as u16,
+        second as u16,
+        minute as u16,
+        hour as u16,
+        day as u16,
+        month as u16,
+        year % 100,
+        year / 100,
+    ];
+    let fmt = neon_two_digits(vld1q_u16(input.as_ptr()));
+
+    // broadcast to allow room for separators, out of range indices result in zero
+    let fmt_lo = vqtbl1q_u8(fmt, vld1q_u8(SHUFFLE_LO.as_ptr()));
+    let fmt_hi = vqtbl1q_u8(fmt, vld1q_u8(SHUFFLE_HI.as_ptr()));
+
+    // insert the last digit of the milliseconds now that we have room, the first two digits were formatted as a pair
+    let fmt_lo = vsetq_lane_u8::<6>((millisecond % 10) as u8, fmt_lo);
+
+    // add '0' and separator ascii values
+    let pattern_lo = vld1q_u8(PATTERN_COMPLETE.as_ptr().add(16));
+    let pattern_hi = vld1q_u8(PATTERN_COMPLETE.as_ptr().add(0));
+    let fmt_lo = vorrq_u8(fmt_lo, pattern_lo);
+    let fmt_hi = vorrq_u8(fmt_hi, pattern_hi);
+
+    vst1q_u8(slice.as_mut_ptr(), fmt_hi);
+    vst1_u8(slice.as_mut_ptr().add(16), vget_low_u8(fmt_lo));
+}
+
+/// NEON port of `format_simd_mul_with_offset_to_slice`.
+/// The slice needs to have room for 29 bytes, returns the number of bytes written.
+#[inline]
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+#[doc(hidden)] // used in benchmarks
+pub unsafe fn format_neon_with_offset_to_slice(
+    slice: &mut [u8],
+    year: u32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    millisecond: u32,
+    offset_minutes: i32,
+) -> usize {
+    use std::arch::aarch64::*;
+
+    const SHUFFLE: [u8; 8] = [0xFF, 1, 0, 0xFF, 3, 2, 0xFF, 0xFF];
+
+    format_neon_to_slice(slice, year, month, day, hour, minute, second, millisecond);
+
+    if offset_minutes == 0 {
+        return 24;
+    }
+
+    let slice = &mut slice[23..29];
+    let offset = offset_minutes.unsigned_abs();
+    let sign = if offset_minutes < 0 { b'-' } else { b'+' };
+
+    let input: [u16; 8] = [(offset / 60) as u16, (offset % 60) as u16, 0, 0, 0, 0, 0, 0];
+    let fmt = neon_two_digits(vld1q_u16(input.as_ptr()));
+
+    let fmt = vqtbl1_u8(fmt, vld1_u8(SHUFFLE.as_ptr()));
+    let pattern: [u8; 8] = [sign, b'0', b'0', b':', b'0', b'0', 0, 0];
+    let fmt = vorr_u8(fmt, vld1_u8(pattern.as_ptr()));
+
+    let mut buffer = [0_u8; 8];
+    vst1_u8(buffer.as_mut_ptr(), fmt);
+    slice.copy_from_slice(&buffer[0..6]);
+
+    29
+}
+
 #[inline]
 #[cfg(target_arch = "x86_64")]
 #[target_feature(enable = "sse2,ssse3")]
@@ -152,6 +391,63 @@ unsafe fn simd_double_dabble_256(numbers: &[u16; 16]) -> __m256i {
     res
 }
 
+/// Formats two utc timestamps with millisecond precision into the first 48 bytes of the slice, using one
+/// 256 bit register for the digits of both. The years have to be in the range from 0 to 9999.
+#[inline]
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn format_simd_dd_x2_to_slice(slice: &mut [u8], first: &DateTimeComponents, second: &DateTimeComponents) {
+    let slice = &mut slice[0..48];
+
+    let numbers = [
+        (first.year / 100) as u16,
+        (first.year % 100) as u16,
+        first.month as u16,
+        first.day as u16,
+        first.hour as u16,
+        first.minute as u16,
+        first.second as u16,
+        (first.millisecond() / 10) as u16,
+        (second.year / 100) as u16,
+        (second.year % 100) as u16,
+        second.month as u16,
+        second.day as u16,
+        second.hour as u16,
+        second.minute as u16,
+        second.second as u16,
+        (second.millisecond() / 10) as u16,
+    ];
+
+    // two digits per lane, with the tens in the low byte
+    let digits = simd_double_dabble_256(&numbers);
+
+    // the shuffle operates separately on each 128 bit lane, so both timestamps use the same indices
+    let fmt_hi = _mm256_shuffle_epi8(
+        digits,
+        _mm256_broadcastsi128_si256(_mm_setr_epi8(0, 1, 2, 3, -1, 4, 5, -1, 6, 7, -1, 8, 9, -1, 10, 11)),
+    );
+    let fmt_lo = _mm256_shuffle_epi8(
+        digits,
+        _mm256_broadcastsi128_si256(_mm_setr_epi8(-1, 12, 13, -1, 14, 15, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1)),
+    );
+
+    // insert the last digit of the milliseconds
+    let fmt_lo = _mm256_insert_epi8::<6>(fmt_lo, (first.millisecond() % 10) as i8);
+    let fmt_lo = _mm256_insert_epi8::<22>(fmt_lo, (second.millisecond() % 10) as i8);
+
+    // add '0' and separator ascii values
+    let pattern_hi = _mm256_broadcastsi128_si256(_mm_loadu_si128(PATTERN_COMPLETE.as_ptr() as *const _));
+    let pattern_lo = _mm256_broadcastsi128_si256(_mm_loadu_si128(PATTERN_COMPLETE.as_ptr().add(16) as *const _));
+    let fmt_hi = _mm256_or_si256(fmt_hi, pattern_hi);
+    let fmt_lo = _mm256_or_si256(fmt_lo, pattern_lo);
+
+    let ptr = slice.as_mut_ptr();
+    _mm_storeu_si128(ptr as *mut __m128i, _mm256_castsi256_si128(fmt_hi));
+    _mm_storel_epi64(ptr.add(16) as *mut __m128i, _mm256_castsi256_si128(fmt_lo));
+    _mm_storeu_si128(ptr.add(24) as *mut __m128i, _mm256_extracti128_si256::<1>(fmt_hi));
+    _mm_storel_epi64(ptr.add(40) as *mut __m128i, _mm256_extracti128_si256::<1>(fmt_lo));
+}
+
 /// formats the timestamp into the output buffer including separator chars, starting with the dash before the month and ending with a dot after the seconds.
 /// Example: -MM-ddThh:mm:ss.
 #[inline]
@@ -306,15 +602,344 @@ pub fn format_to_rfc3339_utc_bytes(
     millisecond: u32,
 ) -> [u8; 24] {
     let mut buffer = [0_u8; 24];
-    #[cfg(all(not(miri), target_feature = "sse4.1"))]
-    unsafe {
-        format_simd_mul_to_slice(&mut buffer, year, month, day, hour, minute, second, millisecond);
+    format_utc_to_slice(&mut buffer, year, month, day, hour, minute, second, millisecond);
+    buffer
+}
+
+/// Formats the timezone offset into the output buffer, as `Z` for a zero offset and as `+HH:MM` or `-HH:MM` otherwise.
+/// Returns the number of bytes written.
+#[inline]
+#[doc(hidden)] // used in benchmarks
+pub fn format_offset_scalar_to_slice(slice: &mut [u8], offset_minutes: i32) -> usize {
+    if offset_minutes == 0 {
+        slice[0] = b'Z';
+        return 1;
     }
-    #[cfg(not(all(not(miri), target_feature = "sse4.1")))]
-    {
-        format_scalar_to_slice(&mut buffer, year, month, day, hour, minute, second, millisecond);
+
+    let slice = &mut slice[0..6];
+    let offset = offset_minutes.unsigned_abs();
+    let offset_hour = offset / 60;
+    let offset_minute = offset % 60;
+
+    slice[0] = if offset_minutes < 0 { b'-' } else { b'+' };
+
+    slice[1] = (b'0' + ((offset_hour / 10 % 10) as u8));
+    slice[2] = (b'0' + ((offset_hour % 10) as u8));
+
+    slice[3] = b':';
+
+    slice[4] = (b'0' + ((offset_minute / 10) as u8));
+    slice[5] = (b'0' + ((offset_minute % 10) as u8));
+
+    6
+}
+
+/// Same as `format_scalar_to_slice`, but replaces the trailing `Z` with the timezone offset
+/// formatted as `+HH:MM` or `-HH:MM` if it is not zero. The slice needs to have room for 29 bytes.
+/// Returns the number of bytes written.
+#[inline]
+#[doc(hidden)] // used in benchmarks
+pub fn format_scalar_with_offset_to_slice(
+    slice: &mut [u8],
+    year: u32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    millisecond: u32,
+    offset_minutes: i32,
+) -> usize {
+    format_scalar_to_slice(slice, year, month, day, hour, minute, second, millisecond);
+    23 + format_offset_scalar_to_slice(&mut slice[23..], offset_minutes)
+}
+
+/// Formats the fractional digits following the milliseconds, that is the micro- and nanosecond part
+/// of the given `nanosecond` value. Returns the number of bytes written.
+#[inline]
+fn format_sub_millis_scalar_to_slice(slice: &mut [u8], nanosecond: u32) -> usize {
+    let slice = &mut slice[0..6];
+    let sub_millis = nanosecond % 1_000_000;
+
+    slice[0] = (b'0' + ((sub_millis / 100_000 % 10) as u8));
+    slice[1] = (b'0' + ((sub_millis / 10_000 % 10) as u8));
+    slice[2] = (b'0' + ((sub_millis / 1_000 % 10) as u8));
+    slice[3] = (b'0' + ((sub_millis / 100 % 10) as u8));
+    slice[4] = (b'0' + ((sub_millis / 10 % 10) as u8));
+    slice[5] = (b'0' + ((sub_millis % 10) as u8));
+
+    6
+}
+
+/// Formats the timestamp with 9 fractional digits including its timezone offset,
+/// using `Z` only if the offset is zero. The slice needs to have room for 35 bytes.
+/// Returns the number of bytes written.
+#[inline]
+#[doc(hidden)] // used in benchmarks
+pub fn format_scalar_nanos_with_offset_to_slice(
+    slice: &mut [u8],
+    year: u32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    nanosecond: u32,
+    offset_minutes: i32,
+) -> usize {
+    format_scalar_to_slice(slice, year, month, day, hour, minute, second, nanosecond / 1_000_000);
+    let len = 23 + format_sub_millis_scalar_to_slice(&mut slice[23..], nanosecond);
+    len + format_offset_scalar_to_slice(&mut slice[len..], offset_minutes)
+}
+
+/// Formats the sign and the digits exceeding four digits of an ISO 8601 expanded year representation,
+/// for example `-` for the year -44 or `+1` for the year 12345. Years between 0 and 9999 do not need a prefix.
+/// Returns the number of bytes written and the remaining last four digits of the year.
+#[inline]
+pub(crate) fn format_expanded_year_prefix_to_slice(slice: &mut [u8], year: i32) -> (usize, u32) {
+    if year >= 0 && year <= 9999 {
+        return (0, year as u32);
+    }
+
+    let abs = year.unsigned_abs();
+    slice[0] = if year < 0 { b'-' } else { b'+' };
+
+    let mut high = abs / 10000;
+    if high == 0 {
+        return (1, abs);
+    }
+
+    let digits = (high.ilog10() + 1) as usize;
+    for i in (1..=digits).rev() {
+        slice[i] = b'0' + (high % 10) as u8;
+        high /= 10;
+    }
+
+    (1 + digits, abs % 10000)
+}
+
+/// Number of fractional second digits written when formatting with `FormatOptions`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FractionalDigits {
+    /// No fractional seconds and no decimal point.
+    None,
+    /// Three digits for milliseconds.
+    #[default]
+    Millis,
+    /// Six digits for microseconds.
+    Micros,
+    /// Nine digits for nanoseconds.
+    Nanos,
+    /// As many digits as needed to represent the fractional seconds without trailing zeros,
+    /// omitting the decimal point if the fractional seconds are zero.
+    Shortest,
+}
+
+/// Options for formatting rfc 3339 timestamps.
+///
+/// The default options produce the same output as `format_to_rfc3339_bytes` and JavaScript `toISOString`,
+/// for example `2022-08-21T17:30:15.250Z`. Using a space separator and `Shortest` fractional digits comes close to the
+/// text representation of PostgreSQL timestamps, for example `2022-08-21 17:30:15.25+00:00`.
+/// PostgreSQL omits the minutes of whole hour offsets though and would print `2022-08-21 17:30:15.25+00`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FormatOptions {
+    fractional_digits: FractionalDigits,
+    space_separator: bool,
+    zero_offset_as_z: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self::new()
     }
-    buffer
+}
+
+impl FormatOptions {
+    pub const fn new() -> Self {
+        Self {
+            fractional_digits: FractionalDigits::Millis,
+            space_separator: false,
+            zero_offset_as_z: true,
+        }
+    }
+
+    pub const fn with_fractional_digits(mut self, fractional_digits: FractionalDigits) -> Self {
+        self.fractional_digits = fractional_digits;
+        self
+    }
+
+    /// Separate the date and time with a space instead of a `T`.
+    pub const fn with_space_separator(mut self, space_separator: bool) -> Self {
+        self.space_separator = space_separator;
+        self
+    }
+
+    /// Format a zero timezone offset as `Z`, otherwise it is formatted as `+00:00`.
+    pub const fn with_zero_offset_as_z(mut self, zero_offset_as_z: bool) -> Self {
+        self.zero_offset_as_z = zero_offset_as_z;
+        self
+    }
+
+    pub const fn fractional_digits(&self) -> FractionalDigits {
+        self.fractional_digits
+    }
+
+    pub const fn space_separator(&self) -> bool {
+        self.space_separator
+    }
+
+    pub const fn zero_offset_as_z(&self) -> bool {
+        self.zero_offset_as_z
+    }
+}
+
+/// Formats the timestamp according to the given options. The slice needs to have room for 35 bytes.
+/// Returns the number of bytes written.
+#[inline]
+#[doc(hidden)] // used in benchmarks
+pub fn format_with_options_to_slice(
+    slice: &mut [u8],
+    year: u32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    nanosecond: u32,
+    offset_minutes: i32,
+    options: &FormatOptions,
+) -> usize {
+    // the utc format already contains the date, time and milliseconds at fixed positions
+    format_utc_to_slice(slice, year, month, day, hour, minute, second, nanosecond / 1_000_000);
+
+    if options.space_separator {
+        slice[10] = b' ';
+    }
+
+    let len = match options.fractional_digits {
+        FractionalDigits::None => 19,
+        FractionalDigits::Millis => 23,
+        FractionalDigits::Micros => 23 + format_sub_millis_scalar_to_slice(&mut slice[23..], nanosecond) - 3,
+        FractionalDigits::Nanos => 23 + format_sub_millis_scalar_to_slice(&mut slice[23..], nanosecond),
+        FractionalDigits::Shortest if nanosecond == 0 => 19,
+        FractionalDigits::Shortest => {
+            let mut len = 23 + format_sub_millis_scalar_to_slice(&mut slice[23..], nanosecond);
+            let mut fraction = nanosecond;
+            while fraction % 10 == 0 {
+                fraction /= 10;
+                len -= 1;
+            }
+            len
+        }
+    };
+
+    if offset_minutes == 0 && !options.zero_offset_as_z {
+        slice[len..len + 6].copy_from_slice(b"+00:00");
+        return len + 6;
+    }
+
+    len + format_offset_scalar_to_slice(&mut slice[len..], offset_minutes)
+}
+
+/// Formats the timestamp according to the given options.
+/// Years outside of the range 0 to 9999 are formatted using the ISO 8601 expanded representation with a sign.
+pub fn format_to_rfc3339_bytes_with_options(
+    year: i32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    nanosecond: u32,
+    offset_minutes: i32,
+    options: &FormatOptions,
+) -> FormattedTimestamp {
+    let mut buffer = [0_u8; MAX_FORMATTED_LEN];
+    let (prefix_len, year) = format_expanded_year_prefix_to_slice(&mut buffer, year);
+    let len = format_with_options_to_slice(
+        &mut buffer[prefix_len..],
+        year,
+        month,
+        day,
+        hour,
+        minute,
+        second,
+        nanosecond,
+        offset_minutes,
+        options,
+    );
+    FormattedTimestamp::new(buffer, prefix_len + len)
+}
+
+/// Formats a date as `yyyy-mm-dd`, the year has to be in the range 0 to 9999. The slice needs to have room for 10 bytes.
+#[inline]
+#[doc(hidden)] // used in benchmarks
+pub fn format_date_scalar_to_slice(slice: &mut [u8], year: u32, month: u32, day: u32) {
+    let slice = &mut slice[0..10];
+
+    slice[0] = (b'0' + ((year / 1000) as u8));
+    slice[1] = (b'0' + ((year / 100 % 10) as u8));
+    slice[2] = (b'0' + ((year / 10 % 10) as u8));
+    slice[3] = (b'0' + ((year % 10) as u8));
+
+    slice[4] = b'-';
+
+    slice[5] = (b'0' + ((month / 10) as u8));
+    slice[6] = (b'0' + ((month % 10) as u8));
+
+    slice[7] = b'-';
+
+    slice[8] = (b'0' + ((day / 10) as u8));
+    slice[9] = (b'0' + ((day % 10) as u8));
+}
+
+/// Formats a date as `yyyy-mm-dd`.
+/// Years outside of the range 0 to 9999 are formatted using the ISO 8601 expanded representation with a sign.
+pub fn format_to_iso_date_bytes(year: i32, month: u32, day: u32) -> FormattedTimestamp {
+    let mut buffer = [0_u8; MAX_FORMATTED_LEN];
+    let (prefix_len, year) = format_expanded_year_prefix_to_slice(&mut buffer, year);
+    format_date_scalar_to_slice(&mut buffer[prefix_len..], year, month, day);
+    FormattedTimestamp::new(buffer, prefix_len + 10)
+}
+
+/// Formats the timestamp with 9 fractional digits including its timezone offset, using `Z` only if the offset is zero.
+/// Years outside of the range 0 to 9999 are formatted using the ISO 8601 expanded representation with a sign.
+pub fn format_to_rfc3339_nanos_bytes(
+    year: i32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    nanosecond: u32,
+    offset_minutes: i32,
+) -> FormattedTimestamp {
+    let mut buffer = [0_u8; MAX_FORMATTED_LEN];
+    let (prefix_len, year) = format_expanded_year_prefix_to_slice(&mut buffer, year);
+    let slice = &mut buffer[prefix_len..];
+    format_utc_to_slice(slice, year, month, day, hour, minute, second, nanosecond / 1_000_000);
+    let len = 23 + format_sub_millis_scalar_to_slice(&mut slice[23..], nanosecond);
+    let len = len + format_offset_scalar_to_slice(&mut slice[len..], offset_minutes);
+    FormattedTimestamp::new(buffer, prefix_len + len)
+}
+
+/// Formats the timestamp including its timezone offset, using `Z` only if the offset is zero.
+/// Years outside of the range 0 to 9999 are formatted using the ISO 8601 expanded representation with a sign.
+pub fn format_to_rfc3339_bytes(
+    year: i32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    millisecond: u32,
+    offset_minutes: i32,
+) -> FormattedTimestamp {
+    let mut buffer = [0_u8; MAX_FORMATTED_LEN];
+    let (prefix_len, year) = format_expanded_year_prefix_to_slice(&mut buffer, year);
+    let slice = &mut buffer[prefix_len..];
+    let len = format_with_offset_to_slice(slice, year, month, day, hour, minute, second, millisecond, offset_minutes);
+    FormattedTimestamp::new(buffer, prefix_len + len)
 }
 
 #[cfg(test)]
@@ -343,10 +968,52 @@ fn assert_format(
     assert_eq!(expected, &actual);
 }
 
+#[cfg(test)]
+type FormatWithOffsetToSlice = unsafe fn(&mut [u8], u32, u32, u32, u32, u32, u32, u32, i32) -> usize;
+
+#[cfg(test)]
+fn assert_format_with_offset(
+    expected: &str,
+    year: u32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    millisecond: u32,
+    offset_minutes: i32,
+    f: FormatWithOffsetToSlice,
+) {
+    let mut buffer = vec![0; 29];
+
+    let len = unsafe {
+        f(
+            buffer.as_mut_slice(),
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            millisecond,
+            offset_minutes,
+        )
+    };
+    buffer.truncate(len);
+
+    let actual = String::from_utf8(buffer).unwrap();
+
+    assert_eq!(expected, &actual);
+}
+
 #[cfg(test)]
 mod scalar_tests {
-    use crate::format::assert_format;
-    use crate::format_scalar_to_slice;
+    use crate::format::{assert_format, assert_format_with_offset};
+    use crate::{
+        format_scalar_nanos_with_offset_to_slice, format_scalar_to_slice, format_scalar_with_offset_to_slice,
+        format_to_iso_date_bytes, format_to_rfc3339_bytes, format_to_rfc3339_bytes_with_options, format_to_rfc3339_nanos_bytes,
+        FormatOptions, FractionalDigits,
+    };
 
     #[test]
     fn test_format_scalar() {
@@ -374,6 +1041,267 @@ mod scalar_tests {
             format_scalar_to_slice,
         );
     }
+
+    #[test]
+    fn test_format_scalar_with_offset() {
+        let f = format_scalar_with_offset_to_slice;
+        assert_format_with_offset("2021-09-10T23:45:31.987Z", 2021, 9, 10, 23, 45, 31, 987, 0, f);
+        assert_format_with_offset("2021-09-10T23:45:31.987+02:00", 2021, 9, 10, 23, 45, 31, 987, 120, f);
+        assert_format_with_offset("2021-09-10T23:45:31.987-02:00", 2021, 9, 10, 23, 45, 31, 987, -120, f);
+        assert_format_with_offset("2021-09-10T23:45:31.987+05:45", 2021, 9, 10, 23, 45, 31, 987, 345, f);
+        assert_format_with_offset("2021-09-10T23:45:31.987-09:30", 2021, 9, 10, 23, 45, 31, 987, -570, f);
+        assert_format_with_offset("2021-09-10T23:45:31.987+18:00", 2021, 9, 10, 23, 45, 31, 987, 1080, f);
+        assert_format_with_offset("2021-09-10T23:45:31.987-00:01", 2021, 9, 10, 23, 45, 31, 987, -1, f);
+    }
+
+    #[test]
+    fn test_format_expanded_year() {
+        assert_eq!(
+            format_to_rfc3339_bytes(-44, 3, 15, 12, 0, 0, 0, 0).as_str(),
+            "-0044-03-15T12:00:00.000Z"
+        );
+        assert_eq!(
+            format_to_rfc3339_bytes(-9999, 1, 1, 0, 0, 0, 0, -60).as_str(),
+            "-9999-01-01T00:00:00.000-01:00"
+        );
+        assert_eq!(
+            format_to_rfc3339_bytes(-12345, 1, 1, 0, 0, 0, 0, 0).as_str(),
+            "-12345-01-01T00:00:00.000Z"
+        );
+        assert_eq!(
+            format_to_rfc3339_bytes(12345, 1, 1, 0, 0, 0, 0, 0).as_str(),
+            "+12345-01-01T00:00:00.000Z"
+        );
+        assert_eq!(
+            format_to_rfc3339_bytes(i32::MIN, 1, 1, 0, 0, 0, 0, 0).as_str(),
+            "-2147483648-01-01T00:00:00.000Z"
+        );
+        assert_eq!(
+            format_to_rfc3339_nanos_bytes(i32::MIN, 1, 1, 0, 0, 0, 123_456_789, -1080).as_str(),
+            "-2147483648-01-01T00:00:00.123456789-18:00"
+        );
+        assert_eq!(
+            format_to_rfc3339_bytes(0, 1, 1, 0, 0, 0, 0, 0).as_str(),
+            "0000-01-01T00:00:00.000Z"
+        );
+        assert_eq!(
+            format_to_rfc3339_bytes(9999, 12, 31, 0, 0, 0, 0, 0).as_str(),
+            "9999-12-31T00:00:00.000Z"
+        );
+        assert_eq!(
+            format_to_rfc3339_bytes(10000, 1, 1, 0, 0, 0, 0, 0).as_str(),
+            "+10000-01-01T00:00:00.000Z"
+        );
+    }
+
+    #[test]
+    fn test_format_scalar_nanos_with_offset() {
+        let mut buffer = [0_u8; 35];
+        let len = format_scalar_nanos_with_offset_to_slice(&mut buffer, 2021, 9, 10, 23, 45, 31, 987_654_321, 0);
+        assert_eq!(&buffer[..len], b"2021-09-10T23:45:31.987654321Z");
+        let len = format_scalar_nanos_with_offset_to_slice(&mut buffer, 2021, 9, 10, 23, 45, 31, 1, -330);
+        assert_eq!(&buffer[..len], b"2021-09-10T23:45:31.000000001-05:30");
+    }
+
+    #[test]
+    fn test_format_to_rfc3339_nanos_bytes() {
+        assert_eq!(
+            format_to_rfc3339_nanos_bytes(2021, 9, 10, 23, 45, 31, 987_654_321, 0).as_str(),
+            "2021-09-10T23:45:31.987654321Z"
+        );
+        assert_eq!(
+            format_to_rfc3339_nanos_bytes(2021, 9, 10, 23, 45, 31, 120_000, 60).as_str(),
+            "2021-09-10T23:45:31.000120000+01:00"
+        );
+    }
+
+    #[test]
+    fn test_format_to_rfc3339_bytes() {
+        assert_eq!(
+            format_to_rfc3339_bytes(2021, 9, 10, 23, 45, 31, 987, 0).as_str(),
+            "2021-09-10T23:45:31.987Z"
+        );
+        assert_eq!(
+            format_to_rfc3339_bytes(2021, 9, 10, 23, 45, 31, 987, 90).as_str(),
+            "2021-09-10T23:45:31.987+01:30"
+        );
+        assert_eq!(
+            format_to_rfc3339_bytes(2021, 9, 10, 23, 45, 31, 987, -90).as_bytes(),
+            b"2021-09-10T23:45:31.987-01:30"
+        );
+    }
+
+    #[test]
+    fn test_format_to_iso_date_bytes() {
+        assert_eq!(format_to_iso_date_bytes(2021, 9, 10).as_str(), "2021-09-10");
+        assert_eq!(format_to_iso_date_bytes(-44, 3, 15).as_str(), "-0044-03-15");
+    }
+
+    #[test]
+    fn test_format_fractional_digits() {
+        let format = |nanosecond: u32, fractional_digits: FractionalDigits| {
+            let options = FormatOptions::new().with_fractional_digits(fractional_digits);
+            format_to_rfc3339_bytes_with_options(2021, 9, 10, 23, 45, 31, nanosecond, 0, &options).to_string()
+        };
+        assert_eq!(format(987_654_321, FractionalDigits::None), "2021-09-10T23:45:31Z");
+        assert_eq!(format(987_654_321, FractionalDigits::Millis), "2021-09-10T23:45:31.987Z");
+        assert_eq!(format(987_654_321, FractionalDigits::Micros), "2021-09-10T23:45:31.987654Z");
+        assert_eq!(format(987_654_321, FractionalDigits::Nanos), "2021-09-10T23:45:31.987654321Z");
+        assert_eq!(
+            format(987_654_321, FractionalDigits::Shortest),
+            "2021-09-10T23:45:31.987654321Z"
+        );
+
+        assert_eq!(format(0, FractionalDigits::Shortest), "2021-09-10T23:45:31Z");
+        assert_eq!(format(0, FractionalDigits::Millis), "2021-09-10T23:45:31.000Z");
+        assert_eq!(format(500_000_000, FractionalDigits::Shortest), "2021-09-10T23:45:31.5Z");
+        assert_eq!(format(250_000_000, FractionalDigits::Shortest), "2021-09-10T23:45:31.25Z");
+        assert_eq!(format(120_000, FractionalDigits::Shortest), "2021-09-10T23:45:31.00012Z");
+        assert_eq!(format(1, FractionalDigits::Shortest), "2021-09-10T23:45:31.000000001Z");
+        assert_eq!(format(10, FractionalDigits::Shortest), "2021-09-10T23:45:31.00000001Z");
+    }
+
+    #[test]
+    fn test_format_with_options() {
+        let javascript = FormatOptions::default();
+        let postgres = FormatOptions::new()
+            .with_fractional_digits(FractionalDigits::Shortest)
+            .with_space_separator(true)
+            .with_zero_offset_as_z(false);
+        let bigquery = FormatOptions::new()
+            .with_fractional_digits(FractionalDigits::Micros)
+            .with_space_separator(true)
+            .with_zero_offset_as_z(false);
+
+        assert_eq!(
+            format_to_rfc3339_bytes_with_options(2022, 8, 21, 17, 30, 15, 250_000_000, 0, &javascript).as_str(),
+            "2022-08-21T17:30:15.250Z"
+        );
+        assert_eq!(
+            format_to_rfc3339_bytes_with_options(2022, 8, 21, 17, 30, 15, 250_000_000, 0, &postgres).as_str(),
+            "2022-08-21 17:30:15.25+00:00"
+        );
+        assert_eq!(
+            format_to_rfc3339_bytes_with_options(2022, 8, 21, 17, 30, 15, 250_000_000, 0, &bigquery).as_str(),
+            "2022-08-21 17:30:15.250000+00:00"
+        );
+        assert_eq!(
+            format_to_rfc3339_bytes_with_options(2022, 8, 21, 17, 30, 15, 0, -330, &postgres).as_str(),
+            "2022-08-21 17:30:15-05:30"
+        );
+        assert_eq!(
+            format_to_rfc3339_bytes_with_options(-44, 3, 15, 12, 0, 0, 0, 0, &postgres).as_str(),
+            "-0044-03-15 12:00:00+00:00"
+        );
+        assert_eq!(
+            format_to_rfc3339_bytes_with_options(
+                i32::MIN,
+                12,
+                31,
+                23,
+                59,
+                59,
+                999_999_999,
+                -1439,
+                &bigquery.with_fractional_digits(FractionalDigits::Nanos)
+            )
+            .len(),
+            42
+        );
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(not(miri), target_arch = "aarch64", target_feature = "neon"))]
+mod neon_tests {
+    use crate::format::{assert_format, assert_format_with_offset};
+    use crate::{format_neon_to_slice, format_neon_with_offset_to_slice, format_scalar_with_offset_to_slice};
+
+    #[test]
+    fn test_format_neon() {
+        assert_format("2021-09-10T23:45:31.987Z", 2021, 9, 10, 23, 45, 31, 987, format_neon_to_slice);
+        assert_format("2021-01-01T00:00:00.000Z", 2021, 1, 1, 0, 0, 0, 0, format_neon_to_slice);
+        assert_format(
+            "2021-12-31T23:59:60.999Z",
+            2021,
+            12,
+            31,
+            23,
+            59,
+            60,
+            999,
+            format_neon_to_slice,
+        );
+        assert_format("0001-01-01T00:00:00.000Z", 1, 1, 1, 0, 0, 0, 0, format_neon_to_slice);
+        assert_format(
+            "9999-12-31T23:59:59.999Z",
+            9999,
+            12,
+            31,
+            23,
+            59,
+            59,
+            999,
+            format_neon_to_slice,
+        );
+    }
+
+    #[test]
+    fn test_format_neon_with_offset() {
+        assert_format_with_offset(
+            "2021-09-10T23:45:31.987Z",
+            2021,
+            9,
+            10,
+            23,
+            45,
+            31,
+            987,
+            0,
+            format_neon_with_offset_to_slice,
+        );
+        assert_format_with_offset(
+            "2021-09-10T23:45:31.987+05:30",
+            2021,
+            9,
+            10,
+            23,
+            45,
+            31,
+            987,
+            330,
+            format_neon_with_offset_to_slice,
+        );
+        assert_format_with_offset(
+            "2021-09-10T23:45:31.987-18:00",
+            2021,
+            9,
+            10,
+            23,
+            45,
+            31,
+            987,
+            -1080,
+            format_neon_with_offset_to_slice,
+        );
+    }
+
+    #[test]
+    fn test_format_neon_matches_scalar() {
+        for (i, offset) in (-1080..=1080).step_by(15).enumerate() {
+            let i = i as u32;
+            let (year, month, day) = (i * 37 % 10000, i % 12 + 1, i % 28 + 1);
+            let (hour, minute, second, millisecond) = (i % 24, i * 7 % 60, i * 13 % 61, i * 17 % 1000);
+            let mut expected = [0_u8; 29];
+            let mut actual = [0_u8; 29];
+            let expected_len =
+                format_scalar_with_offset_to_slice(&mut expected, year, month, day, hour, minute, second, millisecond, offset);
+            let actual_len = unsafe {
+                format_neon_with_offset_to_slice(&mut actual, year, month, day, hour, minute, second, millisecond, offset)
+            };
+            assert_eq!(&actual[..actual_len], &expected[..expected_len]);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -385,8 +1313,8 @@ mod scalar_tests {
     target_feature = "sse4.1"
 ))]
 mod simd_tests {
-    use crate::format::assert_format;
-    use crate::{format_simd_dd_to_slice, format_simd_mul_to_slice};
+    use crate::format::{assert_format, assert_format_with_offset};
+    use crate::{format_simd_dd_to_slice, format_simd_mul_to_slice, format_simd_mul_with_offset_to_slice};
 
     #[test]
     fn test_format_simd_dd() {
@@ -441,4 +1369,16 @@ mod simd_tests {
             format_simd_mul_to_slice,
         );
     }
+
+    #[test]
+    fn test_format_simd_mul_with_offset() {
+        let f = format_simd_mul_with_offset_to_slice;
+        assert_format_with_offset("2021-09-10T23:45:31.987Z", 2021, 9, 10, 23, 45, 31, 987, 0, f);
+        assert_format_with_offset("2021-09-10T23:45:31.987+02:00", 2021, 9, 10, 23, 45, 31, 987, 120, f);
+        assert_format_with_offset("2021-09-10T23:45:31.987-02:00", 2021, 9, 10, 23, 45, 31, 987, -120, f);
+        assert_format_with_offset("2021-09-10T23:45:31.987+05:45", 2021, 9, 10, 23, 45, 31, 987, 345, f);
+        assert_format_with_offset("2021-09-10T23:45:31.987-09:30", 2021, 9, 10, 23, 45, 31, 987, -570, f);
+        assert_format_with_offset("2021-09-10T23:45:31.987+18:00", 2021, 9, 10, 23, 45, 31, 987, 1080, f);
+        assert_format_with_offset("2021-09-10T23:45:31.987-00:01", 2021, 9, 10, 23, 45, 31, 987, -1, f);
+    }
 }
@@ -0,0 +1,244 @@
+use crate::datetime::DateTimeComponents;
+use crate::{EpochDays, PackedTimestamp, MILLIS_PER_DAY, NANOS_PER_DAY};
+use std::ops::Neg;
+
+const NANOS_PER_MILLI: i64 = 1_000_000;
+
+/// An interval with separate month, day and sub-day components,
+/// like the arrow `MonthDayNano` interval type or a PostgreSQL `interval`.
+///
+/// The components are kept separate because their length depends on the date they are applied to,
+/// a month can have between 28 and 31 days and a day in local time can be longer or shorter than 24 hours.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Interval {
+    pub months: i32,
+    pub days: i32,
+    pub nanos: i64,
+}
+
+/// A date or timestamp that an `Interval` can be added to.
+pub trait AddInterval: Sized {
+    /// Adds the months, then the days and then the time of the interval.
+    fn add_interval(self, interval: &Interval) -> Self;
+}
+
+impl Interval {
+    pub const ZERO: Interval = Interval::new(0, 0, 0);
+
+    #[inline]
+    pub const fn new(months: i32, days: i32, nanos: i64) -> Self {
+        Self { months, days, nanos }
+    }
+
+    #[inline]
+    pub const fn from_months(months: i32) -> Self {
+        Self::new(months, 0, 0)
+    }
+
+    #[inline]
+    pub const fn from_days(days: i32) -> Self {
+        Self::new(0, days, 0)
+    }
+
+    #[inline]
+    pub const fn from_millis(millis: i64) -> Self {
+        Self::new(0, 0, millis * NANOS_PER_MILLI)
+    }
+
+    /// Adds this interval to a date or timestamp.
+    ///
+    /// Like in PostgreSQL the months are applied first, clamping the day to the end of the resulting month,
+    /// then the days and finally the time. For example 2024-01-30 + 1 month 1 day is 2024-03-01.
+    #[inline]
+    pub fn add_to<T: AddInterval>(&self, value: T) -> T {
+        value.add_interval(self)
+    }
+
+    /// Subtracts this interval from a date or timestamp, which is the same as adding the negated interval.
+    ///
+    /// For example 2024-03-31 - 1 month 1 day is 2024-02-28.
+    ///
+    /// # Panics
+    ///
+    /// If a component of the interval is the minimum value of its type and can not be negated, see `checked_sub_from`.
+    #[inline]
+    pub fn sub_from<T: AddInterval>(&self, value: T) -> T {
+        self.checked_sub_from(value).expect("interval can not be negated")
+    }
+
+    /// Subtracts this interval from a date or timestamp,
+    /// returns `None` if a component of the interval is the minimum value of its type and can not be negated.
+    #[inline]
+    pub fn checked_sub_from<T: AddInterval>(&self, value: T) -> Option<T> {
+        self.checked_neg().map(|interval| value.add_interval(&interval))
+    }
+
+    /// Negates all components, returns `None` if one of them is the minimum value of its type.
+    #[inline]
+    pub const fn checked_neg(&self) -> Option<Self> {
+        match (self.months.checked_neg(), self.days.checked_neg(), self.nanos.checked_neg()) {
+            (Some(months), Some(days), Some(nanos)) => Some(Self::new(months, days, nanos)),
+            _ => None,
+        }
+    }
+}
+
+/// # Panics
+///
+/// If a component of the interval is the minimum value of its type, see `Interval::checked_neg`.
+impl Neg for Interval {
+    type Output = Interval;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        self.checked_neg().expect("interval can not be negated")
+    }
+}
+
+/// Milliseconds since the unix epoch, the part of the interval smaller than a millisecond is truncated towards zero.
+impl AddInterval for i64 {
+    #[inline]
+    fn add_interval(self, interval: &Interval) -> Self {
+        let (days, millis) = (self.div_euclid(MILLIS_PER_DAY), self.rem_euclid(MILLIS_PER_DAY));
+        let epoch_days = EpochDays::new(days as i32).add_months(interval.months);
+        let date_part = (epoch_days.days() as i64 + interval.days as i64) * MILLIS_PER_DAY;
+        date_part + millis + interval.nanos / NANOS_PER_MILLI
+    }
+}
+
+/// A date is treated as a timestamp at midnight, the result is the date of the resulting timestamp.
+/// That means adding a time of less than a day keeps the date while subtracting it moves to the previous day.
+impl AddInterval for EpochDays {
+    #[inline]
+    fn add_interval(self, interval: &Interval) -> Self {
+        let epoch_days = self.add_months(interval.months);
+        let days = epoch_days.days() as i64 + interval.days as i64 + interval.nanos.div_euclid(NANOS_PER_DAY);
+        EpochDays::new(days as i32)
+    }
+}
+
+/// The interval is applied to the local date and time fields, the offset of the timestamp stays the same.
+impl AddInterval for PackedTimestamp {
+    #[inline]
+    fn add_interval(self, interval: &Interval) -> Self {
+        let offset_minutes = self.offset_minutes();
+        let local = self.to_timestamp_millis() + offset_minutes as i64 * 60 * 1000;
+        let mut components = DateTimeComponents::from_timestamp_millis(local.add_interval(interval));
+        components.offset_minute = offset_minutes;
+        components.to_packed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::date_millis;
+    use crate::{EpochDays, Interval, PackedTimestamp, MILLIS_PER_DAY};
+
+    const HOUR: i64 = 60 * 60 * 1000;
+
+    #[test]
+    fn test_add_months_before_days() {
+        let interval = Interval::new(1, 1, 0);
+        assert_eq!(
+            interval.add_to(EpochDays::from_ymd(2024, 1, 30)),
+            EpochDays::from_ymd(2024, 3, 1)
+        );
+        assert_eq!(
+            interval.sub_from(EpochDays::from_ymd(2024, 3, 31)),
+            EpochDays::from_ymd(2024, 2, 28)
+        );
+        assert_eq!(
+            interval.add_to(date_millis(2024, 1, 30) + 12 * HOUR),
+            date_millis(2024, 3, 1) + 12 * HOUR
+        );
+        assert_eq!(
+            interval.sub_from(date_millis(2024, 3, 31) + 12 * HOUR),
+            date_millis(2024, 2, 28) + 12 * HOUR
+        );
+    }
+
+    #[test]
+    fn test_add_time() {
+        let interval = Interval::new(0, 1, 25 * HOUR * 1_000_000 + 1_500_000);
+        assert_eq!(interval.add_to(date_millis(2024, 2, 28)), date_millis(2024, 3, 1) + HOUR + 1);
+        assert_eq!(
+            interval.sub_from(date_millis(2024, 3, 1) + HOUR + 1),
+            date_millis(2024, 2, 28)
+        );
+        assert_eq!(interval.add_to(-1), date_millis(1970, 1, 3) + HOUR);
+        assert_eq!(Interval::from_millis(-1).add_to(0_i64), -1);
+        assert_eq!(Interval::from_millis(HOUR).add_to(-MILLIS_PER_DAY), -MILLIS_PER_DAY + HOUR);
+        assert_eq!(Interval::ZERO.add_to(-12345_i64), -12345);
+
+        assert_eq!(
+            interval.add_to(EpochDays::from_ymd(2024, 2, 28)),
+            EpochDays::from_ymd(2024, 3, 1)
+        );
+        assert_eq!(
+            Interval::from_millis(HOUR).add_to(EpochDays::from_ymd(2024, 2, 28)),
+            EpochDays::from_ymd(2024, 2, 28)
+        );
+        assert_eq!(
+            Interval::from_millis(HOUR).sub_from(EpochDays::from_ymd(2024, 2, 28)),
+            EpochDays::from_ymd(2024, 2, 27)
+        );
+    }
+
+    #[test]
+    fn test_add_to_timestamp_before_epoch() {
+        let interval = Interval::new(1, 0, 0);
+        assert_eq!(
+            interval.add_to(date_millis(1969, 12, 31) + 23 * HOUR),
+            date_millis(1970, 1, 31) + 23 * HOUR
+        );
+        assert_eq!(
+            interval.sub_from(date_millis(1969, 3, 31) + 23 * HOUR),
+            date_millis(1969, 2, 28) + 23 * HOUR
+        );
+        assert_eq!(
+            Interval::from_days(-1).add_to(date_millis(1970, 1, 1) + HOUR),
+            date_millis(1969, 12, 31) + HOUR
+        );
+    }
+
+    #[test]
+    fn test_add_to_packed_timestamp_keeps_offset() {
+        // 2024-01-31T23:30:00+02:00 is still january in local time but already february in utc
+        let timestamp = PackedTimestamp::new(2024, 1, 31, 23, 30, 0, 0, 120);
+        let interval = Interval::new(1, 0, 60 * 60 * 1_000_000_000);
+        assert_eq!(interval.add_to(timestamp), PackedTimestamp::new(2024, 3, 1, 0, 30, 0, 0, 120));
+        assert_eq!(
+            Interval::from_months(-1).add_to(timestamp),
+            PackedTimestamp::new(2023, 12, 31, 23, 30, 0, 0, 120)
+        );
+
+        let timestamp = PackedTimestamp::new(2024, 3, 31, 0, 15, 0, 250, -330);
+        assert_eq!(
+            Interval::new(1, 1, 0).sub_from(timestamp),
+            PackedTimestamp::new(2024, 2, 28, 0, 15, 0, 250, -330)
+        );
+    }
+
+    #[test]
+    fn test_neg() {
+        assert_eq!(-Interval::new(1, -2, 3), Interval::new(-1, 2, -3));
+        assert_eq!(-Interval::ZERO, Interval::ZERO);
+        assert_eq!(Interval::default(), Interval::ZERO);
+
+        assert_eq!(
+            Interval::new(i32::MAX, i32::MAX, i64::MAX).checked_neg(),
+            Some(Interval::new(-i32::MAX, -i32::MAX, -i64::MAX))
+        );
+        assert_eq!(Interval::new(i32::MIN, 0, 0).checked_neg(), None);
+        assert_eq!(Interval::new(0, i32::MIN, 0).checked_neg(), None);
+        assert_eq!(Interval::new(0, 0, i64::MIN).checked_neg(), None);
+        assert_eq!(Interval::new(i32::MIN, 0, 0).checked_sub_from(0_i64), None);
+        assert_eq!(Interval::from_days(1).checked_sub_from(MILLIS_PER_DAY), Some(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "interval can not be negated")]
+    fn test_neg_overflow() {
+        let _ = -Interval::new(i32::MIN, 0, 0);
+    }
+}
@@ -1,8 +1,9 @@
-use crate::{EpochDays, MILLIS_PER_DAY};
+use crate::{EpochDays, Interval, PackedTimestamp, TimeZone, Weekday, MILLIS_PER_DAY};
 
+/// Rounds down to a multiple of `stride`, also for negative timestamps.
 #[inline]
-fn truncate_millis(ts: i64, truncate: i64) -> i64 {
-    ts / truncate * truncate
+fn floor_millis(ts: i64, stride: i64) -> i64 {
+    ts - ts.rem_euclid(stride)
 }
 
 #[inline]
@@ -11,9 +12,62 @@ fn truncate_millis_float(ts: f64, truncate: i64) -> f64 {
     (ts / truncate).floor() * truncate
 }
 
+#[inline]
+pub fn date_trunc_second_timestamp_millis(ts: i64) -> i64 {
+    floor_millis(ts, 1000)
+}
+
+#[inline]
+pub fn date_trunc_second_timestamp_millis_float(ts: f64) -> f64 {
+    truncate_millis_float(ts, 1000)
+}
+
+#[inline]
+pub fn date_trunc_minute_timestamp_millis(ts: i64) -> i64 {
+    floor_millis(ts, 60 * 1000)
+}
+
+#[inline]
+pub fn date_trunc_minute_timestamp_millis_float(ts: f64) -> f64 {
+    truncate_millis_float(ts, 60 * 1000)
+}
+
+#[inline]
+pub fn date_trunc_hour_timestamp_millis(ts: i64) -> i64 {
+    floor_millis(ts, 60 * 60 * 1000)
+}
+
+#[inline]
+pub fn date_trunc_hour_timestamp_millis_float(ts: f64) -> f64 {
+    truncate_millis_float(ts, 60 * 60 * 1000)
+}
+
+/// Buckets a timestamp into intervals of `stride` milliseconds that are aligned to `origin`,
+/// like the `date_bin` function of PostgreSQL.
+///
+/// # Panics
+///
+/// If the stride is not positive, or if the distance between `ts` and `origin` or the start of the bin does not fit into an `i64`.
+#[inline]
+pub fn date_bin_timestamp_millis(ts: i64, stride: i64, origin: i64) -> i64 {
+    assert!(stride > 0, "stride must be positive");
+    ts.checked_sub(origin)
+        .and_then(|diff| ts.checked_sub(diff.rem_euclid(stride)))
+        .expect("date_bin out of range")
+}
+
+/// # Panics
+///
+/// If the stride is not positive.
+#[inline]
+pub fn date_bin_timestamp_millis_float(ts: f64, stride: i64, origin: f64) -> f64 {
+    assert!(stride > 0, "stride must be positive");
+    truncate_millis_float(ts - origin, stride) + origin
+}
+
 #[inline]
 pub fn date_trunc_day_timestamp_millis(ts: i64) -> i64 {
-    truncate_millis(ts, MILLIS_PER_DAY)
+    floor_millis(ts, MILLIS_PER_DAY)
 }
 
 #[inline]
@@ -21,16 +75,34 @@ pub fn date_trunc_day_timestamp_millis_float(ts: f64) -> f64 {
     truncate_millis_float(ts, MILLIS_PER_DAY)
 }
 
+/// Truncates to the start of the week, with weeks starting on monday.
 #[inline]
 pub fn date_trunc_week_timestamp_millis(ts: i64) -> i64 {
-    // unix epoch starts on a thursday
-    let offset = 4 * MILLIS_PER_DAY;
-    truncate_millis(ts - offset, 7 * MILLIS_PER_DAY) + offset
+    date_trunc_week_timestamp_millis_starting_on(ts, Weekday::Monday)
 }
 
 #[inline]
 pub fn date_trunc_week_timestamp_millis_float(ts: f64) -> f64 {
-    let offset = (4 * MILLIS_PER_DAY) as f64;
+    date_trunc_week_timestamp_millis_float_starting_on(ts, Weekday::Monday)
+}
+
+/// The first start of a week on or after the unix epoch, in milliseconds.
+#[inline]
+fn first_week_start_millis(week_start: Weekday) -> i64 {
+    let epoch_weekday = EpochDays::new(0).weekday();
+    (week_start.days_since_monday() - epoch_weekday.days_since_monday()).rem_euclid(7) as i64 * MILLIS_PER_DAY
+}
+
+/// Truncates to the start of the week, with weeks starting on `week_start`.
+#[inline]
+pub fn date_trunc_week_timestamp_millis_starting_on(ts: i64, week_start: Weekday) -> i64 {
+    let offset = first_week_start_millis(week_start);
+    floor_millis(ts - offset, 7 * MILLIS_PER_DAY) + offset
+}
+
+#[inline]
+pub fn date_trunc_week_timestamp_millis_float_starting_on(ts: f64, week_start: Weekday) -> f64 {
+    let offset = first_week_start_millis(week_start) as f64;
     truncate_millis_float(ts - offset, 7 * MILLIS_PER_DAY) + offset
 }
 
@@ -76,18 +148,369 @@ pub fn date_trunc_quarter_timestamp_millis_float(ts: f64) -> f64 {
     truncated.to_timestamp_millis_float()
 }
 
+/// Applies a truncation in local time with a fixed offset and converts the result back to an instant.
+#[inline]
+fn truncate_with_offset(ts: i64, offset_minutes: i32, truncate: impl Fn(i64) -> i64) -> i64 {
+    let offset = offset_minutes as i64 * 60 * 1000;
+    truncate(ts + offset) - offset
+}
+
+#[inline]
+fn truncate_with_offset_float(ts: f64, offset_minutes: i32, truncate: impl Fn(f64) -> f64) -> f64 {
+    let offset = (offset_minutes as i64 * 60 * 1000) as f64;
+    truncate(ts + offset) - offset
+}
+
+/// Applies a truncation in the local time of the zone and converts the result back to an instant.
+/// If the truncated local time is skipped by a transition, the first instant after the gap is returned,
+/// so a day starting at 01:00 because of a transition at midnight is truncated to 01:00.
+#[inline]
+fn truncate_in_zone(ts: i64, zone: &TimeZone, truncate: impl Fn(i64) -> i64) -> i64 {
+    zone.resolve_local(truncate(zone.to_local_millis(ts))).compatible()
+}
+
+#[inline]
+fn truncate_in_zone_float(ts: f64, zone: &TimeZone, truncate: impl Fn(f64) -> f64) -> f64 {
+    let offset = (zone.offset_seconds_at(ts.floor() as i64) as i64 * 1000) as f64;
+    zone.resolve_local(truncate(ts + offset) as i64).compatible() as f64
+}
+
+/// Truncates to the start of the day in local time with the given offset.
+#[inline]
+pub fn date_trunc_day_timestamp_millis_with_offset(ts: i64, offset_minutes: i32) -> i64 {
+    truncate_with_offset(ts, offset_minutes, date_trunc_day_timestamp_millis)
+}
+
+#[inline]
+pub fn date_trunc_day_timestamp_millis_float_with_offset(ts: f64, offset_minutes: i32) -> f64 {
+    truncate_with_offset_float(ts, offset_minutes, date_trunc_day_timestamp_millis_float)
+}
+
+/// Truncates to the start of the day in the local time of the zone.
+#[inline]
+pub fn date_trunc_day_timestamp_millis_in_zone(ts: i64, zone: &TimeZone) -> i64 {
+    truncate_in_zone(ts, zone, date_trunc_day_timestamp_millis)
+}
+
+#[inline]
+pub fn date_trunc_day_timestamp_millis_float_in_zone(ts: f64, zone: &TimeZone) -> f64 {
+    truncate_in_zone_float(ts, zone, date_trunc_day_timestamp_millis_float)
+}
+
+/// Truncates to the start of the week in local time with the given offset.
+#[inline]
+pub fn date_trunc_week_timestamp_millis_with_offset(ts: i64, offset_minutes: i32) -> i64 {
+    truncate_with_offset(ts, offset_minutes, date_trunc_week_timestamp_millis)
+}
+
+#[inline]
+pub fn date_trunc_week_timestamp_millis_float_with_offset(ts: f64, offset_minutes: i32) -> f64 {
+    truncate_with_offset_float(ts, offset_minutes, date_trunc_week_timestamp_millis_float)
+}
+
+/// Truncates to the start of the week in the local time of the zone.
+#[inline]
+pub fn date_trunc_week_timestamp_millis_in_zone(ts: i64, zone: &TimeZone) -> i64 {
+    truncate_in_zone(ts, zone, date_trunc_week_timestamp_millis)
+}
+
+#[inline]
+pub fn date_trunc_week_timestamp_millis_float_in_zone(ts: f64, zone: &TimeZone) -> f64 {
+    truncate_in_zone_float(ts, zone, date_trunc_week_timestamp_millis_float)
+}
+
+/// Truncates to the start of the month in local time with the given offset.
+#[inline]
+pub fn date_trunc_month_timestamp_millis_with_offset(ts: i64, offset_minutes: i32) -> i64 {
+    truncate_with_offset(ts, offset_minutes, date_trunc_month_timestamp_millis)
+}
+
+#[inline]
+pub fn date_trunc_month_timestamp_millis_float_with_offset(ts: f64, offset_minutes: i32) -> f64 {
+    truncate_with_offset_float(ts, offset_minutes, date_trunc_month_timestamp_millis_float)
+}
+
+/// Truncates to the start of the month in the local time of the zone.
+#[inline]
+pub fn date_trunc_month_timestamp_millis_in_zone(ts: i64, zone: &TimeZone) -> i64 {
+    truncate_in_zone(ts, zone, date_trunc_month_timestamp_millis)
+}
+
+#[inline]
+pub fn date_trunc_month_timestamp_millis_float_in_zone(ts: f64, zone: &TimeZone) -> f64 {
+    truncate_in_zone_float(ts, zone, date_trunc_month_timestamp_millis_float)
+}
+
+/// Truncates to the start of the year in local time with the given offset.
+#[inline]
+pub fn date_trunc_year_timestamp_millis_with_offset(ts: i64, offset_minutes: i32) -> i64 {
+    truncate_with_offset(ts, offset_minutes, date_trunc_year_timestamp_millis)
+}
+
+#[inline]
+pub fn date_trunc_year_timestamp_millis_float_with_offset(ts: f64, offset_minutes: i32) -> f64 {
+    truncate_with_offset_float(ts, offset_minutes, date_trunc_year_timestamp_millis_float)
+}
+
+/// Truncates to the start of the year in the local time of the zone.
+#[inline]
+pub fn date_trunc_year_timestamp_millis_in_zone(ts: i64, zone: &TimeZone) -> i64 {
+    truncate_in_zone(ts, zone, date_trunc_year_timestamp_millis)
+}
+
+#[inline]
+pub fn date_trunc_year_timestamp_millis_float_in_zone(ts: f64, zone: &TimeZone) -> f64 {
+    truncate_in_zone_float(ts, zone, date_trunc_year_timestamp_millis_float)
+}
+
+/// Truncates to the start of the quarter in local time with the given offset.
+#[inline]
+pub fn date_trunc_quarter_timestamp_millis_with_offset(ts: i64, offset_minutes: i32) -> i64 {
+    truncate_with_offset(ts, offset_minutes, date_trunc_quarter_timestamp_millis)
+}
+
+#[inline]
+pub fn date_trunc_quarter_timestamp_millis_float_with_offset(ts: f64, offset_minutes: i32) -> f64 {
+    truncate_with_offset_float(ts, offset_minutes, date_trunc_quarter_timestamp_millis_float)
+}
+
+/// Truncates to the start of the quarter in the local time of the zone.
+#[inline]
+pub fn date_trunc_quarter_timestamp_millis_in_zone(ts: i64, zone: &TimeZone) -> i64 {
+    truncate_in_zone(ts, zone, date_trunc_quarter_timestamp_millis)
+}
+
+#[inline]
+pub fn date_trunc_quarter_timestamp_millis_float_in_zone(ts: f64, zone: &TimeZone) -> f64 {
+    truncate_in_zone_float(ts, zone, date_trunc_quarter_timestamp_millis_float)
+}
+
+/// Truncates to the start of the ISO 8601 week-based year, which is the monday of week 1.
+#[inline]
+pub fn date_trunc_iso_year_timestamp_millis(ts: i64) -> i64 {
+    let epoch_days = EpochDays::from_timestamp_millis(ts);
+    let truncated = epoch_days.date_trunc_iso_year();
+    truncated.to_timestamp_millis()
+}
+
+#[inline]
+pub fn date_trunc_iso_year_timestamp_millis_float(ts: f64) -> f64 {
+    let epoch_days = EpochDays::from_timestamp_millis_float(ts);
+    let truncated = epoch_days.date_trunc_iso_year();
+    truncated.to_timestamp_millis_float()
+}
+
+#[inline]
+pub fn date_part_iso_year_timestamp_millis(ts: i64) -> i32 {
+    let epoch_days = EpochDays::from_timestamp_millis(ts);
+    epoch_days.extract_iso_year()
+}
+
+#[inline]
+pub fn date_part_iso_year_timestamp_millis_float(ts: f64) -> i32 {
+    let epoch_days = EpochDays::from_timestamp_millis_float(ts);
+    epoch_days.extract_iso_year()
+}
+
+#[inline]
+pub fn date_part_iso_week_timestamp_millis(ts: i64) -> i32 {
+    let epoch_days = EpochDays::from_timestamp_millis(ts);
+    epoch_days.extract_iso_week()
+}
+
+#[inline]
+pub fn date_part_iso_week_timestamp_millis_float(ts: f64) -> i32 {
+    let epoch_days = EpochDays::from_timestamp_millis_float(ts);
+    epoch_days.extract_iso_week()
+}
+
 #[inline]
 pub fn date_part_year_timestamp_millis(ts: i64) -> i32 {
     let epoch_days = EpochDays::from_timestamp_millis(ts);
     epoch_days.extract_year()
 }
 
+#[inline]
+pub fn date_part_year_timestamp_millis_float(ts: f64) -> i32 {
+    let epoch_days = EpochDays::from_timestamp_millis_float(ts);
+    epoch_days.extract_year()
+}
+
 #[inline]
 pub fn date_part_month_timestamp_millis(ts: i64) -> i32 {
     let epoch_days = EpochDays::from_timestamp_millis(ts);
     epoch_days.extract_month()
 }
 
+#[inline]
+pub fn date_part_month_timestamp_millis_float(ts: f64) -> i32 {
+    let epoch_days = EpochDays::from_timestamp_millis_float(ts);
+    epoch_days.extract_month()
+}
+
+#[inline]
+pub fn date_part_quarter_timestamp_millis(ts: i64) -> i32 {
+    let epoch_days = EpochDays::from_timestamp_millis(ts);
+    epoch_days.extract_quarter()
+}
+
+#[inline]
+pub fn date_part_quarter_timestamp_millis_float(ts: f64) -> i32 {
+    let epoch_days = EpochDays::from_timestamp_millis_float(ts);
+    epoch_days.extract_quarter()
+}
+
+/// The day of the month from 1 to 31.
+#[inline]
+pub fn date_part_day_timestamp_millis(ts: i64) -> i32 {
+    let epoch_days = EpochDays::from_timestamp_millis(ts);
+    epoch_days.extract_day_of_month()
+}
+
+#[inline]
+pub fn date_part_day_timestamp_millis_float(ts: f64) -> i32 {
+    let epoch_days = EpochDays::from_timestamp_millis_float(ts);
+    epoch_days.extract_day_of_month()
+}
+
+/// The day of the week from 0 for sunday to 6 for saturday.
+#[inline]
+pub fn date_part_day_of_week_timestamp_millis(ts: i64) -> i32 {
+    let epoch_days = EpochDays::from_timestamp_millis(ts);
+    epoch_days.extract_day_of_week()
+}
+
+#[inline]
+pub fn date_part_day_of_week_timestamp_millis_float(ts: f64) -> i32 {
+    let epoch_days = EpochDays::from_timestamp_millis_float(ts);
+    epoch_days.extract_day_of_week()
+}
+
+/// The ISO 8601 day of the week from 1 for monday to 7 for sunday.
+#[inline]
+pub fn date_part_iso_day_of_week_timestamp_millis(ts: i64) -> i32 {
+    let epoch_days = EpochDays::from_timestamp_millis(ts);
+    epoch_days.extract_iso_day_of_week()
+}
+
+#[inline]
+pub fn date_part_iso_day_of_week_timestamp_millis_float(ts: f64) -> i32 {
+    let epoch_days = EpochDays::from_timestamp_millis_float(ts);
+    epoch_days.extract_iso_day_of_week()
+}
+
+/// The day of the year from 1 to 366.
+#[inline]
+pub fn date_part_day_of_year_timestamp_millis(ts: i64) -> i32 {
+    let epoch_days = EpochDays::from_timestamp_millis(ts);
+    epoch_days.extract_day_of_year()
+}
+
+#[inline]
+pub fn date_part_day_of_year_timestamp_millis_float(ts: f64) -> i32 {
+    let epoch_days = EpochDays::from_timestamp_millis_float(ts);
+    epoch_days.extract_day_of_year()
+}
+
+#[inline]
+pub fn date_part_decade_timestamp_millis(ts: i64) -> i32 {
+    let epoch_days = EpochDays::from_timestamp_millis(ts);
+    epoch_days.extract_decade()
+}
+
+#[inline]
+pub fn date_part_decade_timestamp_millis_float(ts: f64) -> i32 {
+    let epoch_days = EpochDays::from_timestamp_millis_float(ts);
+    epoch_days.extract_decade()
+}
+
+#[inline]
+pub fn date_part_century_timestamp_millis(ts: i64) -> i32 {
+    let epoch_days = EpochDays::from_timestamp_millis(ts);
+    epoch_days.extract_century()
+}
+
+#[inline]
+pub fn date_part_century_timestamp_millis_float(ts: f64) -> i32 {
+    let epoch_days = EpochDays::from_timestamp_millis_float(ts);
+    epoch_days.extract_century()
+}
+
+#[inline]
+pub fn date_part_millennium_timestamp_millis(ts: i64) -> i32 {
+    let epoch_days = EpochDays::from_timestamp_millis(ts);
+    epoch_days.extract_millennium()
+}
+
+#[inline]
+pub fn date_part_millennium_timestamp_millis_float(ts: f64) -> i32 {
+    let epoch_days = EpochDays::from_timestamp_millis_float(ts);
+    epoch_days.extract_millennium()
+}
+
+/// The hour of the day from 0 to 23.
+#[inline]
+pub fn date_part_hour_timestamp_millis(ts: i64) -> i32 {
+    let millis = ts.rem_euclid(MILLIS_PER_DAY);
+    (millis / (60 * 60 * 1000)) as i32
+}
+
+#[inline]
+pub fn date_part_hour_timestamp_millis_float(ts: f64) -> i32 {
+    let (_, millis) = timestamp_to_epoch_days_and_remainder_float(ts);
+    (millis / (60 * 60 * 1000) as f64).floor() as i32
+}
+
+/// The minute of the hour from 0 to 59.
+#[inline]
+pub fn date_part_minute_timestamp_millis(ts: i64) -> i32 {
+    let millis = ts.rem_euclid(MILLIS_PER_DAY);
+    (millis / (60 * 1000) % 60) as i32
+}
+
+#[inline]
+pub fn date_part_minute_timestamp_millis_float(ts: f64) -> i32 {
+    let (_, millis) = timestamp_to_epoch_days_and_remainder_float(ts);
+    ((millis / (60 * 1000) as f64).floor() % 60.0) as i32
+}
+
+/// The seconds field including fractional seconds, from 0 to less than 60, like `date_part('second', ...)` in SQL.
+#[inline]
+pub fn date_part_second_timestamp_millis(ts: i64) -> f64 {
+    let millis = ts.rem_euclid(60 * 1000);
+    millis as f64 / 1000.0
+}
+
+#[inline]
+pub fn date_part_second_timestamp_millis_float(ts: f64) -> f64 {
+    let (_, millis) = timestamp_to_epoch_days_and_remainder_float(ts);
+    (millis % (60 * 1000) as f64) / 1000.0
+}
+
+/// The seconds field multiplied by 1000, from 0 to 59999, like `date_part('millisecond', ...)` in SQL.
+#[inline]
+pub fn date_part_millisecond_timestamp_millis(ts: i64) -> i32 {
+    ts.rem_euclid(60 * 1000) as i32
+}
+
+#[inline]
+pub fn date_part_millisecond_timestamp_millis_float(ts: f64) -> i32 {
+    let (_, millis) = timestamp_to_epoch_days_and_remainder_float(ts);
+    (millis % (60 * 1000) as f64).floor() as i32
+}
+
+/// The number of whole seconds since the epoch, rounded down.
+#[inline]
+pub fn date_part_epoch_timestamp_millis(ts: i64) -> i64 {
+    ts.div_euclid(1000)
+}
+
+/// The number of seconds since the epoch, including fractional seconds.
+#[inline]
+pub fn date_part_epoch_timestamp_millis_float(ts: f64) -> f64 {
+    ts / 1000.0
+}
+
 #[inline]
 fn timestamp_to_epoch_days_and_remainder(ts: i64) -> (EpochDays, i64) {
     let (days, millis) = (ts.div_euclid(MILLIS_PER_DAY), ts.rem_euclid(MILLIS_PER_DAY));
@@ -115,6 +538,115 @@ pub fn date_add_month_timestamp_millis_float(ts: f64, months: i32) -> f64 {
     new_epoch_days.to_timestamp_millis_float() + millis
 }
 
+/// Moves to the next date after the one of the timestamp that falls on the given weekday, keeping the time of day.
+#[inline]
+pub fn next_weekday_timestamp_millis(ts: i64, weekday: Weekday) -> i64 {
+    let (epoch_days, millis) = timestamp_to_epoch_days_and_remainder(ts);
+    epoch_days.next_weekday(weekday).to_timestamp_millis() + millis
+}
+
+#[inline]
+pub fn next_weekday_timestamp_millis_float(ts: f64, weekday: Weekday) -> f64 {
+    let (epoch_days, millis) = timestamp_to_epoch_days_and_remainder_float(ts);
+    epoch_days.next_weekday(weekday).to_timestamp_millis_float() + millis
+}
+
+/// Moves to the last date before the one of the timestamp that falls on the given weekday, keeping the time of day.
+#[inline]
+pub fn previous_weekday_timestamp_millis(ts: i64, weekday: Weekday) -> i64 {
+    let (epoch_days, millis) = timestamp_to_epoch_days_and_remainder(ts);
+    epoch_days.previous_weekday(weekday).to_timestamp_millis() + millis
+}
+
+#[inline]
+pub fn previous_weekday_timestamp_millis_float(ts: f64, weekday: Weekday) -> f64 {
+    let (epoch_days, millis) = timestamp_to_epoch_days_and_remainder_float(ts);
+    epoch_days.previous_weekday(weekday).to_timestamp_millis_float() + millis
+}
+
+/// Moves to the last occurrence of the weekday in the month of the timestamp, keeping the time of day.
+#[inline]
+pub fn last_weekday_of_month_timestamp_millis(ts: i64, weekday: Weekday) -> i64 {
+    let (epoch_days, millis) = timestamp_to_epoch_days_and_remainder(ts);
+    epoch_days.last_weekday_of_month(weekday).to_timestamp_millis() + millis
+}
+
+#[inline]
+pub fn last_weekday_of_month_timestamp_millis_float(ts: f64, weekday: Weekday) -> f64 {
+    let (epoch_days, millis) = timestamp_to_epoch_days_and_remainder_float(ts);
+    epoch_days.last_weekday_of_month(weekday).to_timestamp_millis_float() + millis
+}
+
+/// Moves to the `n`-th occurrence of the weekday in the month of the timestamp, keeping the time of day,
+/// or returns `None` if the month does not have that many occurrences.
+#[inline]
+pub fn nth_weekday_of_month_timestamp_millis(ts: i64, n: u32, weekday: Weekday) -> Option<i64> {
+    let (epoch_days, millis) = timestamp_to_epoch_days_and_remainder(ts);
+    epoch_days
+        .nth_weekday_of_month(n, weekday)
+        .map(|date| date.to_timestamp_millis() + millis)
+}
+
+#[inline]
+pub fn nth_weekday_of_month_timestamp_millis_float(ts: f64, n: u32, weekday: Weekday) -> Option<f64> {
+    let (epoch_days, millis) = timestamp_to_epoch_days_and_remainder_float(ts);
+    epoch_days
+        .nth_weekday_of_month(n, weekday)
+        .map(|date| date.to_timestamp_millis_float() + millis)
+}
+
+#[inline]
+pub fn date_add_day_timestamp_millis(ts: i64, days: i32) -> i64 {
+    ts + days as i64 * MILLIS_PER_DAY
+}
+
+#[inline]
+pub fn date_add_day_timestamp_millis_float(ts: f64, days: i32) -> f64 {
+    ts + days as f64 * MILLIS_PER_DAY as f64
+}
+
+#[inline]
+pub fn date_add_week_timestamp_millis(ts: i64, weeks: i32) -> i64 {
+    ts + weeks as i64 * (7 * MILLIS_PER_DAY)
+}
+
+#[inline]
+pub fn date_add_week_timestamp_millis_float(ts: f64, weeks: i32) -> f64 {
+    ts + weeks as f64 * (7 * MILLIS_PER_DAY) as f64
+}
+
+/// Adds the given number of quarters, clamping the day to the end of the resulting month like `date_add_month`.
+#[inline]
+pub fn date_add_quarter_timestamp_millis(ts: i64, quarters: i32) -> i64 {
+    date_add_month_timestamp_millis(ts, quarters * 3)
+}
+
+#[inline]
+pub fn date_add_quarter_timestamp_millis_float(ts: f64, quarters: i32) -> f64 {
+    date_add_month_timestamp_millis_float(ts, quarters * 3)
+}
+
+/// Adds the given number of years, clamping february 29 to february 28 in years that are not leap years.
+#[inline]
+pub fn date_add_year_timestamp_millis(ts: i64, years: i32) -> i64 {
+    let (epoch_days, millis) = timestamp_to_epoch_days_and_remainder(ts);
+    let new_epoch_days = epoch_days.add_years(years);
+    new_epoch_days.to_timestamp_millis() + millis
+}
+
+#[inline]
+pub fn date_add_year_timestamp_millis_float(ts: f64, years: i32) -> f64 {
+    let (epoch_days, millis) = timestamp_to_epoch_days_and_remainder_float(ts);
+    let new_epoch_days = epoch_days.add_years(years);
+    new_epoch_days.to_timestamp_millis_float() + millis
+}
+
+/// Adds the months, days and time of the interval to the timestamp, see `Interval::add_to`.
+#[inline]
+pub fn date_add_interval_timestamp_millis(ts: i64, interval: &Interval) -> i64 {
+    interval.add_to(ts)
+}
+
 #[inline]
 fn timestamp_to_year_month_millis_of_month(ts: i64) -> (i32, i32, i64) {
     let (ed, millis) = timestamp_to_epoch_days_and_remainder(ts);
@@ -171,14 +703,62 @@ pub fn days_in_month_timestamp_millis_float(ts: f64) -> i32 {
     epoch_days.days_in_month()
 }
 
+/// Converts the timestamp to the same instant with an offset of zero.
+/// After this normalization, timestamps sort by their instant in time.
+#[inline]
+pub fn packed_timestamp_to_utc(ts: PackedTimestamp) -> PackedTimestamp {
+    ts.to_utc()
+}
+
+/// Normalizes a column of timestamps with mixed offsets to an offset of zero.
+pub fn packed_timestamps_to_utc(input: &[PackedTimestamp], output: &mut [PackedTimestamp]) {
+    assert_eq!(input.len(), output.len());
+    output.iter_mut().zip(input.iter()).for_each(|(output, input)| {
+        *output = packed_timestamp_to_utc(*input);
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use crate::epoch_days::EpochDays;
+    use crate::fixtures::{date_time_millis, BERLIN, SAO_PAULO};
     use crate::{
-        date_add_month_timestamp_millis, date_diff_month_timestamp_millis, date_diff_year_timestamp_millis,
-        date_trunc_month_timestamp_millis, date_trunc_quarter_timestamp_millis, date_trunc_year_timestamp_millis,
+        date_add_day_timestamp_millis, date_add_day_timestamp_millis_float, date_add_interval_timestamp_millis,
+        date_add_month_timestamp_millis, date_add_quarter_timestamp_millis, date_add_quarter_timestamp_millis_float,
+        date_add_week_timestamp_millis, date_add_week_timestamp_millis_float, date_add_year_timestamp_millis,
+        date_add_year_timestamp_millis_float, date_bin_timestamp_millis, date_bin_timestamp_millis_float,
+        date_diff_month_timestamp_millis, date_diff_year_timestamp_millis, date_part_century_timestamp_millis,
+        date_part_century_timestamp_millis_float, date_part_day_of_week_timestamp_millis,
+        date_part_day_of_week_timestamp_millis_float, date_part_day_of_year_timestamp_millis,
+        date_part_day_of_year_timestamp_millis_float, date_part_day_timestamp_millis, date_part_day_timestamp_millis_float,
+        date_part_decade_timestamp_millis, date_part_decade_timestamp_millis_float, date_part_epoch_timestamp_millis,
+        date_part_epoch_timestamp_millis_float, date_part_hour_timestamp_millis, date_part_hour_timestamp_millis_float,
+        date_part_iso_day_of_week_timestamp_millis, date_part_iso_day_of_week_timestamp_millis_float,
+        date_part_iso_week_timestamp_millis, date_part_iso_week_timestamp_millis_float, date_part_iso_year_timestamp_millis,
+        date_part_iso_year_timestamp_millis_float, date_part_millennium_timestamp_millis,
+        date_part_millennium_timestamp_millis_float, date_part_millisecond_timestamp_millis,
+        date_part_millisecond_timestamp_millis_float, date_part_minute_timestamp_millis, date_part_minute_timestamp_millis_float,
+        date_part_month_timestamp_millis, date_part_month_timestamp_millis_float, date_part_quarter_timestamp_millis,
+        date_part_quarter_timestamp_millis_float, date_part_second_timestamp_millis, date_part_second_timestamp_millis_float,
+        date_part_year_timestamp_millis, date_part_year_timestamp_millis_float, date_trunc_day_timestamp_millis,
+        date_trunc_day_timestamp_millis_float, date_trunc_day_timestamp_millis_float_in_zone,
+        date_trunc_day_timestamp_millis_float_with_offset, date_trunc_day_timestamp_millis_in_zone,
+        date_trunc_day_timestamp_millis_with_offset, date_trunc_hour_timestamp_millis, date_trunc_hour_timestamp_millis_float,
+        date_trunc_iso_year_timestamp_millis, date_trunc_iso_year_timestamp_millis_float, date_trunc_minute_timestamp_millis,
+        date_trunc_minute_timestamp_millis_float, date_trunc_month_timestamp_millis, date_trunc_month_timestamp_millis_in_zone,
+        date_trunc_month_timestamp_millis_with_offset, date_trunc_quarter_timestamp_millis,
+        date_trunc_quarter_timestamp_millis_in_zone, date_trunc_second_timestamp_millis, date_trunc_second_timestamp_millis_float,
+        date_trunc_week_timestamp_millis, date_trunc_week_timestamp_millis_float,
+        date_trunc_week_timestamp_millis_float_starting_on, date_trunc_week_timestamp_millis_in_zone,
+        date_trunc_week_timestamp_millis_starting_on, date_trunc_week_timestamp_millis_with_offset,
+        date_trunc_year_timestamp_millis, date_trunc_year_timestamp_millis_in_zone, date_trunc_year_timestamp_millis_with_offset,
+        days_in_month_timestamp_millis, days_in_month_timestamp_millis_float, last_weekday_of_month_timestamp_millis,
+        last_weekday_of_month_timestamp_millis_float, next_weekday_timestamp_millis, next_weekday_timestamp_millis_float,
+        nth_weekday_of_month_timestamp_millis, nth_weekday_of_month_timestamp_millis_float, packed_timestamp_to_utc,
+        packed_timestamps_to_utc, previous_weekday_timestamp_millis, previous_weekday_timestamp_millis_float, Interval,
+        PackedTimestamp, TimeZone, Weekday, MILLIS_PER_DAY,
     };
-    use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime};
+    use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
     use std::ops::Add;
 
     fn timestamp_to_naive_date_time(ts: i64) -> NaiveDateTime {
@@ -199,20 +779,481 @@ mod tests {
 
     #[test]
     fn test_date_trunc_year_millis() {
-        assert_eq!(1640995200_000, date_trunc_year_timestamp_millis(1640995200_000));
-        assert_eq!(1640995200_000, date_trunc_year_timestamp_millis(1658765238_000));
+        assert_eq!(1_640_995_200_000, date_trunc_year_timestamp_millis(1_640_995_200_000));
+        assert_eq!(1_640_995_200_000, date_trunc_year_timestamp_millis(1_658_765_238_000));
     }
 
     #[test]
     fn test_date_trunc_quarter_millis() {
-        assert_eq!(1640995200_000, date_trunc_quarter_timestamp_millis(1640995200_000));
-        assert_eq!(1656633600_000, date_trunc_quarter_timestamp_millis(1658766592_000));
+        assert_eq!(1_640_995_200_000, date_trunc_quarter_timestamp_millis(1_640_995_200_000));
+        assert_eq!(1_656_633_600_000, date_trunc_quarter_timestamp_millis(1_658_766_592_000));
     }
 
     #[test]
     fn test_date_trunc_month_millis() {
-        assert_eq!(1640995200_000, date_trunc_month_timestamp_millis(1640995200_000));
-        assert_eq!(1656633600_000, date_trunc_month_timestamp_millis(1658765238_000));
+        assert_eq!(1_640_995_200_000, date_trunc_month_timestamp_millis(1_640_995_200_000));
+        assert_eq!(1_656_633_600_000, date_trunc_month_timestamp_millis(1_658_765_238_000));
+    }
+
+    #[test]
+    fn test_date_trunc_sub_day() {
+        // 1960-06-15T13:45:30.250Z
+        let ts = -301227269750;
+        assert_eq!(date_trunc_hour_timestamp_millis(ts), -301230000000);
+        assert_eq!(date_trunc_minute_timestamp_millis(ts), -301227300000);
+        assert_eq!(date_trunc_second_timestamp_millis(ts), -301227270000);
+        assert_eq!(date_trunc_hour_timestamp_millis_float(ts as f64), -301230000000.0);
+        assert_eq!(date_trunc_minute_timestamp_millis_float(ts as f64), -301227300000.0);
+        assert_eq!(date_trunc_second_timestamp_millis_float(ts as f64), -301227270000.0);
+
+        assert_eq!(date_trunc_hour_timestamp_millis(-1), -3600000);
+        assert_eq!(date_trunc_minute_timestamp_millis(-1), -60000);
+        assert_eq!(date_trunc_second_timestamp_millis(-1), -1000);
+        assert_eq!(date_trunc_second_timestamp_millis(-1000), -1000);
+        assert_eq!(date_trunc_second_timestamp_millis(0), 0);
+        assert_eq!(date_trunc_hour_timestamp_millis(1581435857000), 1581433200000);
+
+        for ts in -10_000_i64..10_000 {
+            assert_eq!(
+                date_trunc_second_timestamp_millis(ts) as f64,
+                date_trunc_second_timestamp_millis_float(ts as f64)
+            );
+            assert_eq!(date_trunc_second_timestamp_millis(ts), ts.div_euclid(1000) * 1000);
+        }
+    }
+
+    #[test]
+    fn test_date_trunc_day_before_epoch() {
+        assert_eq!(date_trunc_day_timestamp_millis(-1), -MILLIS_PER_DAY);
+        assert_eq!(date_trunc_day_timestamp_millis(-MILLIS_PER_DAY), -MILLIS_PER_DAY);
+        assert_eq!(date_trunc_day_timestamp_millis(-MILLIS_PER_DAY - 1), -2 * MILLIS_PER_DAY);
+        // thursday 1970-01-01 belongs to the week starting on monday 1969-12-29
+        assert_eq!(date_trunc_week_timestamp_millis(0), -3 * MILLIS_PER_DAY);
+        assert_eq!(date_trunc_week_timestamp_millis(-1), -3 * MILLIS_PER_DAY);
+        assert_eq!(date_trunc_week_timestamp_millis(-3 * MILLIS_PER_DAY), -3 * MILLIS_PER_DAY);
+        assert_eq!(
+            date_trunc_week_timestamp_millis(-3 * MILLIS_PER_DAY - 1),
+            -10 * MILLIS_PER_DAY
+        );
+        // 1960-06-15T13:45:30.250Z was a wednesday
+        assert_eq!(date_trunc_day_timestamp_millis(-301227269750), -301276800000);
+        assert_eq!(
+            date_trunc_week_timestamp_millis(-301227269750),
+            -301276800000 - 2 * MILLIS_PER_DAY
+        );
+    }
+
+    #[test]
+    fn test_date_trunc_day_and_week_around_epoch_exhaustive() {
+        let check = |ts: i64| {
+            let day = date_trunc_day_timestamp_millis(ts);
+            assert_eq!(day, EpochDays::from_timestamp_millis(ts).to_timestamp_millis(), "{}", ts);
+            assert_eq!(day as f64, date_trunc_day_timestamp_millis_float(ts as f64), "{}", ts);
+            let week = date_trunc_week_timestamp_millis(ts);
+            assert_eq!(week as f64, date_trunc_week_timestamp_millis_float(ts as f64), "{}", ts);
+            assert!(week <= ts && ts - week < 7 * MILLIS_PER_DAY, "{}", ts);
+            // weeks start on monday, 1970-01-05 was a monday
+            assert_eq!((week - 4 * MILLIS_PER_DAY).rem_euclid(7 * MILLIS_PER_DAY), 0, "{}", ts);
+        };
+        // every millisecond around each of the day boundaries of the two weeks around the epoch
+        for day in -14..=14 {
+            for ts in day * MILLIS_PER_DAY - 2000..day * MILLIS_PER_DAY + 2000 {
+                check(ts);
+            }
+        }
+        for ts in (-21 * MILLIS_PER_DAY..21 * MILLIS_PER_DAY).step_by(999_983) {
+            check(ts);
+        }
+    }
+
+    #[test]
+    fn test_date_trunc_week_starting_on() {
+        // wednesday 2022-08-24T12:00:00Z
+        let ts = 1661342400000;
+        assert_eq!(
+            date_trunc_week_timestamp_millis_starting_on(ts, Weekday::Monday),
+            1661126400000
+        );
+        assert_eq!(
+            date_trunc_week_timestamp_millis_starting_on(ts, Weekday::Sunday),
+            1661040000000
+        );
+        assert_eq!(
+            date_trunc_week_timestamp_millis_starting_on(ts, Weekday::Wednesday),
+            1661299200000
+        );
+        assert_eq!(
+            date_trunc_week_timestamp_millis_float_starting_on(ts as f64, Weekday::Sunday),
+            1661040000000.0
+        );
+
+        for week_start in [Weekday::Monday, Weekday::Thursday, Weekday::Saturday, Weekday::Sunday] {
+            for ts in (-30 * MILLIS_PER_DAY..30 * MILLIS_PER_DAY).step_by(3_599_999) {
+                let truncated = date_trunc_week_timestamp_millis_starting_on(ts, week_start);
+                let expected = EpochDays::from_timestamp_millis(ts)
+                    .date_trunc_week(week_start)
+                    .to_timestamp_millis();
+                assert_eq!(truncated, expected, "{:?} {}", week_start, ts);
+                assert_eq!(
+                    truncated as f64,
+                    date_trunc_week_timestamp_millis_float_starting_on(ts as f64, week_start),
+                    "{:?} {}",
+                    week_start,
+                    ts
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_iso_year_kernels() {
+        // 2021-01-03T12:00:00Z belongs to week 53 of 2020, which started on 2019-12-30
+        let ts = 1609675200000;
+        assert_eq!(date_part_iso_week_timestamp_millis(ts), 53);
+        assert_eq!(date_part_iso_year_timestamp_millis(ts), 2020);
+        assert_eq!(date_trunc_iso_year_timestamp_millis(ts), 1577664000000);
+        assert_eq!(date_trunc_iso_year_timestamp_millis_float(ts as f64), 1577664000000.0);
+    }
+
+    #[test]
+    fn test_date_part_kernels_chrono() {
+        let start = chrono::NaiveDate::from_ymd(-1000, 1, 1).and_hms(0, 0, 0).timestamp_millis();
+        let end = chrono::NaiveDate::from_ymd(3000, 1, 1).and_hms(0, 0, 0).timestamp_millis();
+
+        for ts in (start..end).step_by(3 * 60 * 60 * 1000 + 59_999 + 997 * 1000 * 1000) {
+            let ndt = timestamp_to_naive_date_time(ts);
+            let tsf = ts as f64;
+            let check = |name: &str, int: i32, float: i32, expected: i32| {
+                assert_eq!(int, expected, "{} {}", name, ts);
+                assert_eq!(float, expected, "{} float {}", name, ts);
+            };
+            check(
+                "year",
+                date_part_year_timestamp_millis(ts),
+                date_part_year_timestamp_millis_float(tsf),
+                ndt.year(),
+            );
+            check(
+                "month",
+                date_part_month_timestamp_millis(ts),
+                date_part_month_timestamp_millis_float(tsf),
+                ndt.month() as i32,
+            );
+            check(
+                "quarter",
+                date_part_quarter_timestamp_millis(ts),
+                date_part_quarter_timestamp_millis_float(tsf),
+                (ndt.month() as i32 - 1) / 3 + 1,
+            );
+            check(
+                "day",
+                date_part_day_timestamp_millis(ts),
+                date_part_day_timestamp_millis_float(tsf),
+                ndt.day() as i32,
+            );
+            check(
+                "day_of_week",
+                date_part_day_of_week_timestamp_millis(ts),
+                date_part_day_of_week_timestamp_millis_float(tsf),
+                ndt.weekday().num_days_from_sunday() as i32,
+            );
+            check(
+                "iso_day_of_week",
+                date_part_iso_day_of_week_timestamp_millis(ts),
+                date_part_iso_day_of_week_timestamp_millis_float(tsf),
+                ndt.weekday().number_from_monday() as i32,
+            );
+            check(
+                "day_of_year",
+                date_part_day_of_year_timestamp_millis(ts),
+                date_part_day_of_year_timestamp_millis_float(tsf),
+                ndt.ordinal() as i32,
+            );
+            check(
+                "iso_week",
+                date_part_iso_week_timestamp_millis(ts),
+                date_part_iso_week_timestamp_millis_float(tsf),
+                ndt.iso_week().week() as i32,
+            );
+            check(
+                "iso_year",
+                date_part_iso_year_timestamp_millis(ts),
+                date_part_iso_year_timestamp_millis_float(tsf),
+                ndt.iso_week().year(),
+            );
+            check(
+                "hour",
+                date_part_hour_timestamp_millis(ts),
+                date_part_hour_timestamp_millis_float(tsf),
+                ndt.hour() as i32,
+            );
+            check(
+                "minute",
+                date_part_minute_timestamp_millis(ts),
+                date_part_minute_timestamp_millis_float(tsf),
+                ndt.minute() as i32,
+            );
+            let millisecond = ndt.second() * 1000 + ndt.timestamp_subsec_millis();
+            check(
+                "millisecond",
+                date_part_millisecond_timestamp_millis(ts),
+                date_part_millisecond_timestamp_millis_float(tsf),
+                millisecond as i32,
+            );
+            let second = millisecond as f64 / 1000.0;
+            assert_eq!(date_part_second_timestamp_millis(ts), second, "second {}", ts);
+            assert_eq!(date_part_second_timestamp_millis_float(tsf), second, "second float {}", ts);
+            assert_eq!(date_part_epoch_timestamp_millis(ts), ndt.timestamp(), "{}", ts);
+            assert_eq!(date_part_epoch_timestamp_millis_float(tsf), ts as f64 / 1000.0, "{}", ts);
+        }
+    }
+
+    #[test]
+    fn test_date_part_kernels() {
+        // 1969-12-31T23:59:58.250Z
+        let ts = -1750;
+        assert_eq!(date_part_year_timestamp_millis(ts), 1969);
+        assert_eq!(date_part_day_of_year_timestamp_millis(ts), 365);
+        assert_eq!(date_part_hour_timestamp_millis(ts), 23);
+        assert_eq!(date_part_minute_timestamp_millis(ts), 59);
+        assert_eq!(date_part_second_timestamp_millis(ts), 58.25);
+        assert_eq!(date_part_second_timestamp_millis_float(ts as f64), 58.25);
+        assert_eq!(date_part_millisecond_timestamp_millis(ts), 58250);
+        assert_eq!(date_part_millisecond_timestamp_millis_float(ts as f64), 58250);
+        assert_eq!(date_part_epoch_timestamp_millis(ts), -2);
+        assert_eq!(date_part_epoch_timestamp_millis_float(ts as f64), -1.75);
+        assert_eq!(date_part_decade_timestamp_millis(ts), 196);
+        assert_eq!(date_part_decade_timestamp_millis_float(ts as f64), 196);
+        assert_eq!(date_part_century_timestamp_millis(ts), 20);
+        assert_eq!(date_part_century_timestamp_millis_float(ts as f64), 20);
+        assert_eq!(date_part_millennium_timestamp_millis(ts), 2);
+        assert_eq!(date_part_millennium_timestamp_millis_float(ts as f64), 2);
+    }
+
+    #[test]
+    fn test_weekday_kernels() {
+        // wednesday 2022-08-24T12:34:56.789Z
+        let ts = 1661344496789;
+        assert_eq!(
+            next_weekday_timestamp_millis(ts, Weekday::Friday),
+            date_time_millis(2022, 8, 26)
+        );
+        assert_eq!(
+            next_weekday_timestamp_millis_float(ts as f64, Weekday::Friday),
+            date_time_millis(2022, 8, 26) as f64
+        );
+        assert_eq!(
+            previous_weekday_timestamp_millis(ts, Weekday::Friday),
+            date_time_millis(2022, 8, 19)
+        );
+        assert_eq!(
+            previous_weekday_timestamp_millis_float(ts as f64, Weekday::Friday),
+            date_time_millis(2022, 8, 19) as f64
+        );
+        assert_eq!(
+            last_weekday_of_month_timestamp_millis(ts, Weekday::Monday),
+            date_time_millis(2022, 8, 29)
+        );
+        assert_eq!(
+            last_weekday_of_month_timestamp_millis_float(ts as f64, Weekday::Monday),
+            date_time_millis(2022, 8, 29) as f64
+        );
+        assert_eq!(
+            nth_weekday_of_month_timestamp_millis(ts, 3, Weekday::Friday),
+            Some(date_time_millis(2022, 8, 19))
+        );
+        assert_eq!(
+            nth_weekday_of_month_timestamp_millis_float(ts as f64, 3, Weekday::Friday),
+            Some(date_time_millis(2022, 8, 19) as f64)
+        );
+        assert_eq!(nth_weekday_of_month_timestamp_millis(ts, 5, Weekday::Friday), None);
+
+        // 1969-12-31T23:00:00Z was a wednesday
+        assert_eq!(
+            next_weekday_timestamp_millis(-3_600_000, Weekday::Thursday),
+            MILLIS_PER_DAY - 3_600_000
+        );
+        assert_eq!(
+            previous_weekday_timestamp_millis(-3_600_000, Weekday::Thursday),
+            -7 * MILLIS_PER_DAY + MILLIS_PER_DAY - 3_600_000
+        );
+    }
+
+    #[test]
+    fn test_days_in_month_kernels() {
+        // 2023-01-15T00:00:00Z
+        assert_eq!(days_in_month_timestamp_millis(1673740800000), 31);
+        assert_eq!(days_in_month_timestamp_millis_float(1673740800000.0), 31);
+        // 2023-02-15T00:00:00Z
+        assert_eq!(days_in_month_timestamp_millis(1676419200000), 28);
+        // 2023-12-15T00:00:00Z
+        assert_eq!(days_in_month_timestamp_millis(1702598400000), 31);
+    }
+
+    #[test]
+    #[should_panic(expected = "stride must be positive")]
+    fn test_date_bin_zero_stride() {
+        date_bin_timestamp_millis(1581435857000, 0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "stride must be positive")]
+    fn test_date_bin_negative_stride() {
+        date_bin_timestamp_millis_float(1581435857000.0, -1000, 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "date_bin out of range")]
+    fn test_date_bin_out_of_range() {
+        date_bin_timestamp_millis(i64::MAX, 1000, -1);
+    }
+
+    #[test]
+    fn test_date_bin() {
+        let fifteen_minutes = 15 * 60 * 1000;
+        // 2020-02-11T15:44:17Z with origin 2001-01-01T00:00:00Z
+        assert_eq!(
+            date_bin_timestamp_millis(1581435857000, fifteen_minutes, 978307200000),
+            1581435000000
+        );
+        // origin 2001-01-01T00:02:30Z
+        assert_eq!(
+            date_bin_timestamp_millis(1581435857000, fifteen_minutes, 978307350000),
+            1581435150000
+        );
+        assert_eq!(
+            date_bin_timestamp_millis_float(1581435857000.0, fifteen_minutes, 978307350000.0),
+            1581435150000.0
+        );
+
+        // timestamps before the origin and before the epoch
+        assert_eq!(date_bin_timestamp_millis(-1, 10, 0), -10);
+        assert_eq!(date_bin_timestamp_millis(-1, 10, 5), -5);
+        assert_eq!(date_bin_timestamp_millis(4, 10, 5), -5);
+        assert_eq!(date_bin_timestamp_millis(5, 10, 5), 5);
+        assert_eq!(date_bin_timestamp_millis_float(-1.0, 10, 5.0), -5.0);
+        assert_eq!(date_bin_timestamp_millis(-301227269750, fifteen_minutes, 0), -301227300000);
+
+        assert_eq!(
+            date_bin_timestamp_millis(1581435857000, MILLIS_PER_DAY, 0),
+            date_trunc_day_timestamp_millis(1581435857000)
+        );
+    }
+
+    #[test]
+    fn test_date_trunc_with_offset() {
+        // 2022-07-25T16:07:18Z is 2022-07-26T01:07:18+09:00
+        assert_eq!(
+            date_trunc_day_timestamp_millis_with_offset(1_658_765_238_000, 9 * 60),
+            1_658_761_200_000
+        );
+        assert_eq!(
+            date_trunc_day_timestamp_millis_with_offset(1_658_765_238_000, -60),
+            1_658_707_200_000 + 3_600_000
+        );
+        assert_eq!(
+            date_trunc_day_timestamp_millis_float_with_offset(1_658_765_238_000.0, 9 * 60),
+            1_658_761_200_000.0
+        );
+        // monday 2022-07-25T00:00+09:00
+        assert_eq!(
+            date_trunc_week_timestamp_millis_with_offset(1_658_765_238_000, 9 * 60),
+            1_658_674_800_000
+        );
+        // 2022-07-01T00:00-05:00
+        assert_eq!(
+            date_trunc_month_timestamp_millis_with_offset(1_658_765_238_000, -5 * 60),
+            1_656_651_600_000
+        );
+        // 2021-12-31T23:30Z is already 2022 in +01:00
+        assert_eq!(
+            date_trunc_year_timestamp_millis_with_offset(1_640_993_400_000, 60),
+            1_640_991_600_000
+        );
+
+        let fixed = TimeZone::fixed(9 * 60);
+        for ts in (1_640_995_200_000..1_672_531_200_000).step_by(3_600_000 * 7 + 1234) {
+            assert_eq!(
+                date_trunc_day_timestamp_millis_with_offset(ts, 9 * 60),
+                date_trunc_day_timestamp_millis_in_zone(ts, &fixed)
+            );
+            assert_eq!(
+                date_trunc_month_timestamp_millis_with_offset(ts, 9 * 60),
+                date_trunc_month_timestamp_millis_in_zone(ts, &fixed)
+            );
+        }
+    }
+
+    #[test]
+    fn test_date_trunc_in_zone_dst_days() {
+        let berlin = TimeZone::from_tzif_bytes(BERLIN).unwrap();
+
+        // 2022-03-27 is 23 hours long
+        let start = date_trunc_day_timestamp_millis_in_zone(1648375200000, &berlin);
+        let end = date_trunc_day_timestamp_millis_in_zone(1648461600000, &berlin);
+        assert_eq!(start, 1648335600000);
+        assert_eq!(end, 1648418400000);
+        assert_eq!(end - start, 23 * 3_600_000);
+
+        // 2022-10-30 is 25 hours long
+        let start = date_trunc_day_timestamp_millis_in_zone(1667127600000, &berlin);
+        let end = date_trunc_day_timestamp_millis_in_zone(1667214000000, &berlin);
+        assert_eq!(start, 1667080800000);
+        assert_eq!(end, 1667170800000);
+        assert_eq!(end - start, 25 * 3_600_000);
+
+        assert_eq!(
+            date_trunc_day_timestamp_millis_float_in_zone(1667127600000.0, &berlin),
+            1667080800000.0
+        );
+    }
+
+    #[test]
+    fn test_date_trunc_in_zone() {
+        let berlin = TimeZone::from_tzif_bytes(BERLIN).unwrap();
+        // wednesday 2022-03-30T12:00+02:00, the week started on monday with a different offset than the previous day
+        assert_eq!(
+            date_trunc_week_timestamp_millis_in_zone(1648634400000, &berlin),
+            1648418400000
+        );
+        // 2022-04-15T12:00+02:00
+        assert_eq!(
+            date_trunc_month_timestamp_millis_in_zone(1650016800000, &berlin),
+            1648764000000
+        );
+        assert_eq!(
+            date_trunc_quarter_timestamp_millis_in_zone(1650016800000, &berlin),
+            1648764000000
+        );
+        // 2022-03-31T22:00+02:00, the month started with an offset of +01:00
+        assert_eq!(
+            date_trunc_month_timestamp_millis_in_zone(1648756800000, &berlin),
+            1646089200000
+        );
+        // 2022-01-01T00:30+01:00
+        assert_eq!(
+            date_trunc_year_timestamp_millis_in_zone(1640993400000, &berlin),
+            1640991600000
+        );
+    }
+
+    #[test]
+    fn test_date_trunc_in_zone_midnight_gap() {
+        // clocks in Sao Paulo were turned forward from 2018-11-04T00:00 to 01:00
+        let sao_paulo = TimeZone::from_tzif_bytes(SAO_PAULO).unwrap();
+        assert_eq!(
+            date_trunc_day_timestamp_millis_in_zone(1541340000000, &sao_paulo),
+            1541300400000
+        );
+        assert_eq!(
+            date_trunc_day_timestamp_millis_in_zone(1541300400000, &sao_paulo),
+            1541300400000
+        );
+        // 2018-11-03T12:00-03:00
+        assert_eq!(
+            date_trunc_day_timestamp_millis_in_zone(1541257200000, &sao_paulo),
+            1541214000000
+        );
     }
 
     #[test]
@@ -263,10 +1304,96 @@ mod tests {
         assert_eq!(epoch_day.add_months(-7), EpochDays::from_ymd(2021, 12, 31));
     }
 
+    #[test]
+    fn test_date_add_day_and_week() {
+        // 1969-12-31T23:00:00Z
+        assert_eq!(date_add_day_timestamp_millis(-3_600_000, 1), MILLIS_PER_DAY - 3_600_000);
+        assert_eq!(date_add_day_timestamp_millis(-3_600_000, -1), -MILLIS_PER_DAY - 3_600_000);
+        assert_eq!(
+            date_add_day_timestamp_millis_float(-3_600_000.0, -1),
+            (-MILLIS_PER_DAY - 3_600_000) as f64
+        );
+        assert_eq!(date_add_week_timestamp_millis(1_661_102_969_000, 2), 1_662_312_569_000);
+        assert_eq!(date_add_week_timestamp_millis(1_661_102_969_000, -2), 1_659_893_369_000);
+        assert_eq!(
+            date_add_week_timestamp_millis_float(1_661_102_969_000.0, 2),
+            1_662_312_569_000.0
+        );
+    }
+
+    #[test]
+    fn test_date_add_quarter_and_year() {
+        assert_eq!(
+            date_add_quarter_timestamp_millis(date_time_millis(2022, 11, 30), 1),
+            date_time_millis(2023, 2, 28)
+        );
+        assert_eq!(
+            date_add_quarter_timestamp_millis(date_time_millis(2022, 5, 31), -1),
+            date_time_millis(2022, 2, 28)
+        );
+        assert_eq!(
+            date_add_quarter_timestamp_millis_float(date_time_millis(2022, 11, 30) as f64, 1),
+            date_time_millis(2023, 2, 28) as f64
+        );
+        assert_eq!(
+            date_add_year_timestamp_millis(date_time_millis(2024, 2, 29), 1),
+            date_time_millis(2025, 2, 28)
+        );
+        assert_eq!(
+            date_add_year_timestamp_millis(date_time_millis(2024, 2, 29), 4),
+            date_time_millis(2028, 2, 29)
+        );
+        assert_eq!(
+            date_add_year_timestamp_millis(date_time_millis(2024, 2, 29), -100),
+            date_time_millis(1924, 2, 29)
+        );
+        assert_eq!(
+            date_add_year_timestamp_millis(date_time_millis(2000, 2, 29), -100),
+            date_time_millis(1900, 2, 28)
+        );
+        assert_eq!(
+            date_add_year_timestamp_millis_float(date_time_millis(2024, 2, 29) as f64, 1),
+            date_time_millis(2025, 2, 28) as f64
+        );
+        assert_eq!(
+            date_add_year_timestamp_millis(date_time_millis(1969, 12, 31), 1),
+            date_time_millis(1970, 12, 31)
+        );
+
+        for t in (date_time_millis(1960, 1, 1)..date_time_millis(1980, 1, 1)).step_by(3 * 60 * 60 * 1000 + 59_999) {
+            assert_eq!(
+                date_add_year_timestamp_millis(t, 3),
+                date_add_month_timestamp_millis(t, 36),
+                "{}",
+                t
+            );
+            assert_eq!(
+                date_add_year_timestamp_millis(t, -3),
+                date_add_month_timestamp_millis(t, -36),
+                "{}",
+                t
+            );
+            assert_eq!(
+                date_add_year_timestamp_millis_float(t as f64, 3),
+                date_add_year_timestamp_millis(t, 3) as f64,
+                "{}",
+                t
+            );
+        }
+    }
+
+    #[test]
+    fn test_date_add_interval() {
+        assert_eq!(
+            date_add_interval_timestamp_millis(date_time_millis(2024, 1, 30), &Interval::new(1, 1, 1_000_000_000)),
+            date_time_millis(2024, 3, 1) + 1000
+        );
+    }
+
     #[test]
     fn test_date_add_months_timestamp_millis() {
-        assert_eq!(date_add_month_timestamp_millis(1661102969_000, 1), 1663781369000);
-        assert_eq!(date_add_month_timestamp_millis(1661102969_000, 12), 1692638969000);
+        assert_eq!(date_add_month_timestamp_millis(1_661_102_969_000, 1), 1663781369000);
+        assert_eq!(date_add_month_timestamp_millis(1_661_102_969_000, 12), 1692638969000);
     }
 
     #[test]
@@ -361,6 +1488,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_packed_timestamps_to_utc() {
+        let input = [
+            PackedTimestamp::new(2022, 8, 21, 10, 0, 0, 0, 120),
+            PackedTimestamp::new_utc(2022, 8, 21, 9, 0, 0, 0),
+            PackedTimestamp::new(2022, 8, 21, 0, 30, 0, 0, -540),
+        ];
+        let mut output = [PackedTimestamp::from_value(0); 3];
+        packed_timestamps_to_utc(&input, &mut output);
+        assert_eq!(
+            output,
+            [
+                PackedTimestamp::new_utc(2022, 8, 21, 8, 0, 0, 0),
+                PackedTimestamp::new_utc(2022, 8, 21, 9, 0, 0, 0),
+                PackedTimestamp::new_utc(2022, 8, 21, 9, 30, 0, 0),
+            ]
+        );
+        output.sort();
+        assert_eq!(output[0], packed_timestamp_to_utc(input[0]));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_kernels_negative_years() {
+        let start = chrono::NaiveDate::from_ymd(-1000, 1, 1).and_hms(0, 0, 0).timestamp_millis();
+        let end = chrono::NaiveDate::from_ymd(1, 1, 1).and_hms(0, 0, 0).timestamp_millis();
+
+        for ts in (start..end).step_by(3 * 60 * 60 * 1000 + 59_999) {
+            assert_eq!(date_trunc_year_timestamp_millis(ts), date_trunc_year_chrono(ts), "{}", ts);
+            assert_eq!(date_trunc_month_timestamp_millis(ts), date_trunc_month_chrono(ts), "{}", ts);
+        }
+
+        let ts = chrono::NaiveDate::from_ymd(-44, 3, 15).and_hms(12, 0, 0).timestamp_millis();
+        let expected = chrono::NaiveDate::from_ymd(-43, 2, 15).and_hms(12, 0, 0).timestamp_millis();
+        assert_eq!(date_add_month_timestamp_millis(ts, 11), expected);
+        let expected = chrono::NaiveDate::from_ymd(-44, 1, 1).and_hms(0, 0, 0).timestamp_millis();
+        assert_eq!(date_trunc_quarter_timestamp_millis(ts), expected);
+        assert_eq!(date_part_year_timestamp_millis(ts), -44);
+        assert_eq!(date_part_month_timestamp_millis(ts), 3);
+    }
+
     #[test]
     #[cfg_attr(any(miri, not(feature = "expensive_tests")), ignore)]
     fn test_date_trunc_year_exhaustive() {
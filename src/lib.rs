@@ -4,20 +4,42 @@
 #![allow(clippy::too_many_arguments)]
 #![allow(clippy::manual_range_contains)]
 
+mod batch;
+mod calendar;
 mod datetime;
+mod dispatch;
+mod duration;
 mod epoch_days;
 mod error;
 mod format;
+#[cfg(test)]
+mod fixtures;
+mod interval;
 mod kernels;
 mod packed;
+mod packed_nanos;
 mod parse;
+mod pattern;
+mod posix_tz;
+mod timezone;
 mod util;
 
+pub use batch::*;
+pub use calendar::*;
+pub use dispatch::*;
+pub use duration::*;
 pub use epoch_days::*;
 pub use error::*;
 pub use format::*;
+pub use interval::*;
 pub use kernels::*;
 pub use packed::*;
+pub use packed_nanos::*;
 pub use parse::*;
+pub use pattern::*;
+pub use posix_tz::*;
+pub use timezone::*;
 
 pub(crate) const MILLIS_PER_DAY: i64 = 24 * 60 * 60 * 1000;
+pub(crate) const MICROS_PER_DAY: i64 = MILLIS_PER_DAY * 1000;
+pub(crate) const NANOS_PER_DAY: i64 = MICROS_PER_DAY * 1000;
@@ -4,8 +4,10 @@
 
 use crate::datetime::DateTimeComponents;
 use crate::format::*;
-use crate::{EpochDays, ParseError, ParseResult};
+use crate::{DateTimeField, EpochDays, ParseError, ParseResult, RangeError, MILLIS_PER_DAY};
+use std::cmp::Ordering;
 use std::fmt::{Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 
 const OFFSET_BITS: u32 = 12;
@@ -19,16 +21,21 @@ const YEAR_BITS: u32 = 64 - (MONTH_BITS + DAY_BITS + HOUR_BITS + MINUTE_BITS + S
 
 const MIN_YEAR_INTERNAL: i32 = -(1 << (YEAR_BITS - 1));
 const MAX_YEAR_INTERNAL: i32 = (1 << (YEAR_BITS - 1)) - 1;
-const MIN_YEAR: i32 = -9999;
-const MAX_YEAR: i32 = 9999;
+pub(crate) const MIN_YEAR: i32 = -9999;
+pub(crate) const MAX_YEAR: i32 = 9999;
 
 const MIN_OFFSET_MINUTES_INTERNAL: i32 = -(1 << (OFFSET_BITS - 1));
 const MAX_OFFSET_MINUTES_INTERNAL: i32 = (1 << (OFFSET_BITS - 1)) - 1;
 
 const MAX_OFFSET_HOURS: i32 = 18;
 const MIN_OFFSET_HOURS: i32 = -18;
-const MIN_OFFSET_MINUTES: i32 = MIN_OFFSET_HOURS * 60;
-const MAX_OFFSET_MINUTES: i32 = MAX_OFFSET_HOURS * 60;
+pub(crate) const MIN_OFFSET_MINUTES: i32 = MIN_OFFSET_HOURS * 60;
+pub(crate) const MAX_OFFSET_MINUTES: i32 = MAX_OFFSET_HOURS * 60;
+
+/// -9999-01-01T00:00:00.000Z, which is 25 cycles of 400 years before 0000-01-01, minus the leap year -10000.
+pub(crate) const MIN_TIMESTAMP_MILLIS: i64 = -(25 * 146097 - 366 + 719528) * MILLIS_PER_DAY;
+/// 9999-12-31T23:59:59.999Z
+pub(crate) const MAX_TIMESTAMP_MILLIS: i64 = 2932897 * MILLIS_PER_DAY - 1;
 
 #[allow(clippy::assertions_on_constants)]
 const _: () = {
@@ -36,12 +43,40 @@ const _: () = {
     assert!(MIN_OFFSET_MINUTES_INTERNAL < MIN_OFFSET_MINUTES || MAX_OFFSET_MINUTES_INTERNAL > MAX_OFFSET_MINUTES);
 };
 
-#[derive(PartialEq, Clone, Copy, Ord, PartialOrd, Eq)]
+/// A bit-packed timestamp consisting of the local date and time fields and a timezone offset.
+///
+/// The `Eq` and `Ord` implementations compare the local fields from year to millisecond and then the offset,
+/// that means timestamps with different offsets do not compare by their instant in time.
+/// Use `cmp_instant` and `eq_instant` or the `ByInstant` wrapper for that.
+#[derive(PartialEq, Clone, Copy, Eq)]
 #[repr(transparent)]
 pub struct PackedTimestamp {
     value: u64,
 }
 
+impl PackedTimestamp {
+    /// The packed value with the sign bits of the year and offset flipped,
+    /// so that comparing it as unsigned integer orders negative years and offsets before positive ones.
+    #[inline]
+    fn sort_key(&self) -> u64 {
+        self.value ^ (1 << 63 | 1 << (OFFSET_BITS - 1))
+    }
+}
+
+impl PartialOrd for PackedTimestamp {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PackedTimestamp {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
 impl PackedTimestamp {
     #[inline]
     #[allow(clippy::too_many_arguments)]
@@ -69,6 +104,60 @@ impl PackedTimestamp {
         Self { value }
     }
 
+    /// Creates a timestamp after validating that all components are within their valid range,
+    /// including the number of days of the month, leap years and the supported range of years and offsets.
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_new(
+        year: i32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        minute: u32,
+        second: u32,
+        milli: u32,
+        offset_minutes: i32,
+    ) -> Result<Self, RangeError> {
+        if milli > 999 {
+            return Err(RangeError(DateTimeField::Millisecond));
+        }
+        let components = DateTimeComponents::new_with_offset_minute(
+            year,
+            saturating_u8(month),
+            saturating_u8(day),
+            saturating_u8(hour),
+            saturating_u8(minute),
+            saturating_u8(second),
+            milli,
+            offset_minutes,
+        );
+        components.validate()?;
+        Ok(components.to_packed())
+    }
+
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_new_utc(
+        year: i32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        minute: u32,
+        second: u32,
+        milli: u32,
+    ) -> Result<Self, RangeError> {
+        Self::try_new(year, month, day, hour, minute, second, milli, 0)
+    }
+
+    /// Converts milliseconds since the unix epoch, returning an error if the resulting year is outside of the supported range.
+    #[inline]
+    pub fn try_from_timestamp_millis(ts: i64) -> Result<Self, RangeError> {
+        if ts < MIN_TIMESTAMP_MILLIS || ts > MAX_TIMESTAMP_MILLIS {
+            return Err(RangeError(DateTimeField::Year));
+        }
+        Ok(Self::from_timestamp_millis(ts))
+    }
+
     #[inline]
     pub fn from_value(value: u64) -> Self {
         Self { value }
@@ -90,13 +179,13 @@ impl PackedTimestamp {
             components.hour as _,
             components.minute as _,
             components.second as _,
-            components.millisecond,
+            components.millisecond(),
         )
     }
 
     #[inline]
     pub fn to_timestamp_millis(&self) -> i64 {
-        let date_part = EpochDays::from_ymd(self.year() as i32, self.month() as i32, self.day() as i32).to_timestamp_millis();
+        let date_part = EpochDays::from_ymd(self.year(), self.month() as i32, self.day() as i32).to_timestamp_millis();
 
         let h = self.hour() as i64;
         let m = self.minute() as i64;
@@ -110,17 +199,60 @@ impl PackedTimestamp {
         date_part + time_part
     }
 
-    pub fn from_rfc3339_bytes(input: &[u8]) -> ParseResult<Self> {
-        #[cfg(all(not(miri), target_feature = "sse4.1"))]
-        {
-            let ts = crate::parse::parse_simd(input)?;
-            Ok(ts.to_packed())
-        }
-        #[cfg(not(all(not(miri), target_feature = "sse4.1")))]
-        {
-            let ts = crate::parse::parse_scalar(input)?;
-            Ok(ts.to_packed())
+    /// Compares the instants in time represented by both timestamps, taking their offsets into account.
+    #[inline]
+    pub fn cmp_instant(&self, other: &Self) -> Ordering {
+        self.to_timestamp_millis().cmp(&other.to_timestamp_millis())
+    }
+
+    /// Returns true if both timestamps represent the same instant in time, even if their offsets differ.
+    #[inline]
+    pub fn eq_instant(&self, other: &Self) -> bool {
+        self.to_timestamp_millis() == other.to_timestamp_millis()
+    }
+
+    /// Converts to the same instant in time with an offset of zero, rewriting the local date and time fields.
+    #[inline]
+    pub fn to_utc(&self) -> Self {
+        self.with_offset_unchecked(0)
+    }
+
+    /// Converts to the same instant in time with the given offset, rewriting the local date and time fields.
+    /// A leap second is normalized to the first second of the following minute.
+    ///
+    /// # Panics
+    ///
+    /// If the offset is outside the supported range of -18:00 to +18:00, see `try_with_offset`.
+    #[inline]
+    pub fn with_offset(&self, offset_minutes: i32) -> Self {
+        assert!(
+            offset_minutes >= MIN_OFFSET_MINUTES && offset_minutes <= MAX_OFFSET_MINUTES,
+            "offset out of range"
+        );
+        self.with_offset_unchecked(offset_minutes)
+    }
+
+    /// Converts to the same instant in time with the given offset like `with_offset`,
+    /// returning an error if the offset is outside the supported range.
+    #[inline]
+    pub fn try_with_offset(&self, offset_minutes: i32) -> Result<Self, RangeError> {
+        if offset_minutes < MIN_OFFSET_MINUTES || offset_minutes > MAX_OFFSET_MINUTES {
+            return Err(RangeError(DateTimeField::OffsetMinutes));
         }
+        Ok(self.with_offset_unchecked(offset_minutes))
+    }
+
+    #[inline]
+    fn with_offset_unchecked(&self, offset_minutes: i32) -> Self {
+        let local = self.to_timestamp_millis() + offset_minutes as i64 * 60 * 1000;
+        let mut components = DateTimeComponents::from_timestamp_millis(local);
+        components.offset_minute = offset_minutes;
+        components.to_packed()
+    }
+
+    pub fn from_rfc3339_bytes(input: &[u8]) -> ParseResult<Self> {
+        let ts = crate::dispatch::parse_components(input)?;
+        Ok(ts.to_packed())
     }
 
     pub fn from_rfc3339_str(input: &str) -> ParseResult<Self> {
@@ -128,8 +260,9 @@ impl PackedTimestamp {
     }
 
     #[inline]
-    pub fn year(&self) -> u32 {
-        (self.value >> (MONTH_BITS + DAY_BITS + HOUR_BITS + MINUTE_BITS + SECOND_BITS + MILLI_BITS + OFFSET_BITS)) as u32
+    pub fn year(&self) -> i32 {
+        // year is stored in the highest bits, an arithmetic shift does the sign extension
+        ((self.value as i64) >> (MONTH_BITS + DAY_BITS + HOUR_BITS + MINUTE_BITS + SECOND_BITS + MILLI_BITS + OFFSET_BITS)) as i32
     }
 
     #[inline]
@@ -173,25 +306,19 @@ impl PackedTimestamp {
     #[inline]
     pub fn write_rfc3339_bytes<W: std::io::Write>(&self, mut writer: W) -> std::io::Result<()> {
         let buffer = self.to_rfc3339_bytes();
-        writer.write_all(&buffer)
+        writer.write_all(buffer.as_bytes())
     }
 
     #[inline]
     pub fn write_rfc3339_str<W: std::fmt::Write>(&self, mut writer: W) -> std::fmt::Result {
         let buffer = self.to_rfc3339_bytes();
-        #[cfg(not(debug_assertions))]
-        {
-            writer.write_str(unsafe { std::str::from_utf8_unchecked(&buffer) })
-        }
-        #[cfg(debug_assertions)]
-        {
-            writer.write_str(std::str::from_utf8(&buffer).expect("utf8 string"))
-        }
+        writer.write_str(buffer.as_str())
     }
 
+    /// Formats the timestamp including its timezone offset, using `Z` only if the offset is zero.
     #[inline]
-    pub fn to_rfc3339_bytes(&self) -> [u8; 24] {
-        format_to_rfc3339_utc_bytes(
+    pub fn to_rfc3339_bytes(&self) -> FormattedTimestamp {
+        format_to_rfc3339_bytes(
             self.year(),
             self.month(),
             self.day(),
@@ -199,23 +326,37 @@ impl PackedTimestamp {
             self.minute(),
             self.second(),
             self.millisecond(),
+            self.offset_minutes(),
+        )
+    }
+
+    /// Formats the timestamp including its timezone offset according to the given options.
+    #[inline]
+    pub fn to_rfc3339_bytes_with_options(&self, options: &FormatOptions) -> FormattedTimestamp {
+        format_to_rfc3339_bytes_with_options(
+            self.year(),
+            self.month(),
+            self.day(),
+            self.hour(),
+            self.minute(),
+            self.second(),
+            self.millisecond() * 1_000_000,
+            self.offset_minutes(),
+            options,
         )
     }
 
     #[inline]
     pub fn to_rfc3339_string(&self) -> String {
-        let buffer = self.to_rfc3339_bytes();
-        #[cfg(not(debug_assertions))]
-        {
-            unsafe { std::str::from_utf8_unchecked(&buffer).to_string() }
-        }
-        #[cfg(debug_assertions)]
-        {
-            std::str::from_utf8(&buffer).expect("utf8 string").to_string()
-        }
+        self.to_rfc3339_bytes().as_str().to_string()
     }
 }
 
+#[inline]
+pub(crate) fn saturating_u8(value: u32) -> u8 {
+    value.min(u8::MAX as u32) as u8
+}
+
 impl Display for PackedTimestamp {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         self.write_rfc3339_str(f)
@@ -230,16 +371,31 @@ impl Debug for PackedTimestamp {
         }
         #[cfg(debug_assertions)]
         {
+            let year = self.year();
+            if year < 0 {
+                f.write_fmt(format_args!("-{:04}", year.unsigned_abs()))?;
+            } else if year > 9999 {
+                f.write_fmt(format_args!("+{:04}", year))?;
+            } else {
+                f.write_fmt(format_args!("{:04}", year))?;
+            }
             f.write_fmt(format_args!(
-                "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
-                self.year(),
+                "-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}",
                 self.month(),
                 self.day(),
                 self.hour(),
                 self.minute(),
                 self.second(),
                 self.millisecond()
-            ))
+            ))?;
+            let offset_minutes = self.offset_minutes();
+            if offset_minutes == 0 {
+                f.write_str("Z")
+            } else {
+                let sign = if offset_minutes < 0 { '-' } else { '+' };
+                let offset = offset_minutes.unsigned_abs();
+                f.write_fmt(format_args!("{}{:02}:{:02}", sign, offset / 60, offset % 60))
+            }
         }
     }
 }
@@ -251,6 +407,47 @@ impl From<EpochDays> for PackedTimestamp {
     }
 }
 
+/// Wrapper around a `PackedTimestamp` that implements `Eq`, `Ord` and `Hash`
+/// based on the instant in time instead of the local date and time fields.
+#[derive(Clone, Copy, Debug)]
+#[repr(transparent)]
+pub struct ByInstant(pub PackedTimestamp);
+
+impl PartialEq for ByInstant {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_instant(&other.0)
+    }
+}
+
+impl Eq for ByInstant {}
+
+impl PartialOrd for ByInstant {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ByInstant {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp_instant(&other.0)
+    }
+}
+
+impl Hash for ByInstant {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_timestamp_millis().hash(state)
+    }
+}
+
+impl From<PackedTimestamp> for ByInstant {
+    fn from(ts: PackedTimestamp) -> Self {
+        ByInstant(ts)
+    }
+}
+
 impl TryFrom<&str> for PackedTimestamp {
     type Error = ParseError;
 
@@ -269,7 +466,8 @@ impl FromStr for PackedTimestamp {
 
 #[cfg(test)]
 pub mod tests {
-    use crate::{PackedTimestamp, ParseError};
+    use crate::{ByInstant, DateTimeField, Expected, FormatOptions, FractionalDigits, PackedTimestamp, ParseError, RangeError};
+    use std::cmp::Ordering;
 
     #[test]
     fn test_format() {
@@ -291,6 +489,112 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_format_with_options() {
+        let options = FormatOptions::new()
+            .with_fractional_digits(FractionalDigits::None)
+            .with_zero_offset_as_z(false);
+        assert_eq!(
+            PackedTimestamp::new_utc(2022, 8, 21, 17, 30, 15, 250)
+                .to_rfc3339_bytes_with_options(&options)
+                .as_str(),
+            "2022-08-21T17:30:15+00:00"
+        );
+        assert_eq!(
+            PackedTimestamp::new(2022, 8, 21, 17, 30, 15, 250, -150)
+                .to_rfc3339_bytes_with_options(&options.with_fractional_digits(FractionalDigits::Nanos))
+                .as_str(),
+            "2022-08-21T17:30:15.250000000-02:30"
+        );
+    }
+
+    #[test]
+    fn test_format_with_offset() {
+        assert_eq!(
+            PackedTimestamp::new(2022, 8, 21, 17, 30, 15, 250, 120).to_rfc3339_string(),
+            "2022-08-21T17:30:15.250+02:00".to_owned()
+        );
+        assert_eq!(
+            PackedTimestamp::new(2022, 8, 21, 17, 30, 15, 250, -150).to_rfc3339_string(),
+            "2022-08-21T17:30:15.250-02:30".to_owned()
+        );
+        assert_eq!(
+            PackedTimestamp::new(2022, 8, 21, 17, 30, 15, 250, 0).to_string(),
+            "2022-08-21T17:30:15.250Z".to_owned()
+        );
+        assert_eq!(
+            format!("{:?}", PackedTimestamp::new(2022, 8, 21, 17, 30, 15, 250, -120)),
+            "2022-08-21T17:30:15.250-02:00".to_owned()
+        );
+
+        let mut buffer = vec![];
+        PackedTimestamp::new(2022, 8, 21, 17, 30, 15, 250, 345)
+            .write_rfc3339_bytes(&mut buffer)
+            .unwrap();
+        assert_eq!(buffer.as_slice(), b"2022-08-21T17:30:15.250+05:45");
+    }
+
+    #[test]
+    fn test_negative_year() {
+        let ts = PackedTimestamp::new(-44, 3, 15, 12, 30, 0, 0, 60);
+        assert_eq!(ts.year(), -44);
+        assert_eq!(ts.month(), 3);
+        assert_eq!(ts.day(), 15);
+        assert_eq!(ts.offset_minutes(), 60);
+        assert_eq!(ts.to_string(), "-0044-03-15T12:30:00.000+01:00");
+        assert_eq!(format!("{:?}", ts), "-0044-03-15T12:30:00.000+01:00");
+        assert_eq!("-0044-03-15T12:30:00.000+01:00".parse(), Ok(ts));
+
+        let expected = chrono::NaiveDate::from_ymd(-44, 3, 15).and_hms(11, 30, 0).timestamp_millis();
+        assert_eq!(ts.to_timestamp_millis(), expected);
+        assert_eq!(
+            PackedTimestamp::from_timestamp_millis(expected),
+            PackedTimestamp::new_utc(-44, 3, 15, 11, 30, 0, 0)
+        );
+
+        assert_eq!(PackedTimestamp::new_ymd_utc(-9999, 1, 1).year(), -9999);
+        assert_eq!(PackedTimestamp::new_ymd_utc(0, 1, 1).to_string(), "0000-01-01T00:00:00.000Z");
+        assert_eq!(
+            PackedTimestamp::try_from_timestamp_millis(-377705116800000),
+            Ok(PackedTimestamp::new_ymd_utc(-9999, 1, 1))
+        );
+    }
+
+    #[test]
+    fn test_parse_expanded_year() {
+        assert_eq!(
+            "+2022-08-21T17:30:15.250Z".parse(),
+            Ok(PackedTimestamp::new_utc(2022, 8, 21, 17, 30, 15, 250))
+        );
+        assert_eq!(
+            "-0001-12-31 23:59:59Z".parse(),
+            Ok(PackedTimestamp::new_utc(-1, 12, 31, 23, 59, 59, 0))
+        );
+        assert_eq!(
+            "+12345-01-01T00:00:00Z".parse::<PackedTimestamp>(),
+            Err(ParseError::InvalidValue(DateTimeField::Year))
+        );
+        assert_eq!(
+            "-10000-01-01T00:00:00Z".parse::<PackedTimestamp>(),
+            Err(ParseError::InvalidValue(DateTimeField::Year))
+        );
+    }
+
+    #[test]
+    fn test_parse_format_roundtrip() {
+        for input in [
+            "2022-08-21T17:30:15.250Z",
+            "2022-08-21T17:30:15.250+02:00",
+            "2022-08-21T17:30:15.250-02:00",
+            "2022-08-21T17:30:15.250+05:45",
+            "2022-08-21T17:30:15.250-18:00",
+        ] {
+            let ts: PackedTimestamp = input.parse().unwrap();
+            assert_eq!(ts.to_string(), input);
+            assert_eq!(ts.to_string().parse::<PackedTimestamp>().unwrap(), ts);
+        }
+    }
+
     #[test]
     fn test_parse() {
         assert_eq!(
@@ -343,10 +647,268 @@ pub mod tests {
         );
         assert_eq!(
             PackedTimestamp::try_from("2022-08-21 XX:YY::ZZZ"),
-            Err(ParseError::InvalidChar(11))
+            Err(ParseError::InvalidChar {
+                position: 11,
+                byte: b'X',
+                expected: Expected::Digit
+            })
+        );
+        assert_eq!(
+            PackedTimestamp::try_from("2022-08-21T12:60:00Z"),
+            Err(ParseError::InvalidValue(DateTimeField::Minute))
+        );
+    }
+
+    #[test]
+    fn test_parse_error_display() {
+        let message = |input: &str| PackedTimestamp::try_from(input).unwrap_err().to_string();
+        assert_eq!(message("2022-08-21 00:00"), "unexpected end of input at byte 16");
+        assert_eq!(
+            message("2022-08-21 XX:YY::ZZZ"),
+            "invalid character 'X' at byte 11, expected a digit"
+        );
+        assert_eq!(
+            message("2022-08-21/00:00:00Z"),
+            "invalid character '/' at byte 10, expected 'T' or ' '"
+        );
+        assert_eq!(
+            message("2022-08-21T00:00:00.000Y"),
+            "invalid character 'Y' at byte 23, expected 'Z', '+' or '-'"
+        );
+        assert_eq!(
+            message("2022-08-21T00:00:00ZZ"),
+            "unexpected trailing character 'Z' at byte 20"
+        );
+        assert_eq!(message("2023-02-30T00:00:00Z"), "invalid value for day");
+        assert_eq!(
+            message("2023-02-28T00:00:00+01:60"),
+            "invalid value for timezone offset"
+        );
+        assert_eq!(
+            message("2022-08-21T00:00:00\u{e4}"),
+            "invalid character '\\xc3' at byte 19, expected 'Z', '+' or '-'"
+        );
+
+        let error = PackedTimestamp::try_from("2022-08-21 XX:YY").unwrap_err();
+        assert_eq!(error.position(), Some(11));
+        assert_eq!(error.field(), None);
+        let error: Box<dyn std::error::Error> = Box::new(PackedTimestamp::try_from("2023-02-30T00:00:00Z").unwrap_err());
+        assert_eq!(error.to_string(), "invalid value for day");
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(
+            PackedTimestamp::try_new(2022, 8, 21, 17, 30, 15, 250, 120),
+            Ok(PackedTimestamp::new(2022, 8, 21, 17, 30, 15, 250, 120))
+        );
+        assert_eq!(
+            PackedTimestamp::try_new_utc(2024, 2, 29, 23, 59, 60, 999),
+            Ok(PackedTimestamp::new_utc(2024, 2, 29, 23, 59, 60, 999))
+        );
+        assert_eq!(
+            PackedTimestamp::try_new_utc(2000, 2, 29, 0, 0, 0, 0),
+            Ok(PackedTimestamp::new_utc(2000, 2, 29, 0, 0, 0, 0))
+        );
+        assert_eq!(
+            PackedTimestamp::try_new(9999, 12, 31, 0, 0, 0, 0, -1080),
+            Ok(PackedTimestamp::new(9999, 12, 31, 0, 0, 0, 0, -1080))
         );
     }
 
+    #[test]
+    fn test_try_new_invalid() {
+        let err = |field| Err(RangeError(field));
+        assert_eq!(PackedTimestamp::try_new_utc(20000, 1, 1, 0, 0, 0, 0), err(DateTimeField::Year));
+        assert_eq!(PackedTimestamp::try_new_utc(-10000, 1, 1, 0, 0, 0, 0), err(DateTimeField::Year));
+        assert_eq!(PackedTimestamp::try_new_utc(2023, 0, 1, 0, 0, 0, 0), err(DateTimeField::Month));
+        assert_eq!(PackedTimestamp::try_new_utc(2023, 13, 1, 0, 0, 0, 0), err(DateTimeField::Month));
+        assert_eq!(PackedTimestamp::try_new_utc(2023, 257, 1, 0, 0, 0, 0), err(DateTimeField::Month));
+        assert_eq!(PackedTimestamp::try_new_utc(2023, 1, 0, 0, 0, 0, 0), err(DateTimeField::Day));
+        assert_eq!(PackedTimestamp::try_new_utc(2023, 1, 32, 0, 0, 0, 0), err(DateTimeField::Day));
+        assert_eq!(PackedTimestamp::try_new_utc(2023, 2, 29, 0, 0, 0, 0), err(DateTimeField::Day));
+        assert_eq!(PackedTimestamp::try_new_utc(1900, 2, 29, 0, 0, 0, 0), err(DateTimeField::Day));
+        assert_eq!(PackedTimestamp::try_new_utc(2023, 4, 31, 0, 0, 0, 0), err(DateTimeField::Day));
+        assert_eq!(PackedTimestamp::try_new_utc(2023, 1, 1, 24, 0, 0, 0), err(DateTimeField::Hour));
+        assert_eq!(PackedTimestamp::try_new_utc(2023, 1, 1, 0, 60, 0, 0), err(DateTimeField::Minute));
+        assert_eq!(PackedTimestamp::try_new_utc(2023, 1, 1, 0, 0, 61, 0), err(DateTimeField::Second));
+        assert_eq!(PackedTimestamp::try_new_utc(2023, 1, 1, 0, 0, 0, 1000), err(DateTimeField::Millisecond));
+        assert_eq!(
+            PackedTimestamp::try_new(2023, 1, 1, 0, 0, 0, 0, 18 * 60 + 1),
+            err(DateTimeField::OffsetMinutes)
+        );
+        assert_eq!(
+            PackedTimestamp::try_new(2023, 1, 1, 0, 0, 0, 0, -18 * 60 - 1),
+            err(DateTimeField::OffsetMinutes)
+        );
+    }
+
+    #[test]
+    fn test_try_from_timestamp_millis() {
+        assert_eq!(
+            PackedTimestamp::try_from_timestamp_millis(0),
+            Ok(PackedTimestamp::new_utc(1970, 1, 1, 0, 0, 0, 0))
+        );
+        assert_eq!(
+            PackedTimestamp::try_from_timestamp_millis(253402300799999),
+            Ok(PackedTimestamp::new_utc(9999, 12, 31, 23, 59, 59, 999))
+        );
+        assert_eq!(
+            PackedTimestamp::try_from_timestamp_millis(253402300800000),
+            Err(RangeError(DateTimeField::Year))
+        );
+        assert_eq!(
+            PackedTimestamp::try_from_timestamp_millis(i64::MIN),
+            Err(RangeError(DateTimeField::Year))
+        );
+    }
+
+    #[test]
+    fn test_timestamp_millis_range() {
+        use crate::packed::{MAX_TIMESTAMP_MILLIS, MIN_TIMESTAMP_MILLIS};
+        let min = chrono::NaiveDate::from_ymd(-9999, 1, 1).and_hms(0, 0, 0).timestamp_millis();
+        let max = chrono::NaiveDate::from_ymd(9999, 12, 31).and_hms_milli(23, 59, 59, 999).timestamp_millis();
+        assert_eq!(MIN_TIMESTAMP_MILLIS, min);
+        assert_eq!(MAX_TIMESTAMP_MILLIS, max);
+    }
+
+    #[test]
+    fn test_parse_invalid_value() {
+        assert_eq!(
+            PackedTimestamp::try_from("2023-02-30T00:00:00Z"),
+            Err(ParseError::InvalidValue(DateTimeField::Day))
+        );
+        assert_eq!(
+            PackedTimestamp::try_from("2023-02-29T00:00:00Z"),
+            Err(ParseError::InvalidValue(DateTimeField::Day))
+        );
+        assert_eq!(
+            PackedTimestamp::try_from("2023-13-01T00:00:00Z"),
+            Err(ParseError::InvalidValue(DateTimeField::Month))
+        );
+        assert_eq!(
+            PackedTimestamp::try_from("2023-00-01T00:00:00Z"),
+            Err(ParseError::InvalidValue(DateTimeField::Month))
+        );
+        assert_eq!(
+            PackedTimestamp::try_from("2023-01-00T00:00:00Z"),
+            Err(ParseError::InvalidValue(DateTimeField::Day))
+        );
+        assert_eq!(
+            PackedTimestamp::try_from("2023-01-01T24:00:00Z"),
+            Err(ParseError::InvalidValue(DateTimeField::Hour))
+        );
+        assert_eq!(
+            PackedTimestamp::try_from("2023-01-01T23:60:00Z"),
+            Err(ParseError::InvalidValue(DateTimeField::Minute))
+        );
+        assert_eq!(
+            PackedTimestamp::try_from("2023-01-01T23:59:61Z"),
+            Err(ParseError::InvalidValue(DateTimeField::Second))
+        );
+        assert_eq!(
+            PackedTimestamp::try_from("2023-01-01T23:59:59+19:00"),
+            Err(ParseError::InvalidValue(DateTimeField::OffsetMinutes))
+        );
+        assert_eq!(
+            PackedTimestamp::try_from("2023-01-01T23:59:59+01:60"),
+            Err(ParseError::InvalidValue(DateTimeField::OffsetMinutes))
+        );
+        assert_eq!(
+            PackedTimestamp::try_from("2023-01-01T23:59:59.123-01:60"),
+            Err(ParseError::InvalidValue(DateTimeField::OffsetMinutes))
+        );
+        assert_eq!(
+            PackedTimestamp::try_from("2024-02-29T23:59:60.123+18:00"),
+            Ok(PackedTimestamp::new(2024, 2, 29, 23, 59, 60, 123, 1080))
+        );
+    }
+
+    #[test]
+    fn test_ord_across_year_zero() {
+        let mut values = [
+            PackedTimestamp::new_utc(1, 1, 1, 0, 0, 0, 0),
+            PackedTimestamp::new_utc(-1, 12, 31, 23, 59, 59, 999),
+            PackedTimestamp::new_utc(0, 6, 15, 12, 0, 0, 0),
+            PackedTimestamp::new_utc(-9999, 1, 1, 0, 0, 0, 0),
+            PackedTimestamp::new_utc(9999, 12, 31, 0, 0, 0, 0),
+            PackedTimestamp::new_utc(-1, 1, 1, 0, 0, 0, 0),
+        ];
+        assert!(PackedTimestamp::new_utc(-1, 1, 1, 0, 0, 0, 0) < PackedTimestamp::new_utc(1, 1, 1, 0, 0, 0, 0));
+        values.sort();
+        assert_eq!(values.map(|ts| ts.year()), [-9999, -1, -1, 0, 1, 9999]);
+        assert_eq!(values[1].month(), 1);
+
+        // same local fields, ordered by offset
+        let a = PackedTimestamp::new(2022, 8, 21, 10, 0, 0, 0, -60);
+        let b = PackedTimestamp::new(2022, 8, 21, 10, 0, 0, 0, 60);
+        assert!(a < b);
+    }
+
+    #[test]
+    fn test_cmp_instant() {
+        let a = PackedTimestamp::new(2022, 8, 21, 10, 0, 0, 0, 120);
+        let b = PackedTimestamp::new_utc(2022, 8, 21, 9, 0, 0, 0);
+        let c = PackedTimestamp::new(2022, 8, 21, 11, 0, 0, 0, 180);
+        let d = PackedTimestamp::new(2022, 8, 20, 23, 0, 0, 0, -600);
+
+        assert_eq!(a.cmp(&b), Ordering::Greater);
+        assert_eq!(a.cmp_instant(&b), Ordering::Less);
+        assert_eq!(b.cmp_instant(&a), Ordering::Greater);
+
+        assert_ne!(a, c);
+        assert!(a.eq_instant(&c));
+        assert_eq!(a.cmp_instant(&c), Ordering::Equal);
+        assert!(b.eq_instant(&d));
+
+        assert_eq!(ByInstant(a), ByInstant(c));
+        assert_ne!(ByInstant(a), ByInstant(b));
+
+        let mut values = [ByInstant(a), ByInstant(b), ByInstant(d)];
+        values.sort();
+        assert_eq!(values.iter().map(|v| v.0).collect::<Vec<_>>(), vec![a, b, d]);
+    }
+
+    #[test]
+    fn test_to_utc() {
+        assert_eq!(
+            PackedTimestamp::new(2022, 8, 21, 10, 0, 0, 250, 120).to_utc(),
+            PackedTimestamp::new_utc(2022, 8, 21, 8, 0, 0, 250)
+        );
+        assert_eq!(
+            PackedTimestamp::new(2022, 1, 1, 1, 30, 0, 0, 120).to_utc(),
+            PackedTimestamp::new_utc(2021, 12, 31, 23, 30, 0, 0)
+        );
+        assert_eq!(
+            PackedTimestamp::new(2024, 2, 28, 20, 0, 0, 0, -330).to_utc(),
+            PackedTimestamp::new_utc(2024, 2, 29, 1, 30, 0, 0)
+        );
+        assert_eq!(
+            PackedTimestamp::new_utc(2022, 8, 21, 17, 30, 15, 250).to_utc(),
+            PackedTimestamp::new_utc(2022, 8, 21, 17, 30, 15, 250)
+        );
+    }
+
+    #[test]
+    fn test_with_offset() {
+        let ts = PackedTimestamp::new_utc(2022, 12, 31, 23, 30, 0, 0);
+        assert_eq!(ts.with_offset(60), PackedTimestamp::new(2023, 1, 1, 0, 30, 0, 0, 60));
+        assert_eq!(ts.with_offset(-1080), PackedTimestamp::new(2022, 12, 31, 5, 30, 0, 0, -1080));
+        assert_eq!(ts.with_offset(60).with_offset(-120).to_utc(), ts);
+        assert!(ts.with_offset(345).eq_instant(&ts));
+
+        assert_eq!(ts.try_with_offset(1080), Ok(PackedTimestamp::new(2023, 1, 1, 17, 30, 0, 0, 1080)));
+        assert_eq!(ts.try_with_offset(1081), Err(RangeError(DateTimeField::OffsetMinutes)));
+        assert_eq!(ts.try_with_offset(100_000), Err(RangeError(DateTimeField::OffsetMinutes)));
+        assert_eq!(ts.try_with_offset(-1081), Err(RangeError(DateTimeField::OffsetMinutes)));
+    }
+
+    #[test]
+    #[should_panic(expected = "offset out of range")]
+    fn test_with_offset_out_of_range() {
+        PackedTimestamp::new_utc(2022, 12, 31, 23, 30, 0, 0).with_offset(100_000);
+    }
+
     #[test]
     fn test_packed() {
         let ts = PackedTimestamp::new_utc(2020, 9, 10, 17, 30, 15, 123);
@@ -0,0 +1,577 @@
+use crate::datetime::DateTimeComponents;
+use crate::format::*;
+use crate::packed::saturating_u8;
+use crate::{EpochDays, PackedTimestamp, ParseError, ParseResult, RangeError, MICROS_PER_DAY, MILLIS_PER_DAY, NANOS_PER_DAY};
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+
+const OFFSET_BITS: u32 = 12;
+const NANO_BITS: u32 = 30;
+const SECOND_BITS: u32 = 6;
+const MINUTE_BITS: u32 = 6;
+const HOUR_BITS: u32 = 5;
+const DAY_BITS: u32 = 5;
+const MONTH_BITS: u32 = 4;
+const YEAR_BITS: u32 = 128 - (MONTH_BITS + DAY_BITS + HOUR_BITS + MINUTE_BITS + SECOND_BITS + NANO_BITS + OFFSET_BITS);
+
+#[allow(clippy::assertions_on_constants)]
+const _: () = {
+    assert!(999_999_999 < (1 << NANO_BITS));
+    assert!(YEAR_BITS >= 16);
+};
+
+/// A bit-packed timestamp with nanosecond precision, using the same layout as `PackedTimestamp`
+/// but with 30 bits for the fractional seconds, stored in an `u128`.
+///
+/// Like `PackedTimestamp`, the `Ord` implementation compares the local fields and then the offset.
+#[derive(PartialEq, Clone, Copy, Eq)]
+#[repr(transparent)]
+pub struct PackedTimestampNanos {
+    value: u128,
+}
+
+impl PackedTimestampNanos {
+    /// The packed value with the sign bits of the year and offset flipped, see `PackedTimestamp::sort_key`.
+    #[inline]
+    fn sort_key(&self) -> u128 {
+        self.value ^ (1 << 127 | 1 << (OFFSET_BITS - 1))
+    }
+}
+
+impl PartialOrd for PackedTimestampNanos {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PackedTimestampNanos {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+impl PackedTimestampNanos {
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_utc(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: u32, nano: u32) -> Self {
+        Self::new(year, month, day, hour, minute, second, nano, 0)
+    }
+
+    #[inline]
+    pub fn new_ymd_utc(year: i32, month: u32, day: u32) -> Self {
+        Self::new(year, month, day, 0, 0, 0, 0, 0)
+    }
+
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: u32, nano: u32, offset_minutes: i32) -> Self {
+        let value = ((((((((year as u128) << MONTH_BITS | month as u128) << DAY_BITS | day as u128) << HOUR_BITS
+            | hour as u128)
+            << MINUTE_BITS
+            | minute as u128)
+            << SECOND_BITS
+            | second as u128)
+            << NANO_BITS
+            | nano as u128)
+            << OFFSET_BITS)
+            | (offset_minutes & ((1 << OFFSET_BITS) - 1)) as u128;
+        Self { value }
+    }
+
+    /// Creates a timestamp after validating that all components are within their valid range,
+    /// including the number of days of the month, leap years and the supported range of years and offsets.
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_new(
+        year: i32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        minute: u32,
+        second: u32,
+        nano: u32,
+        offset_minutes: i32,
+    ) -> Result<Self, RangeError> {
+        let components = DateTimeComponents::new_with_nanosecond(
+            year,
+            saturating_u8(month),
+            saturating_u8(day),
+            saturating_u8(hour),
+            saturating_u8(minute),
+            saturating_u8(second),
+            nano,
+            offset_minutes,
+        );
+        components.validate()?;
+        Ok(components.to_packed_nanos())
+    }
+
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_new_utc(
+        year: i32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        minute: u32,
+        second: u32,
+        nano: u32,
+    ) -> Result<Self, RangeError> {
+        Self::try_new(year, month, day, hour, minute, second, nano, 0)
+    }
+
+    #[inline]
+    pub fn from_value(value: u128) -> Self {
+        Self { value }
+    }
+
+    #[inline]
+    pub fn value(&self) -> u128 {
+        self.value
+    }
+
+    #[inline]
+    pub fn from_timestamp_millis(ts: i64) -> Self {
+        DateTimeComponents::from_timestamp_millis(ts).to_packed_nanos()
+    }
+
+    #[inline]
+    pub fn from_timestamp_micros(ts: i64) -> Self {
+        // nanoseconds since the epoch only cover the years 1677 to 2262, so convert days and micros separately
+        let days = ts.div_euclid(MICROS_PER_DAY);
+        let micros = ts.rem_euclid(MICROS_PER_DAY);
+        let mut components = DateTimeComponents::from_timestamp_nanos(micros * 1000);
+        let (year, month, day) = EpochDays::new(days as i32).to_ymd();
+        components.year = year;
+        components.month = month as _;
+        components.day = day as _;
+        components.to_packed_nanos()
+    }
+
+    #[inline]
+    pub fn from_timestamp_nanos(ts: i64) -> Self {
+        DateTimeComponents::from_timestamp_nanos(ts).to_packed_nanos()
+    }
+
+    #[inline]
+    fn epoch_days_and_nano_of_day(&self) -> (i64, i64) {
+        let epoch_days = EpochDays::from_ymd(self.year(), self.month() as i32, self.day() as i32).days() as i64;
+
+        let h = self.hour() as i64;
+        let m = self.minute() as i64;
+        let s = self.second() as i64;
+        let o = self.offset_minutes() as i64;
+        let seconds = h * 60 * 60 + m * 60 + s - o * 60;
+
+        (epoch_days, seconds * 1_000_000_000 + self.nanosecond() as i64)
+    }
+
+    /// Converts to milliseconds since the unix epoch, rounding down any sub-millisecond digits.
+    #[inline]
+    pub fn to_timestamp_millis(&self) -> i64 {
+        let (epoch_days, nano_of_day) = self.epoch_days_and_nano_of_day();
+        epoch_days * MILLIS_PER_DAY + nano_of_day.div_euclid(1_000_000)
+    }
+
+    /// Converts to microseconds since the unix epoch, rounding down any sub-microsecond digits.
+    #[inline]
+    pub fn to_timestamp_micros(&self) -> i64 {
+        let (epoch_days, nano_of_day) = self.epoch_days_and_nano_of_day();
+        epoch_days * MICROS_PER_DAY + nano_of_day.div_euclid(1_000)
+    }
+
+    /// Converts to nanoseconds since the unix epoch, which can represent the years 1677 to 2262.
+    ///
+    /// # Panics
+    ///
+    /// If the instant is outside of that range, see `checked_to_timestamp_nanos`.
+    #[inline]
+    pub fn to_timestamp_nanos(&self) -> i64 {
+        self.checked_to_timestamp_nanos().expect("timestamp out of range")
+    }
+
+    /// Converts to nanoseconds since the unix epoch, returns `None` if the instant is before
+    /// 1677-09-21T00:12:43.145224192Z or after 2262-04-11T23:47:16.854775807Z.
+    #[inline]
+    pub fn checked_to_timestamp_nanos(&self) -> Option<i64> {
+        let (epoch_days, nano_of_day) = self.epoch_days_and_nano_of_day();
+        // the product alone can overflow for the earliest day of the range, so compute it with more bits
+        let nanos = epoch_days as i128 * NANOS_PER_DAY as i128 + nano_of_day as i128;
+        i64::try_from(nanos).ok()
+    }
+
+    /// Converts to a `PackedTimestamp`, truncating any sub-millisecond digits.
+    #[inline]
+    pub fn to_packed_timestamp(&self) -> PackedTimestamp {
+        PackedTimestamp::new(
+            self.year(),
+            self.month(),
+            self.day(),
+            self.hour(),
+            self.minute(),
+            self.second(),
+            self.millisecond(),
+            self.offset_minutes(),
+        )
+    }
+
+    pub fn from_rfc3339_bytes(input: &[u8]) -> ParseResult<Self> {
+        let ts = crate::dispatch::parse_components(input)?;
+        Ok(ts.to_packed_nanos())
+    }
+
+    pub fn from_rfc3339_str(input: &str) -> ParseResult<Self> {
+        Self::from_rfc3339_bytes(input.as_bytes())
+    }
+
+    #[inline]
+    pub fn year(&self) -> i32 {
+        // year is stored in the highest bits, an arithmetic shift does the sign extension
+        ((self.value as i128) >> (MONTH_BITS + DAY_BITS + HOUR_BITS + MINUTE_BITS + SECOND_BITS + NANO_BITS + OFFSET_BITS)) as i32
+    }
+
+    #[inline]
+    pub fn month(&self) -> u32 {
+        ((self.value >> (DAY_BITS + HOUR_BITS + MINUTE_BITS + SECOND_BITS + NANO_BITS + OFFSET_BITS)) & ((1 << MONTH_BITS) - 1))
+            as u32
+    }
+
+    #[inline]
+    pub fn day(&self) -> u32 {
+        ((self.value >> (HOUR_BITS + MINUTE_BITS + SECOND_BITS + NANO_BITS + OFFSET_BITS)) & ((1 << DAY_BITS) - 1)) as u32
+    }
+
+    #[inline]
+    pub fn hour(&self) -> u32 {
+        ((self.value >> (MINUTE_BITS + SECOND_BITS + NANO_BITS + OFFSET_BITS)) & ((1 << HOUR_BITS) - 1)) as u32
+    }
+
+    #[inline]
+    pub fn minute(&self) -> u32 {
+        ((self.value >> (SECOND_BITS + NANO_BITS + OFFSET_BITS)) & ((1 << MINUTE_BITS) - 1)) as u32
+    }
+
+    #[inline]
+    pub fn second(&self) -> u32 {
+        ((self.value >> (NANO_BITS + OFFSET_BITS)) & ((1 << SECOND_BITS) - 1)) as u32
+    }
+
+    #[inline]
+    pub fn nanosecond(&self) -> u32 {
+        ((self.value >> (OFFSET_BITS)) & ((1 << NANO_BITS) - 1)) as u32
+    }
+
+    #[inline]
+    pub fn microsecond(&self) -> u32 {
+        self.nanosecond() / 1_000
+    }
+
+    #[inline]
+    pub fn millisecond(&self) -> u32 {
+        self.nanosecond() / 1_000_000
+    }
+
+    #[inline]
+    pub fn offset_minutes(&self) -> i32 {
+        let bits = (self.value & ((1 << OFFSET_BITS) - 1)) as i32;
+        // offset is the only field that can be negative and needs sign extension
+        bits << (32 - OFFSET_BITS) >> (32 - OFFSET_BITS)
+    }
+
+    #[inline]
+    pub fn write_rfc3339_bytes<W: std::io::Write>(&self, mut writer: W) -> std::io::Result<()> {
+        let buffer = self.to_rfc3339_bytes();
+        writer.write_all(buffer.as_bytes())
+    }
+
+    #[inline]
+    pub fn write_rfc3339_str<W: std::fmt::Write>(&self, mut writer: W) -> std::fmt::Result {
+        let buffer = self.to_rfc3339_bytes();
+        writer.write_str(buffer.as_str())
+    }
+
+    /// Formats the timestamp with 9 fractional digits including its timezone offset, using `Z` only if the offset is zero.
+    #[inline]
+    pub fn to_rfc3339_bytes(&self) -> FormattedTimestamp {
+        format_to_rfc3339_nanos_bytes(
+            self.year(),
+            self.month(),
+            self.day(),
+            self.hour(),
+            self.minute(),
+            self.second(),
+            self.nanosecond(),
+            self.offset_minutes(),
+        )
+    }
+
+    /// Formats the timestamp including its timezone offset according to the given options.
+    #[inline]
+    pub fn to_rfc3339_bytes_with_options(&self, options: &FormatOptions) -> FormattedTimestamp {
+        format_to_rfc3339_bytes_with_options(
+            self.year(),
+            self.month(),
+            self.day(),
+            self.hour(),
+            self.minute(),
+            self.second(),
+            self.nanosecond(),
+            self.offset_minutes(),
+            options,
+        )
+    }
+
+    #[inline]
+    pub fn to_rfc3339_string(&self) -> String {
+        self.to_rfc3339_bytes().as_str().to_string()
+    }
+}
+
+impl Display for PackedTimestampNanos {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.write_rfc3339_str(f)
+    }
+}
+
+impl Debug for PackedTimestampNanos {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.write_rfc3339_str(f)
+    }
+}
+
+impl From<PackedTimestamp> for PackedTimestampNanos {
+    fn from(ts: PackedTimestamp) -> Self {
+        PackedTimestampNanos::new(
+            ts.year(),
+            ts.month(),
+            ts.day(),
+            ts.hour(),
+            ts.minute(),
+            ts.second(),
+            ts.millisecond() * 1_000_000,
+            ts.offset_minutes(),
+        )
+    }
+}
+
+impl From<EpochDays> for PackedTimestampNanos {
+    fn from(epoch_days: EpochDays) -> Self {
+        let (year, month, day) = epoch_days.to_ymd();
+        PackedTimestampNanos::new_ymd_utc(year, month as _, day as _)
+    }
+}
+
+impl TryFrom<&str> for PackedTimestampNanos {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        PackedTimestampNanos::from_rfc3339_str(s)
+    }
+}
+
+impl FromStr for PackedTimestampNanos {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PackedTimestampNanos::from_rfc3339_str(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DateTimeField, FormatOptions, FractionalDigits, PackedTimestamp, PackedTimestampNanos, RangeError};
+
+    #[test]
+    fn test_ord_across_year_zero() {
+        let negative = PackedTimestampNanos::new_utc(-1, 12, 31, 23, 59, 59, 999_999_999);
+        let zero = PackedTimestampNanos::new_ymd_utc(0, 1, 1);
+        let positive = PackedTimestampNanos::new_ymd_utc(1, 1, 1);
+        assert!(negative < zero);
+        assert!(zero < positive);
+        assert!(PackedTimestampNanos::new_ymd_utc(-2, 6, 1) < negative);
+        assert!(PackedTimestampNanos::new(1, 1, 1, 0, 0, 0, 0, -60) < PackedTimestampNanos::new(1, 1, 1, 0, 0, 0, 0, 0));
+    }
+
+    #[test]
+    fn test_packed_nanos() {
+        let ts = PackedTimestampNanos::new(2020, 9, 10, 17, 30, 15, 123_456_789, -90);
+        assert_eq!(2020, ts.year());
+        assert_eq!(9, ts.month());
+        assert_eq!(10, ts.day());
+        assert_eq!(17, ts.hour());
+        assert_eq!(30, ts.minute());
+        assert_eq!(15, ts.second());
+        assert_eq!(123_456_789, ts.nanosecond());
+        assert_eq!(123_456, ts.microsecond());
+        assert_eq!(123, ts.millisecond());
+        assert_eq!(-90, ts.offset_minutes());
+    }
+
+    #[test]
+    fn test_try_new_nanos() {
+        assert_eq!(
+            PackedTimestampNanos::try_new(2024, 2, 29, 17, 30, 15, 999_999_999, -90),
+            Ok(PackedTimestampNanos::new(2024, 2, 29, 17, 30, 15, 999_999_999, -90))
+        );
+        assert_eq!(
+            PackedTimestampNanos::try_new_utc(2023, 2, 29, 17, 30, 15, 0),
+            Err(RangeError(DateTimeField::Day))
+        );
+        assert_eq!(
+            PackedTimestampNanos::try_new_utc(2023, 2, 28, 17, 30, 15, 1_000_000_000),
+            Err(RangeError(DateTimeField::Nanosecond))
+        );
+    }
+
+    #[test]
+    fn test_parse_nanos() {
+        assert_eq!(
+            "2022-08-21T17:30:15.123456789Z".parse(),
+            Ok(PackedTimestampNanos::new_utc(2022, 8, 21, 17, 30, 15, 123_456_789))
+        );
+        assert_eq!(
+            "2022-08-21T17:30:15.1234Z".parse(),
+            Ok(PackedTimestampNanos::new_utc(2022, 8, 21, 17, 30, 15, 123_400_000))
+        );
+        assert_eq!(
+            "2022-08-21 17:30:15.123456+02:00".parse(),
+            Ok(PackedTimestampNanos::new(2022, 8, 21, 17, 30, 15, 123_456_000, 120))
+        );
+        assert_eq!(
+            "2022-08-21 17:30:15Z".parse(),
+            Ok(PackedTimestampNanos::new_utc(2022, 8, 21, 17, 30, 15, 0))
+        );
+    }
+
+    #[test]
+    fn test_format_nanos() {
+        assert_eq!(
+            PackedTimestampNanos::new_utc(2022, 8, 21, 17, 30, 15, 123_456_789).to_rfc3339_string(),
+            "2022-08-21T17:30:15.123456789Z".to_owned()
+        );
+        assert_eq!(
+            PackedTimestampNanos::new(2022, 8, 21, 17, 30, 15, 1_000, -120).to_string(),
+            "2022-08-21T17:30:15.000001000-02:00".to_owned()
+        );
+    }
+
+    #[test]
+    fn test_format_nanos_with_options() {
+        let options = FormatOptions::new()
+            .with_fractional_digits(FractionalDigits::Shortest)
+            .with_space_separator(true);
+        assert_eq!(
+            PackedTimestampNanos::new_utc(2022, 8, 21, 17, 30, 15, 123_456_000)
+                .to_rfc3339_bytes_with_options(&options)
+                .as_str(),
+            "2022-08-21 17:30:15.123456Z"
+        );
+        assert_eq!(
+            PackedTimestampNanos::new(2022, 8, 21, 17, 30, 15, 0, 60)
+                .to_rfc3339_bytes_with_options(&options.with_fractional_digits(FractionalDigits::Micros))
+                .as_str(),
+            "2022-08-21 17:30:15.000000+01:00"
+        );
+    }
+
+    #[test]
+    fn test_parse_format_roundtrip_nanos() {
+        for input in [
+            "2022-08-21T17:30:15.123456789Z",
+            "2022-08-21T17:30:15.000000001+02:00",
+            "2022-08-21T17:30:15.999999999-05:30",
+        ] {
+            let ts: PackedTimestampNanos = input.parse().unwrap();
+            assert_eq!(ts.to_string(), input);
+        }
+    }
+
+    #[test]
+    fn test_timestamp_conversions_nanos() {
+        let ts = PackedTimestampNanos::new_utc(2023, 7, 3, 22, 55, 30, 123_456_789);
+        assert_eq!(ts.to_timestamp_millis(), 1688424930123);
+        assert_eq!(ts.to_timestamp_micros(), 1688424930123456);
+        assert_eq!(ts.to_timestamp_nanos(), 1688424930123456789);
+
+        assert_eq!(
+            PackedTimestampNanos::from_timestamp_millis(1688424930123).nanosecond(),
+            123_000_000
+        );
+        assert_eq!(
+            PackedTimestampNanos::from_timestamp_micros(1688424930123456).nanosecond(),
+            123_456_000
+        );
+        assert_eq!(PackedTimestampNanos::from_timestamp_nanos(1688424930123456789), ts);
+
+        let ts = PackedTimestampNanos::new(2023, 7, 3, 22, 55, 30, 123_456_789, 120);
+        assert_eq!(ts.to_timestamp_nanos(), 1688417730123456789);
+    }
+
+    #[test]
+    fn test_timestamp_conversions_nanos_negative() {
+        assert_eq!(
+            PackedTimestampNanos::from_timestamp_nanos(-1),
+            PackedTimestampNanos::new_utc(1969, 12, 31, 23, 59, 59, 999_999_999)
+        );
+        assert_eq!(
+            PackedTimestampNanos::from_timestamp_micros(-1),
+            PackedTimestampNanos::new_utc(1969, 12, 31, 23, 59, 59, 999_999_000)
+        );
+        let ts = PackedTimestampNanos::new_utc(1969, 12, 31, 23, 59, 59, 999_999_999);
+        assert_eq!(ts.to_timestamp_nanos(), -1);
+        assert_eq!(ts.to_timestamp_micros(), -1);
+        assert_eq!(ts.to_timestamp_millis(), -1);
+
+        // outside the range of nanoseconds since the epoch
+        let ts = PackedTimestampNanos::from_timestamp_micros(-12_000_000_000_000_000);
+        assert_eq!(ts.year(), 1589);
+        assert_eq!(ts.to_timestamp_micros(), -12_000_000_000_000_000);
+        assert_eq!(ts.checked_to_timestamp_nanos(), None);
+    }
+
+    #[test]
+    fn test_checked_to_timestamp_nanos() {
+        let min = PackedTimestampNanos::new_utc(1677, 9, 21, 0, 12, 43, 145_224_192);
+        assert_eq!(min.checked_to_timestamp_nanos(), Some(i64::MIN));
+        let max = PackedTimestampNanos::new_utc(2262, 4, 11, 23, 47, 16, 854_775_807);
+        assert_eq!(max.checked_to_timestamp_nanos(), Some(i64::MAX));
+
+        let before = PackedTimestampNanos::new_utc(1677, 9, 21, 0, 12, 43, 145_224_191);
+        assert_eq!(before.checked_to_timestamp_nanos(), None);
+        let after = PackedTimestampNanos::new_utc(2262, 4, 11, 23, 47, 16, 854_775_808);
+        assert_eq!(after.checked_to_timestamp_nanos(), None);
+        // the offset moves the instant back into range
+        let local = PackedTimestampNanos::new(2262, 4, 12, 0, 47, 16, 854_775_807, 60);
+        assert_eq!(local.checked_to_timestamp_nanos(), Some(i64::MAX));
+        let far = PackedTimestampNanos::new_utc(-9999, 1, 1, 0, 0, 0, 0);
+        assert_eq!(far.checked_to_timestamp_nanos(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "timestamp out of range")]
+    fn test_to_timestamp_nanos_out_of_range() {
+        PackedTimestampNanos::new_utc(2262, 4, 12, 0, 0, 0, 0).to_timestamp_nanos();
+    }
+
+    #[test]
+    fn test_negative_year_nanos() {
+        let ts = PackedTimestampNanos::new(-44, 3, 15, 12, 30, 0, 1, -60);
+        assert_eq!(ts.year(), -44);
+        assert_eq!(ts.offset_minutes(), -60);
+        assert_eq!(ts.to_string(), "-0044-03-15T12:30:00.000000001-01:00");
+        assert_eq!("-0044-03-15T12:30:00.000000001-01:00".parse(), Ok(ts));
+        assert_eq!(ts.to_packed_timestamp(), PackedTimestamp::new(-44, 3, 15, 12, 30, 0, 0, -60));
+    }
+
+    #[test]
+    fn test_packed_timestamp_conversion() {
+        let ts = PackedTimestamp::new(2022, 8, 21, 17, 30, 15, 250, 120);
+        let nanos = PackedTimestampNanos::from(ts);
+        assert_eq!(nanos, PackedTimestampNanos::new(2022, 8, 21, 17, 30, 15, 250_000_000, 120));
+        assert_eq!(nanos.to_packed_timestamp(), ts);
+        assert_eq!(nanos.to_timestamp_millis(), ts.to_timestamp_millis());
+    }
+}
@@ -3,7 +3,7 @@ use crate::error::*;
 use crate::{EpochDays, PackedTimestamp};
 
 #[repr(C)]
-#[derive(PartialEq, Clone, Debug, Default)]
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
 struct SimdTimestamp {
     year_hi: u16,
     year_lo: u16,
@@ -35,7 +35,7 @@ impl SimdTimestamp {
 }
 
 #[inline(always)]
-fn ts_to_epoch_millis(ts: &DateTimeComponents) -> i64 {
+pub(crate) fn ts_to_epoch_millis(ts: &DateTimeComponents) -> i64 {
     let epoch_day = EpochDays::from_ymd(ts.year, ts.month as i32, ts.day as i32).days() as i64;
 
     let h = ts.hour as i64;
@@ -44,7 +44,7 @@ fn ts_to_epoch_millis(ts: &DateTimeComponents) -> i64 {
     let offset_minute = ts.offset_minute as i64;
     let seconds = epoch_day * 24 * 60 * 60 + h * 60 * 60 + m * 60 + s - offset_minute * 60;
 
-    seconds * 1000 + ts.millisecond as i64
+    seconds * 1000 + ts.millisecond() as i64
 }
 
 #[doc(hidden)]
@@ -63,7 +63,7 @@ pub fn parse_to_packed_timestamp_scalar(input: &str) -> ParseResult<PackedTimest
         ts.hour as u32,
         ts.minute as u32,
         ts.second as u32,
-        ts.millisecond,
+        ts.millisecond(),
         ts.offset_minute,
     ))
 }
@@ -76,7 +76,7 @@ pub(crate) fn parse_scalar(bytes: &[u8]) -> ParseResult<DateTimeComponents> {
     let mut timestamp = DateTimeComponents::default();
     let mut index = 0;
 
-    let year = parse_num4(bytes, &mut index)?;
+    let year = parse_year(bytes, &mut index)?;
     expect(bytes, &mut index, b'-')?;
     let month = parse_num2(bytes, &mut index)?;
     expect(bytes, &mut index, b'-')?;
@@ -90,18 +90,73 @@ pub(crate) fn parse_scalar(bytes: &[u8]) -> ParseResult<DateTimeComponents> {
 
     let offset = parse_utc_or_offset_minutes(bytes, &mut index)?;
 
-    timestamp.year = year as i32;
+    timestamp.year = year;
     timestamp.month = month as u8;
     timestamp.day = day as u8;
     timestamp.hour = hour as u8;
     timestamp.minute = minute as u8;
     timestamp.second = second as u8;
-    timestamp.millisecond = nano / 1_000_000;
+    timestamp.nanosecond = nano;
     timestamp.offset_minute = offset;
 
+    timestamp.validate()?;
+
     Ok(timestamp)
 }
 
+/// Parses a date in the format `yyyy-mm-dd`, the year can also use the ISO 8601 expanded representation with a sign.
+pub(crate) fn parse_date_scalar(bytes: &[u8]) -> ParseResult<EpochDays> {
+    let mut index = 0;
+
+    let year = parse_year(bytes, &mut index)?;
+    expect(bytes, &mut index, b'-')?;
+    let month = parse_num2(bytes, &mut index)?;
+    expect(bytes, &mut index, b'-')?;
+    let day = parse_num2(bytes, &mut index)?;
+
+    if index != bytes.len() {
+        return Err(ParseError::TrailingChar {
+            position: index,
+            byte: bytes[index],
+        });
+    }
+
+    epoch_days_from_ymd(year, month, day)
+}
+
+#[inline(always)]
+fn epoch_days_from_ymd(year: i32, month: u32, day: u32) -> ParseResult<EpochDays> {
+    DateTimeComponents::new(year, month as u8, day as u8, 0, 0, 0, 0).validate()?;
+    Ok(EpochDays::from_ymd(year, month as i32, day as i32))
+}
+
+/// Parses a date using the date lanes of the SIMD timestamp parser, callers have to ensure
+/// that the required target features are available, either at compile time or by checking at runtime.
+#[inline]
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+#[cfg_attr(target_arch = "x86_64", target_feature(enable = "sse2,ssse3"))]
+#[cfg_attr(target_arch = "aarch64", target_feature(enable = "neon"))]
+pub(crate) unsafe fn parse_date_simd_unchecked(bytes: &[u8]) -> ParseResult<EpochDays> {
+    if bytes.len() != 10 || bytes[0] == b'+' || bytes[0] == b'-' {
+        // the scalar parser reports the exact position of missing or trailing characters
+        return parse_date_scalar(bytes);
+    }
+
+    // copy into a buffer of the register width since the input could end right after the date
+    let mut buffer = [0_u8; 16];
+    buffer[..10].copy_from_slice(bytes);
+
+    let Some(timestamp) = parse_simd_yyyy_mm_dd_hh_mm(buffer.as_ptr(), 10) else {
+        return parse_date_scalar(bytes);
+    };
+
+    epoch_days_from_ymd(
+        timestamp.year_hi as i32 * 100 + timestamp.year_lo as i32,
+        timestamp.month as u32,
+        timestamp.day as u32,
+    )
+}
+
 #[inline(always)]
 fn parse_seconds_and_nanos(bytes: &[u8], index: &mut usize) -> ParseResult<(u32, u32)> {
     let mut second = 0;
@@ -132,10 +187,10 @@ fn parse_seconds_and_nanos_and_offset_minutes_slow_path(bytes: &[u8], index: &mu
 }
 
 #[inline(never)]
-fn skip_nanos_and_parse_offset_minutes_slow_path(bytes: &[u8], index: &mut usize) -> ParseResult<i32> {
-    skip_fractional_millis(bytes, index);
+fn parse_sub_millis_and_offset_minutes_slow_path(bytes: &[u8], index: &mut usize) -> ParseResult<(u32, i32)> {
+    let sub_millis = parse_sub_millis(bytes, index);
     let offset_minutes = parse_utc_or_offset_minutes(bytes, index)?;
-    Ok(offset_minutes)
+    Ok((sub_millis, offset_minutes))
 }
 
 #[inline(always)]
@@ -146,20 +201,37 @@ fn parse_utc_or_offset_minutes(bytes: &[u8], index: &mut usize) -> ParseResult<i
     let first = bytes[*index];
     if first == b'Z' {
         *index += 1;
-        if *index != bytes.len() {
-            Err(ParseError::TrailingChar(*index))
-        } else {
-            Ok(0)
-        }
+        expect_end(bytes, *index)?;
+        Ok(0)
     } else if first == b'+' {
         *index += 1;
-        Ok(parse_offset_minutes(bytes, index)? as i32)
+        let offset_minutes = parse_offset_minutes(bytes, index)? as i32;
+        expect_end(bytes, *index)?;
+        Ok(offset_minutes)
     } else if first == b'-' {
         *index += 1;
-        Ok(-(parse_offset_minutes(bytes, index)? as i32))
+        let offset_minutes = parse_offset_minutes(bytes, index)? as i32;
+        expect_end(bytes, *index)?;
+        Ok(-offset_minutes)
     } else {
-        Err(ParseError::InvalidChar(*index))
+        Err(ParseError::InvalidChar {
+            position: *index,
+            byte: first,
+            expected: Expected::OffsetSign,
+        })
+    }
+}
+
+/// Returns an error if there are any bytes after `index`.
+#[inline(always)]
+fn expect_end(bytes: &[u8], index: usize) -> ParseResult<()> {
+    if index != bytes.len() {
+        return Err(ParseError::TrailingChar {
+            position: index,
+            byte: bytes[index],
+        });
     }
+    Ok(())
 }
 
 #[inline(always)]
@@ -168,18 +240,51 @@ fn parse_offset_minutes(bytes: &[u8], index: &mut usize) -> ParseResult<u32> {
     expect(bytes, index, b':')?;
     let offset_minute = parse_num2(bytes, index)?;
 
+    if offset_minute > 59 {
+        return Err(ParseError::InvalidValue(DateTimeField::OffsetMinutes));
+    }
+
     Ok(offset_hour * 60 + offset_minute)
 }
 
 #[inline(always)]
-fn parse_num2(bytes: &[u8], i: &mut usize) -> ParseResult<u32> {
+pub(crate) fn parse_num2(bytes: &[u8], i: &mut usize) -> ParseResult<u32> {
     let d1 = digit(bytes, i)?;
     let d2 = digit(bytes, i)?;
     Ok(d1 * 10 + d2)
 }
 
+/// Parses a four digit year or an ISO 8601 expanded year representation consisting of a sign and at least four digits.
 #[inline(always)]
-fn parse_num4(bytes: &[u8], i: &mut usize) -> ParseResult<u32> {
+pub(crate) fn parse_year(bytes: &[u8], i: &mut usize) -> ParseResult<i32> {
+    if *i >= bytes.len() {
+        return Err(ParseError::InvalidLen(*i));
+    }
+    let sign = bytes[*i];
+    if sign != b'+' && sign != b'-' {
+        return Ok(parse_num4(bytes, i)? as i32);
+    }
+    *i += 1;
+
+    let mut year = parse_num4(bytes, i)? as i32;
+    let mut j = 4;
+    // at most 9 digits so that the year fits into an i32
+    while *i < bytes.len() && j < 9 {
+        let ch = bytes[*i];
+        if ch >= b'0' && ch <= b'9' {
+            year = year * 10 + (ch - b'0') as i32;
+            j += 1;
+            *i += 1;
+        } else {
+            break;
+        }
+    }
+
+    Ok(if sign == b'-' { -year } else { year })
+}
+
+#[inline(always)]
+pub(crate) fn parse_num4(bytes: &[u8], i: &mut usize) -> ParseResult<u32> {
     let d1 = digit(bytes, i)?;
     let d2 = digit(bytes, i)?;
     let d3 = digit(bytes, i)?;
@@ -190,7 +295,7 @@ fn parse_num4(bytes: &[u8], i: &mut usize) -> ParseResult<u32> {
 const NANO_MULTIPLIER: [u32; 9] = [1, 10, 100, 1_000, 10_000, 100_000, 1_000_000, 10_000_000, 100_000_000];
 
 #[inline(always)]
-fn parse_nano(bytes: &[u8], i: &mut usize) -> ParseResult<u32> {
+pub(crate) fn parse_nano(bytes: &[u8], i: &mut usize) -> ParseResult<u32> {
     let mut r = digit(bytes, i)?;
     let mut j = 1;
 
@@ -208,23 +313,28 @@ fn parse_nano(bytes: &[u8], i: &mut usize) -> ParseResult<u32> {
     Ok(r * NANO_MULTIPLIER[9 - j])
 }
 
+/// Parses up to 6 fractional digits following the milliseconds and returns them in units of nanoseconds.
 #[inline(always)]
-fn skip_fractional_millis(bytes: &[u8], i: &mut usize) {
+fn parse_sub_millis(bytes: &[u8], i: &mut usize) -> u32 {
+    let mut r = 0;
     let mut j = 0;
 
     while *i < bytes.len() && j < 6 {
         let ch = bytes[*i];
         if ch >= b'0' && ch <= b'9' {
+            r = r * 10 + (ch - b'0') as u32;
             j += 1;
             *i += 1;
         } else {
             break;
         }
     }
+
+    r * NANO_MULTIPLIER[6 - j]
 }
 
 #[inline(always)]
-fn expect(bytes: &[u8], i: &mut usize, expected: u8) -> ParseResult<()> {
+pub(crate) fn expect(bytes: &[u8], i: &mut usize, expected: u8) -> ParseResult<()> {
     if *i >= bytes.len() {
         return Err(ParseError::InvalidLen(*i));
     }
@@ -233,7 +343,11 @@ fn expect(bytes: &[u8], i: &mut usize, expected: u8) -> ParseResult<()> {
         *i += 1;
         Ok(())
     } else {
-        Err(ParseError::InvalidChar(*i))
+        Err(ParseError::InvalidChar {
+            position: *i,
+            byte: ch,
+            expected: Expected::Char(expected),
+        })
     }
 }
 
@@ -247,12 +361,16 @@ fn expect2(bytes: &[u8], i: &mut usize, expected1: u8, expected2: u8) -> ParseRe
         *i += 1;
         Ok(ch)
     } else {
-        Err(ParseError::InvalidChar(*i))
+        Err(ParseError::InvalidChar {
+            position: *i,
+            byte: ch,
+            expected: Expected::OneOf(expected1, expected2),
+        })
     }
 }
 
 #[inline(always)]
-fn digit(bytes: &[u8], i: &mut usize) -> ParseResult<u32> {
+pub(crate) fn digit(bytes: &[u8], i: &mut usize) -> ParseResult<u32> {
     if *i >= bytes.len() {
         return Err(ParseError::InvalidLen(*i));
     }
@@ -261,14 +379,21 @@ fn digit(bytes: &[u8], i: &mut usize) -> ParseResult<u32> {
         *i += 1;
         Ok((ch - b'0') as u32)
     } else {
-        Err(ParseError::InvalidChar(*i))
+        Err(ParseError::InvalidChar {
+            position: *i,
+            byte: ch,
+            expected: Expected::Digit,
+        })
     }
 }
 
 // only public for benchmarks
 #[doc(hidden)]
 #[inline]
-#[cfg(all(target_arch = "x86_64", target_feature = "sse2", target_feature = "ssse3"))]
+#[cfg(any(
+    all(target_arch = "x86_64", target_feature = "ssse3"),
+    all(target_arch = "aarch64", target_feature = "neon")
+))]
 pub fn parse_to_epoch_millis_simd(input: &str) -> ParseResult<i64> {
     let ts = parse_simd(input.as_bytes())?;
     Ok(ts_to_epoch_millis(&ts))
@@ -277,7 +402,10 @@ pub fn parse_to_epoch_millis_simd(input: &str) -> ParseResult<i64> {
 // only public for benchmarks
 #[doc(hidden)]
 #[inline]
-#[cfg(all(target_arch = "x86_64", target_feature = "sse2", target_feature = "ssse3"))]
+#[cfg(any(
+    all(target_arch = "x86_64", target_feature = "ssse3"),
+    all(target_arch = "aarch64", target_feature = "neon")
+))]
 pub fn parse_to_packed_timestamp_simd(input: &str) -> ParseResult<PackedTimestamp> {
     let ts = parse_simd(input.as_bytes())?;
     Ok(PackedTimestamp::new(
@@ -287,15 +415,18 @@ pub fn parse_to_packed_timestamp_simd(input: &str) -> ParseResult<PackedTimestam
         ts.hour as u32,
         ts.minute as u32,
         ts.second as u32,
-        ts.millisecond,
+        ts.millisecond(),
         ts.offset_minute,
     ))
 }
 
+/// Parses the fixed layout `yyyy-mm-ddThh:mm`, validating only the first `validate_len` bytes.
+/// Returns `None` if any of them is invalid, callers then use the scalar parser to report the first error in input order,
+/// so that the error does not depend on the available cpu features.
 #[inline]
 #[cfg(target_arch = "x86_64")]
-#[cfg(all(target_arch = "x86_64", target_feature = "sse2", target_feature = "ssse3"))]
-unsafe fn parse_simd_yyyy_mm_dd_hh_mm(bytes: *const u8) -> ParseResult<SimdTimestamp> {
+#[target_feature(enable = "sse2,ssse3")]
+unsafe fn parse_simd_yyyy_mm_dd_hh_mm(bytes: *const u8, validate_len: usize) -> Option<SimdTimestamp> {
     use std::arch::x86_64::*;
 
     const MIN_BYTES: &[u8] = "))))-)0-)0S))9))9))".as_bytes();
@@ -315,10 +446,12 @@ unsafe fn parse_simd_yyyy_mm_dd_hh_mm(bytes: *const u8) -> ParseResult<SimdTimes
 
     let space_sep = _mm_cmpeq_epi8(ts_without_seconds, space);
     let mask = _mm_or_si128(_mm_and_si128(gt, lt), space_sep);
-    let mask = _mm_movemask_epi8(mask);
+    // only the first `validate_len` bytes need to be valid, so that the date lanes can be used on their own
+    let mask = _mm_movemask_epi8(mask) as u32;
+    let validate_mask = (1_u32 << validate_len) - 1;
 
-    if mask != 0xFFFF {
-        return Err(ParseError::InvalidChar((!mask).trailing_zeros() as usize));
+    if mask & validate_mask != validate_mask {
+        return None;
     }
 
     let nums = _mm_sub_epi8(ts_without_seconds, space);
@@ -334,88 +467,270 @@ unsafe fn parse_simd_yyyy_mm_dd_hh_mm(bytes: *const u8) -> ParseResult<SimdTimes
     let timestamp_ptr: *mut SimdTimestamp = &mut timestamp;
     _mm_storeu_si128(timestamp_ptr as *mut __m128i, res);
 
-    Ok(timestamp)
+    Some(timestamp)
+}
+
+#[inline]
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+unsafe fn parse_simd_yyyy_mm_dd_hh_mm(bytes: *const u8, validate_len: usize) -> Option<SimdTimestamp> {
+    use std::arch::aarch64::*;
+
+    const MIN_BYTES: &[u8] = "))))-)0-)0S))9))9))".as_bytes();
+    const MAX_BYTES: &[u8] = "@@@@-2@-4@U3@;6@;6@".as_bytes();
+    const SPACE_SEP_BYTES: &[u8] = "0000-00-00 00:00:00".as_bytes();
+    const SHUFFLE_BYTES: [u8; 16] = [0, 1, 2, 3, 5, 6, 8, 9, 11, 12, 14, 15, 0xFF, 0xFF, 0xFF, 0xFF];
+
+    let mut timestamp = SimdTimestamp::default();
+    let ts_without_seconds = vld1q_u8(bytes);
+    let min = vld1q_u8(MIN_BYTES.as_ptr());
+    let max = vld1q_u8(MAX_BYTES.as_ptr());
+    let space = vld1q_u8(SPACE_SEP_BYTES.as_ptr());
+
+    // unsigned comparisons give the same result as the signed ones on x86 since all bounds are ascii
+    let gt = vcgtq_u8(ts_without_seconds, min);
+    let lt = vcltq_u8(ts_without_seconds, max);
+
+    let space_sep = vceqq_u8(ts_without_seconds, space);
+    let mask = vorrq_u8(vandq_u8(gt, lt), space_sep);
+    // narrow each byte of the mask to 4 bits, since there is no movemask instruction
+    let mask = vget_lane_u64::<0>(vreinterpret_u64_u8(vshrn_n_u16::<4>(vreinterpretq_u16_u8(mask))));
+    let validate_mask = u64::MAX >> (64 - 4 * validate_len);
+
+    if mask & validate_mask != validate_mask {
+        return None;
+    }
+
+    let nums = vsubq_u8(ts_without_seconds, space);
+    // out of range indices result in zero, same as negative indices with pshufb
+    let nums = vreinterpretq_u16_u8(vqtbl1q_u8(nums, vld1q_u8(SHUFFLE_BYTES.as_ptr())));
+
+    let hundreds = vmulq_n_u16(vandq_u16(nums, vdupq_n_u16(0x00FF)), 10);
+    let ones = vshrq_n_u16::<8>(nums);
+
+    let res = vaddq_u16(ones, hundreds);
+
+    let timestamp_ptr: *mut SimdTimestamp = &mut timestamp;
+    vst1q_u16(timestamp_ptr as *mut u16, res);
+
+    Some(timestamp)
 }
 
 #[inline]
-#[cfg(all(target_arch = "x86_64", target_feature = "sse2", target_feature = "ssse3"))]
+#[cfg(any(
+    all(target_arch = "x86_64", target_feature = "ssse3"),
+    all(target_arch = "aarch64", target_feature = "neon")
+))]
 pub(crate) fn parse_simd(bytes: &[u8]) -> ParseResult<DateTimeComponents> {
+    unsafe { parse_simd_unchecked(bytes) }
+}
+
+/// Parses using SSE or NEON instructions, callers have to ensure that the required target features are available,
+/// either at compile time or by checking at runtime.
+#[inline]
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+#[cfg_attr(target_arch = "x86_64", target_feature(enable = "sse2,ssse3"))]
+#[cfg_attr(target_arch = "aarch64", target_feature(enable = "neon"))]
+pub(crate) unsafe fn parse_simd_unchecked(bytes: &[u8]) -> ParseResult<DateTimeComponents> {
     if bytes.len() < 16 {
         return Err(ParseError::InvalidLen(bytes.len()));
     }
 
-    let timestamp = unsafe { parse_simd_yyyy_mm_dd_hh_mm(bytes.as_ptr())? };
+    if bytes[0] == b'+' || bytes[0] == b'-' {
+        // expanded year representation does not have a fixed layout
+        return parse_scalar(bytes);
+    }
+
+    let Some(timestamp) = parse_simd_yyyy_mm_dd_hh_mm(bytes.as_ptr(), 16) else {
+        return parse_scalar(bytes);
+    };
+
+    parse_simd_remainder(bytes, &timestamp)
+}
+
+/// Parses two timestamps at once, using one 256 bit register for the date, hour and minute components of both.
+/// Callers have to ensure that avx2 is available, either at compile time or by checking at runtime.
+#[inline]
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn parse_simd_x2_unchecked(
+    first: &[u8],
+    second: &[u8],
+) -> (ParseResult<DateTimeComponents>, ParseResult<DateTimeComponents>) {
+    if first.len() < 16 || second.len() < 16 || matches!(first[0], b'+' | b'-') || matches!(second[0], b'+' | b'-') {
+        return (parse_simd_unchecked(first), parse_simd_unchecked(second));
+    }
+
+    let (first_timestamp, second_timestamp) = parse_simd_yyyy_mm_dd_hh_mm_x2(first.as_ptr(), second.as_ptr(), 16);
+
+    (
+        match first_timestamp {
+            Some(timestamp) => parse_simd_remainder(first, &timestamp),
+            None => parse_scalar(first),
+        },
+        match second_timestamp {
+            Some(timestamp) => parse_simd_remainder(second, &timestamp),
+            None => parse_scalar(second),
+        },
+    )
+}
+
+/// Parses two dates at once using the date lanes of `parse_simd_yyyy_mm_dd_hh_mm_x2`.
+/// Callers have to ensure that avx2 is available, either at compile time or by checking at runtime.
+#[inline]
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn parse_date_simd_x2_unchecked(first: &[u8], second: &[u8]) -> (ParseResult<EpochDays>, ParseResult<EpochDays>) {
+    if first.len() != 10 || second.len() != 10 || matches!(first[0], b'+' | b'-') || matches!(second[0], b'+' | b'-') {
+        return (parse_date_simd_unchecked(first), parse_date_simd_unchecked(second));
+    }
+
+    // copy into buffers of the register width since the input could end right after the date
+    let mut first_buffer = [0_u8; 16];
+    first_buffer[..10].copy_from_slice(first);
+    let mut second_buffer = [0_u8; 16];
+    second_buffer[..10].copy_from_slice(second);
+
+    let (first_timestamp, second_timestamp) = parse_simd_yyyy_mm_dd_hh_mm_x2(first_buffer.as_ptr(), second_buffer.as_ptr(), 10);
+
+    let to_epoch_days = |timestamp: SimdTimestamp| {
+        epoch_days_from_ymd(
+            timestamp.year_hi as i32 * 100 + timestamp.year_lo as i32,
+            timestamp.month as u32,
+            timestamp.day as u32,
+        )
+    };
+
+    (
+        first_timestamp.map_or_else(|| parse_date_scalar(first), to_epoch_days),
+        second_timestamp.map_or_else(|| parse_date_scalar(second), to_epoch_days),
+    )
+}
+
+/// Parses the date, hour and minute components of two timestamps, only the first `validate_len` bytes of each have to be valid.
+#[inline]
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn parse_simd_yyyy_mm_dd_hh_mm_x2(
+    first: *const u8,
+    second: *const u8,
+    validate_len: usize,
+) -> (Option<SimdTimestamp>, Option<SimdTimestamp>) {
+    use std::arch::x86_64::*;
+
+    const MIN_BYTES: &[u8] = "))))-)0-)0S))9))9))".as_bytes();
+    const MAX_BYTES: &[u8] = "@@@@-2@-4@U3@;6@;6@".as_bytes();
+    const SPACE_SEP_BYTES: &[u8] = "0000-00-00 00:00:00".as_bytes();
+
+    let mut timestamps = [SimdTimestamp::default(); 2];
+    let ts_without_seconds = _mm256_loadu2_m128i(second as *const __m128i, first as *const __m128i);
+    let min = _mm256_broadcastsi128_si256(_mm_loadu_si128(MIN_BYTES.as_ptr() as *const __m128i));
+    let max = _mm256_broadcastsi128_si256(_mm_loadu_si128(MAX_BYTES.as_ptr() as *const __m128i));
+    let space = _mm256_broadcastsi128_si256(_mm_loadu_si128(SPACE_SEP_BYTES.as_ptr() as *const __m128i));
+
+    let gt = _mm256_cmpgt_epi8(ts_without_seconds, min);
+    let lt = _mm256_cmpgt_epi8(max, ts_without_seconds);
+
+    let space_sep = _mm256_cmpeq_epi8(ts_without_seconds, space);
+    let mask = _mm256_or_si256(_mm256_and_si256(gt, lt), space_sep);
+    let mask = _mm256_movemask_epi8(mask) as u32;
+
+    // the shuffle operates separately on each 128 bit lane
+    let nums = _mm256_sub_epi8(ts_without_seconds, space);
+    let nums = _mm256_shuffle_epi8(
+        nums,
+        _mm256_broadcastsi128_si256(_mm_set_epi8(-1, -1, -1, -1, 15, 14, 12, 11, 9, 8, 6, 5, 3, 2, 1, 0)),
+    );
 
-    let (seconds, millis, offset_minutes) = parse_seconds_and_millis_simd(bytes)?;
+    let hundreds = _mm256_and_si256(nums, _mm256_set1_epi16(0x00FF));
+    let hundreds = _mm256_mullo_epi16(hundreds, _mm256_set1_epi16(10));
 
-    Ok(DateTimeComponents {
+    let ones = _mm256_srli_epi16::<8>(nums);
+
+    let res = _mm256_add_epi16(ones, hundreds);
+
+    _mm256_storeu_si256(timestamps.as_mut_ptr() as *mut __m256i, res);
+
+    let validate_mask = (1_u32 << validate_len) - 1;
+    let first_mask = mask & validate_mask;
+    let second_mask = (mask >> 16) & validate_mask;
+
+    (
+        (first_mask == validate_mask).then_some(timestamps[0]),
+        (second_mask == validate_mask).then_some(timestamps[1]),
+    )
+}
+
+#[inline(always)]
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+fn parse_simd_remainder(bytes: &[u8], timestamp: &SimdTimestamp) -> ParseResult<DateTimeComponents> {
+    let (seconds, nanos, offset_minutes) = parse_seconds_and_nanos_simd(bytes)?;
+
+    let timestamp = DateTimeComponents {
         year: timestamp.year_hi as i32 * 100 + timestamp.year_lo as i32,
         month: timestamp.month as u8,
         day: timestamp.day as u8,
         hour: timestamp.hour as u8,
         minute: timestamp.minute as u8,
         second: seconds as u8,
-        millisecond: millis,
+        nanosecond: nanos,
         offset_minute: offset_minutes,
-    })
+    };
+
+    timestamp.validate()?;
+
+    Ok(timestamp)
 }
 
 #[inline(always)]
-#[cfg(all(target_arch = "x86_64", target_feature = "sse2", target_feature = "ssse3"))]
-fn parse_seconds_and_millis_simd(bytes: &[u8]) -> ParseResult<(u32, u32, i32)> {
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+fn parse_seconds_and_nanos_simd(bytes: &[u8]) -> ParseResult<(u32, u32, i32)> {
     if let Some((seconds, millis, offset_sign)) = try_parse_seconds_and_millis_simd(bytes) {
+        let nanos = millis * 1_000_000;
         match offset_sign {
-            b'Z' => return Ok((seconds, millis, 0)),
+            b'Z' => {
+                expect_end(bytes, 24)?;
+                return Ok((seconds, nanos, 0));
+            }
             b'+' | b'-' => {
                 let mut index = 24;
                 let offset_minutes = parse_offset_minutes(bytes, &mut index)? as i32;
+                expect_end(bytes, index)?;
                 let offset_minutes = if offset_sign == b'-' {
                     -offset_minutes
                 } else {
                     offset_minutes
                 };
-                return Ok((seconds, millis, offset_minutes));
+                return Ok((seconds, nanos, offset_minutes));
             }
             digit @ b'0'..=b'9' => {
                 let mut i = 24 - 1;
-                let offset_minutes = skip_nanos_and_parse_offset_minutes_slow_path(bytes, &mut i)?;
-                return Ok((seconds, millis, offset_minutes));
+                let (sub_millis, offset_minutes) = parse_sub_millis_and_offset_minutes_slow_path(bytes, &mut i)?;
+                return Ok((seconds, nanos + sub_millis, offset_minutes));
+            }
+            byte => {
+                return Err(ParseError::InvalidChar {
+                    position: 23,
+                    byte,
+                    expected: Expected::OffsetSign,
+                })
             }
-            _ => return Err(ParseError::InvalidChar(23)),
         }
     }
 
     let mut index = 16;
     let (second, nano, offset_minutes) = parse_seconds_and_nanos_and_offset_minutes_slow_path(bytes, &mut index)?;
-    Ok((second, nano / 1_000_000, offset_minutes))
+    Ok((second, nano, offset_minutes))
 }
 
 #[inline(always)]
-#[cfg(all(target_arch = "x86_64", target_feature = "sse2", target_feature = "ssse3"))]
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
 fn try_parse_seconds_and_millis_simd(input: &[u8]) -> Option<(u32, u32, u8)> {
-    use std::arch::x86_64::*;
     if input.len() >= 24 {
         let buf = unsafe { std::ptr::read_unaligned(input.as_ptr().add(16) as *const u64) };
 
-        unsafe {
-            let min = _mm_sub_epi8(
-                _mm_set_epi64x(0, i64::from_le_bytes(*b":00.000+")),
-                _mm_set1_epi64x(0x0101_0101_0101_0101),
-            );
-            let max = _mm_add_epi8(
-                _mm_set_epi64x(0, i64::from_le_bytes(*b":99.999Z")),
-                _mm_set1_epi64x(0x0101_0101_0101_0101),
-            );
-            let reg = _mm_set1_epi64x(buf as _);
-
-            let gt = _mm_cmpgt_epi8(reg, min);
-            let lt = _mm_cmplt_epi8(reg, max);
-
-            let mask = _mm_movemask_epi8(_mm_and_si128(gt, lt));
-
-            if mask != 0xFF {
-                return None;
-            }
+        if !seconds_and_millis_in_range_simd(buf) {
+            return None;
         }
 
         let buf = buf.to_le_bytes();
@@ -429,28 +744,82 @@ fn try_parse_seconds_and_millis_simd(input: &[u8]) -> Option<(u32, u32, u8)> {
     }
 }
 
-pub fn parse_to_timestamp_millis(bytes: &[u8]) -> ParseResult<i64> {
-    #[cfg(target_feature = "sse4.1")]
-    {
-        let ts = parse_simd(bytes)?;
-        Ok(ts_to_epoch_millis(&ts))
+#[inline(always)]
+#[cfg(target_arch = "x86_64")]
+fn seconds_and_millis_in_range_simd(buf: u64) -> bool {
+    use std::arch::x86_64::*;
+    unsafe {
+        let min = _mm_sub_epi8(
+            _mm_set_epi64x(0, i64::from_le_bytes(*b":00.000+")),
+            _mm_set1_epi64x(0x0101_0101_0101_0101),
+        );
+        let max = _mm_add_epi8(
+            _mm_set_epi64x(0, i64::from_le_bytes(*b":99.999Z")),
+            _mm_set1_epi64x(0x0101_0101_0101_0101),
+        );
+        let reg = _mm_set1_epi64x(buf as _);
+
+        let gt = _mm_cmpgt_epi8(reg, min);
+        let lt = _mm_cmplt_epi8(reg, max);
+
+        let mask = _mm_movemask_epi8(_mm_and_si128(gt, lt));
+
+        mask == 0xFF
     }
-    #[cfg(not(target_feature = "sse4.1"))]
-    {
-        let ts = parse_scalar(bytes)?;
-        Ok(ts_to_epoch_millis(&ts))
+}
+
+#[inline(always)]
+#[cfg(target_arch = "aarch64")]
+fn seconds_and_millis_in_range_simd(buf: u64) -> bool {
+    use std::arch::aarch64::*;
+    unsafe {
+        let min = vsub_u8(vcreate_u8(u64::from_le_bytes(*b":00.000+")), vdup_n_u8(1));
+        let max = vadd_u8(vcreate_u8(u64::from_le_bytes(*b":99.999Z")), vdup_n_u8(1));
+        let reg = vcreate_u8(buf);
+
+        let gt = vcgt_u8(reg, min);
+        let lt = vclt_u8(reg, max);
+
+        let mask = vget_lane_u64::<0>(vreinterpret_u64_u8(vand_u8(gt, lt)));
+
+        mask == u64::MAX
     }
 }
 
+pub fn parse_to_timestamp_millis(bytes: &[u8]) -> ParseResult<i64> {
+    let ts = crate::dispatch::parse_components(bytes)?;
+    Ok(ts_to_epoch_millis(&ts))
+}
+
 #[cfg(test)]
-#[cfg(all(not(miri), target_arch = "x86_64", target_feature = "sse2", target_feature = "ssse3"))]
+#[cfg(not(miri))]
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
 pub mod simd_tests {
-    use crate::error::ParseError;
-    use crate::parse::{parse_simd, try_parse_seconds_and_millis_simd, DateTimeComponents};
-    use crate::parse_to_epoch_millis_simd;
+    use crate::error::{DateTimeField, Expected, ParseError, ParseResult};
+    use crate::parse::{
+        parse_date_scalar, parse_date_simd_unchecked, parse_simd_unchecked, try_parse_seconds_and_millis_simd, ts_to_epoch_millis,
+        DateTimeComponents,
+    };
+
+    /// Checks at runtime whether the cpu supports the SIMD parser, the same way as the runtime dispatch,
+    /// so that the tests also run for builds without `-C target-cpu` flags.
+    fn simd_available() -> bool {
+        #[cfg(target_arch = "x86_64")]
+        return is_x86_feature_detected!("sse2") && is_x86_feature_detected!("ssse3");
+        #[cfg(target_arch = "aarch64")]
+        return std::arch::is_aarch64_feature_detected!("neon");
+    }
+
+    fn parse_simd(bytes: &[u8]) -> ParseResult<DateTimeComponents> {
+        assert!(simd_available());
+        unsafe { parse_simd_unchecked(bytes) }
+    }
 
     #[test]
     fn test_valid() {
+        if !simd_available() {
+            return;
+        }
         assert!(parse_simd(b"1970-01-01T00:00Z").is_ok());
         assert!(parse_simd(b"1970-01-01T00:00:00Z").is_ok());
         assert!(parse_simd(b"1970-01-01T00:00:00.000Z").is_ok());
@@ -460,8 +829,111 @@ pub mod simd_tests {
         assert!(parse_simd(b"1970-01-01 00:00:00.000Z").is_ok());
     }
 
+    #[test]
+    fn test_parse_date_simd_matches_scalar() {
+        if !simd_available() {
+            return;
+        }
+        let inputs: &[&[u8]] = &[
+            b"1970-01-01",
+            b"2024-02-29",
+            b"2023-02-29",
+            b"2023-13-01",
+            b"2023-00-01",
+            b"0000-01-01",
+            b"9999-12-31",
+            b"-0044-03-15",
+            b"+12345-01-01",
+            b"2023/01/01",
+            b"2023-01-0X",
+            b"2023-29-0X",
+            b"2023-1X-4X",
+            b"2023-01-01T",
+            b"2023-01-1",
+            b"",
+        ];
+        for input in inputs {
+            let expected = parse_date_scalar(input);
+            let actual = unsafe { parse_date_simd_unchecked(input) };
+            assert_eq!(expected, actual, "{}", String::from_utf8_lossy(input));
+            #[cfg(target_arch = "x86_64")]
+            if is_x86_feature_detected!("avx2") {
+                let valid = b"2023-01-03".as_slice();
+                let (first, second) = unsafe { crate::parse::parse_date_simd_x2_unchecked(input, valid) };
+                assert_eq!(expected, first, "{}", String::from_utf8_lossy(input));
+                assert_eq!(parse_date_scalar(valid), second);
+                let (first, second) = unsafe { crate::parse::parse_date_simd_x2_unchecked(valid, input) };
+                assert_eq!(parse_date_scalar(valid), first);
+                assert_eq!(expected, second, "{}", String::from_utf8_lossy(input));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn test_parse_date_simd_x2_lanes() {
+        if !is_x86_feature_detected!("avx2") {
+            return;
+        }
+        // the bytes after the dates are zero, only the date lanes are validated
+        let mut first = [0_u8; 16];
+        first[..10].copy_from_slice(b"2023-01-03");
+        let mut second = [0_u8; 16];
+        second[..10].copy_from_slice(b"1969-12-31");
+        let (first, second) = unsafe { super::parse_simd_yyyy_mm_dd_hh_mm_x2(first.as_ptr(), second.as_ptr(), 10) };
+        let first = first.unwrap();
+        assert_eq!((first.year_hi, first.year_lo, first.month, first.day), (20, 23, 1, 3));
+        let second = second.unwrap();
+        assert_eq!((second.year_hi, second.year_lo, second.month, second.day), (19, 69, 12, 31));
+    }
+
+    #[test]
+    fn test_parse_simd_matches_scalar() {
+        if !simd_available() {
+            return;
+        }
+        let inputs: &[&[u8]] = &[
+            b"2023-01-03T09:30:15.123Z",
+            b"2023-01-03 09:30Z",
+            b"2023-29-0XT09:30:15Z",
+            b"2023-19-10T12:00:00Z",
+            b"2023-09-10T29:00:0XZ",
+            b"2023-09-10T12:60:00Z",
+            b"2023-09-40T12:00:00.000+01:00",
+            b"2023-09-10T12:00:00.000+18:30",
+            b"2023-09-10t12:00:00Z",
+            b"2023-09-10T12:00:00.1234567890Z",
+            b"2023-09-10T12:00:00X",
+            b"2023-09-10T12:00:00ZX",
+            b"2022-08-21T17:30:15.250Zjunk",
+            b"2022-08-21T17:30:15.250+02:00junk",
+            b"2022-08-21T17:30:15+02:00junk",
+            b"2023-09-10T12:00",
+            b"X023-09-10T12:00:00Z",
+            b"-0044-03-15T12:00Z",
+            b"2023",
+        ];
+        for input in inputs {
+            let expected = crate::parse::parse_scalar(input);
+            assert_eq!(expected, parse_simd(input), "{}", String::from_utf8_lossy(input));
+            #[cfg(target_arch = "x86_64")]
+            if is_x86_feature_detected!("avx2") {
+                let valid = b"2023-01-03T09:30:15.123Z".as_slice();
+                let (first, second) = unsafe { crate::parse::parse_simd_x2_unchecked(input, valid) };
+                assert_eq!(expected, first, "{}", String::from_utf8_lossy(input));
+                assert_eq!(parse_simd(valid), second);
+                let (first, second) = unsafe { crate::parse::parse_simd_x2_unchecked(valid, input) };
+                assert_eq!(parse_simd(valid), first);
+                assert_eq!(expected, second, "{}", String::from_utf8_lossy(input));
+            }
+        }
+    }
+
     #[test]
     fn test_invalid_len() {
+        if !simd_available() {
+            return;
+        }
         assert_eq!(Err(ParseError::InvalidLen(0)), parse_simd(b""));
         assert_eq!(Err(ParseError::InvalidLen(1)), parse_simd(b"X"));
         assert_eq!(Err(ParseError::InvalidLen(4)), parse_simd(b"2020"));
@@ -469,16 +941,64 @@ pub mod simd_tests {
 
     #[test]
     fn test_invalid_char() {
-        assert_eq!(Err(ParseError::InvalidChar(0)), parse_simd(b"X020-09-10T12:00:00Z"));
-        assert_eq!(Err(ParseError::InvalidChar(1)), parse_simd(b"2X20-09-10T12:00:00Z"));
-        assert_eq!(Err(ParseError::InvalidChar(2)), parse_simd(b"20X0-09-10T12:00:00Z"));
-        assert_eq!(Err(ParseError::InvalidChar(10)), parse_simd(b"2020-09-10X12:00:00Z"));
-        assert_eq!(Err(ParseError::InvalidChar(10)), parse_simd(b"2020-09-10X12:00/"));
-        assert_eq!(Err(ParseError::InvalidChar(15)), parse_simd(b"2020-09-10T12:0X/"));
+        if !simd_available() {
+            return;
+        }
+        assert_eq!(
+            Err(ParseError::InvalidChar {
+                position: 0,
+                byte: b'X',
+                expected: Expected::Digit
+            }),
+            parse_simd(b"X020-09-10T12:00:00Z")
+        );
+        assert_eq!(
+            Err(ParseError::InvalidChar {
+                position: 1,
+                byte: b'X',
+                expected: Expected::Digit
+            }),
+            parse_simd(b"2X20-09-10T12:00:00Z")
+        );
+        assert_eq!(
+            Err(ParseError::InvalidChar {
+                position: 2,
+                byte: b'X',
+                expected: Expected::Digit
+            }),
+            parse_simd(b"20X0-09-10T12:00:00Z")
+        );
+        assert_eq!(
+            Err(ParseError::InvalidChar {
+                position: 10,
+                byte: b'X',
+                expected: Expected::OneOf(b'T', b' ')
+            }),
+            parse_simd(b"2020-09-10X12:00:00Z")
+        );
+        assert_eq!(
+            Err(ParseError::InvalidChar {
+                position: 10,
+                byte: b'X',
+                expected: Expected::OneOf(b'T', b' ')
+            }),
+            parse_simd(b"2020-09-10X12:00/")
+        );
+        assert_eq!(
+            Err(ParseError::InvalidChar {
+                position: 15,
+                byte: b'X',
+                expected: Expected::Digit
+            }),
+            parse_simd(b"2020-09-10T12:0X/")
+        );
     }
 
     #[test]
     fn test_parse_simd() {
+        if !simd_available() {
+            return;
+        }
         assert_eq!(
             DateTimeComponents::new(2345, 12, 24, 17, 30, 15, 100),
             parse_simd(b"2345-12-24T17:30:15.1Z").unwrap()
@@ -492,37 +1012,40 @@ pub mod simd_tests {
             parse_simd(b"2345-12-24T17:30:15.123Z").unwrap()
         );
         assert_eq!(
-            DateTimeComponents::new(2345, 12, 24, 17, 30, 15, 123),
+            DateTimeComponents::new_with_nanosecond(2345, 12, 24, 17, 30, 15, 123_400_000, 0),
             parse_simd(b"2345-12-24T17:30:15.1234Z").unwrap()
         );
         assert_eq!(
-            DateTimeComponents::new(2345, 12, 24, 17, 30, 15, 123),
+            DateTimeComponents::new_with_nanosecond(2345, 12, 24, 17, 30, 15, 123_450_000, 0),
             parse_simd(b"2345-12-24T17:30:15.12345Z").unwrap()
         );
         assert_eq!(
-            DateTimeComponents::new(2345, 12, 24, 17, 30, 15, 123),
+            DateTimeComponents::new_with_nanosecond(2345, 12, 24, 17, 30, 15, 123_456_000, 0),
             parse_simd(b"2345-12-24T17:30:15.123456Z").unwrap()
         );
         assert_eq!(
-            DateTimeComponents::new(2345, 12, 24, 17, 30, 15, 123),
+            DateTimeComponents::new_with_nanosecond(2345, 12, 24, 17, 30, 15, 123_457_000, 0),
             parse_simd(b"2345-12-24T17:30:15.123457Z").unwrap()
         );
         assert_eq!(
-            DateTimeComponents::new(2345, 12, 24, 17, 30, 15, 123),
+            DateTimeComponents::new_with_nanosecond(2345, 12, 24, 17, 30, 15, 123_456_780, 0),
             parse_simd(b"2345-12-24T17:30:15.12345678Z").unwrap()
         );
         assert_eq!(
-            DateTimeComponents::new(2345, 12, 24, 17, 30, 15, 123),
+            DateTimeComponents::new_with_nanosecond(2345, 12, 24, 17, 30, 15, 123_456_789, 0),
             parse_simd(b"2345-12-24T17:30:15.123456789Z").unwrap()
         );
         assert_eq!(
-            DateTimeComponents::new_with_offset_minute(2345, 12, 24, 17, 30, 15, 123, -60),
+            DateTimeComponents::new_with_nanosecond(2345, 12, 24, 17, 30, 15, 123_456_789, -60),
             parse_simd(b"2345-12-24T17:30:15.123456789-01:00").unwrap()
         );
     }
 
     #[test]
     fn test_parse_with_offset_simd() {
+        if !simd_available() {
+            return;
+        }
         assert_eq!(
             DateTimeComponents::new_with_offset_minute(2020, 9, 19, 11, 40, 20, 123, 2 * 60),
             parse_simd(b"2020-09-19T11:40:20.123+02:00").unwrap()
@@ -531,6 +1054,9 @@ pub mod simd_tests {
 
     #[test]
     fn test_parse_with_zero_offset_simd() {
+        if !simd_available() {
+            return;
+        }
         assert_eq!(
             DateTimeComponents::new_with_offset_minute(2020, 9, 19, 11, 40, 20, 123, 0),
             parse_simd(b"2020-09-19T11:40:20.123-00:00").unwrap()
@@ -539,6 +1065,9 @@ pub mod simd_tests {
 
     #[test]
     fn test_parse_with_negative_offset_simd() {
+        if !simd_available() {
+            return;
+        }
         assert_eq!(
             DateTimeComponents::new_with_offset_minute(2020, 9, 19, 11, 40, 20, 123, -2 * 60),
             parse_simd(b"2020-09-19T11:40:20.123-02:00").unwrap()
@@ -547,18 +1076,24 @@ pub mod simd_tests {
 
     #[test]
     fn test_parse_millis_simd() {
+        if !simd_available() {
+            return;
+        }
         let input = "2020-09-18T23:30:15Z";
         let expected = chrono::DateTime::parse_from_rfc3339(input).unwrap().timestamp_millis();
-        let actual = parse_to_epoch_millis_simd(input).unwrap();
+        let actual = ts_to_epoch_millis(&parse_simd(input.as_bytes()).unwrap());
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn test_parse_millis_simd_masked() {
+        if !simd_available() {
+            return;
+        }
         let input = "2020-09-18T23:30:15Z--::ZZ";
         let input = unsafe { input.get_unchecked(0..20) };
         let expected = chrono::DateTime::parse_from_rfc3339(input).unwrap().timestamp_millis();
-        let actual = parse_to_epoch_millis_simd(input).unwrap();
+        let actual = ts_to_epoch_millis(&parse_simd(input.as_bytes()).unwrap());
         assert_eq!(expected, actual);
     }
 
@@ -598,8 +1133,77 @@ pub mod simd_tests {
         assert_eq!(try_parse_seconds_and_millis_simd(input), Some((29, 123, b'4')));
     }
 
+    #[test]
+    fn test_parse_expanded_year_simd() {
+        if !simd_available() {
+            return;
+        }
+        assert_eq!(
+            DateTimeComponents::new(-44, 3, 15, 12, 0, 0, 0),
+            parse_simd(b"-0044-03-15T12:00Z").unwrap()
+        );
+        assert_eq!(
+            DateTimeComponents::new_with_offset_minute(-1, 12, 31, 23, 59, 59, 999, 60),
+            parse_simd(b"-0001-12-31T23:59:59.999+01:00").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_value_simd() {
+        if !simd_available() {
+            return;
+        }
+        assert_eq!(
+            Err(ParseError::InvalidValue(DateTimeField::Day)),
+            parse_simd(b"2023-02-30T12:00:00Z")
+        );
+        assert_eq!(
+            Err(ParseError::InvalidValue(DateTimeField::Month)),
+            parse_simd(b"2023-19-10T12:00:00Z")
+        );
+        assert_eq!(
+            Err(ParseError::InvalidValue(DateTimeField::Hour)),
+            parse_simd(b"2023-09-10T29:00:00.000Z")
+        );
+        assert_eq!(
+            Err(ParseError::InvalidValue(DateTimeField::OffsetMinutes)),
+            parse_simd(b"2023-09-10T12:00:00.000+18:30")
+        );
+    }
+
+    #[test]
+    fn test_trailing_char_after_offset_simd() {
+        if !simd_available() {
+            return;
+        }
+        assert_eq!(
+            Err(ParseError::TrailingChar {
+                position: 24,
+                byte: b'j'
+            }),
+            parse_simd(b"2022-08-21T17:30:15.250Zjunk")
+        );
+        assert_eq!(
+            Err(ParseError::TrailingChar {
+                position: 29,
+                byte: b'j'
+            }),
+            parse_simd(b"2022-08-21T17:30:15.250+02:00junk")
+        );
+        assert_eq!(
+            Err(ParseError::TrailingChar {
+                position: 25,
+                byte: b'j'
+            }),
+            parse_simd(b"2022-08-21T17:30:15-05:30junk")
+        );
+    }
+
     #[test]
     fn test_parse_leap_seconds_simd() {
+        if !simd_available() {
+            return;
+        }
         assert_eq!(
             DateTimeComponents::new(2023, 1, 3, 9, 30, 60, 123),
             parse_simd(b"2023-01-03T09:30:60.123Z").unwrap()
@@ -610,7 +1214,9 @@ pub mod simd_tests {
 #[cfg(test)]
 mod scalar_tests {
     use crate::datetime::DateTimeComponents;
-    use crate::{parse_scalar, parse_to_epoch_millis_scalar};
+    use crate::error::{DateTimeField, Expected, ParseError};
+    use crate::parse::parse_date_scalar;
+    use crate::{parse_scalar, parse_to_epoch_millis_scalar, EpochDays};
 
     #[test]
     fn test_parse_scalar() {
@@ -620,6 +1226,92 @@ mod scalar_tests {
         );
     }
 
+    #[test]
+    fn test_parse_nanos_scalar() {
+        assert_eq!(
+            DateTimeComponents::new_with_nanosecond(2345, 12, 24, 17, 30, 15, 123_456_789, 0),
+            parse_scalar(b"2345-12-24T17:30:15.123456789Z").unwrap()
+        );
+        assert_eq!(
+            DateTimeComponents::new_with_nanosecond(2345, 12, 24, 17, 30, 15, 123_400_000, 120),
+            parse_scalar(b"2345-12-24T17:30:15.1234+02:00").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_expanded_year_scalar() {
+        assert_eq!(
+            DateTimeComponents::new(-44, 3, 15, 12, 0, 0, 0),
+            parse_scalar(b"-0044-03-15T12:00Z").unwrap()
+        );
+        assert_eq!(
+            DateTimeComponents::new(2023, 3, 15, 12, 0, 0, 0),
+            parse_scalar(b"+2023-03-15T12:00Z").unwrap()
+        );
+        assert_eq!(
+            DateTimeComponents::new(-9999, 3, 15, 12, 0, 0, 0),
+            parse_scalar(b"-9999-03-15T12:00Z").unwrap()
+        );
+        assert_eq!(
+            Err(ParseError::InvalidValue(DateTimeField::Year)),
+            parse_scalar(b"+12345-03-15T12:00Z")
+        );
+        assert_eq!(
+            Err(ParseError::InvalidChar {
+                position: 4,
+                byte: b'-',
+                expected: Expected::Digit
+            }),
+            parse_scalar(b"-044-03-15T12:00:00Z")
+        );
+        assert_eq!(
+            Err(ParseError::InvalidChar {
+                position: 10,
+                byte: b'0',
+                expected: Expected::Char(b'-')
+            }),
+            parse_scalar(b"+1234567890-03-15T12:00:00Z")
+        );
+    }
+
+    #[test]
+    fn test_trailing_char_after_offset_scalar() {
+        assert_eq!(
+            Err(ParseError::TrailingChar {
+                position: 29,
+                byte: b'j'
+            }),
+            parse_scalar(b"2022-08-21T17:30:15.250+02:00junk")
+        );
+        assert_eq!(
+            Err(ParseError::TrailingChar {
+                position: 25,
+                byte: b'j'
+            }),
+            parse_scalar(b"2022-08-21T17:30:15-05:30junk")
+        );
+    }
+
+    #[test]
+    fn test_invalid_value_scalar() {
+        assert_eq!(
+            Err(ParseError::InvalidValue(DateTimeField::Day)),
+            parse_scalar(b"2023-02-30T12:00:00Z")
+        );
+        assert_eq!(
+            Err(ParseError::InvalidValue(DateTimeField::Month)),
+            parse_scalar(b"2023-19-10T12:00:00Z")
+        );
+        assert_eq!(
+            Err(ParseError::InvalidValue(DateTimeField::Hour)),
+            parse_scalar(b"2023-09-10T29:00:00.000Z")
+        );
+        assert_eq!(
+            Err(ParseError::InvalidValue(DateTimeField::OffsetMinutes)),
+            parse_scalar(b"2023-09-10T12:00:00.000+18:30")
+        );
+    }
+
     #[test]
     fn test_parse_leap_seconds_scalar() {
         assert_eq!(
@@ -635,4 +1327,26 @@ mod scalar_tests {
         let actual = parse_to_epoch_millis_scalar(input).unwrap();
         assert_eq!(expected, actual);
     }
+    #[test]
+    fn test_parse_date_scalar() {
+        assert_eq!(Ok(EpochDays::new(0)), parse_date_scalar(b"1970-01-01"));
+        assert_eq!(Ok(EpochDays::from_ymd(2024, 2, 29)), parse_date_scalar(b"2024-02-29"));
+        assert_eq!(Ok(EpochDays::from_ymd(-44, 3, 15)), parse_date_scalar(b"-0044-03-15"));
+        assert_eq!(
+            Err(ParseError::InvalidValue(DateTimeField::Year)),
+            parse_date_scalar(b"+12345-01-01")
+        );
+        assert_eq!(
+            Err(ParseError::InvalidValue(DateTimeField::Day)),
+            parse_date_scalar(b"2023-02-29")
+        );
+        assert_eq!(
+            Err(ParseError::TrailingChar {
+                position: 10,
+                byte: b'T'
+            }),
+            parse_date_scalar(b"2023-02-01T00:00Z")
+        );
+        assert_eq!(Err(ParseError::InvalidLen(7)), parse_date_scalar(b"2023-02"));
+    }
 }
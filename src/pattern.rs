@@ -0,0 +1,1193 @@
+//! Custom timestamp layouts using strftime patterns.
+//!
+//! A pattern is compiled once into a list of fixed-width items, which can then be applied to many timestamps
+//! without interpreting the pattern again.
+
+use crate::batch::{offset_rows, RowWriter};
+use crate::datetime::DateTimeComponents;
+use crate::epoch_days::is_leap_year;
+use crate::error::{DateTimeField, Expected, ParseError, ParseResult, PatternError, RowParseError};
+use crate::format::format_expanded_year_prefix_to_slice;
+use crate::packed::{saturating_u8, MAX_YEAR, MIN_YEAR};
+use crate::parse::{digit, expect, parse_nano, parse_num2, parse_year, ts_to_epoch_millis};
+use crate::{EpochDays, PackedTimestamp, PackedTimestampNanos};
+use std::str::FromStr;
+
+const WEEKDAY_NAMES: [&str; 7] = ["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"];
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Index into the two digit values that are converted together for each timestamp.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Lane {
+    Century = 0,
+    YearOfCentury = 1,
+    Month = 2,
+    Day = 3,
+    Hour = 4,
+    Minute = 5,
+    Second = 6,
+    Hour12 = 7,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Item {
+    /// Range of bytes in the literals buffer.
+    Literal {
+        start: u32,
+        end: u32,
+    },
+    /// Four digit year, or the ISO 8601 expanded representation for years outside of 0 to 9999.
+    Year,
+    /// Year divided by 100, with at least two digits.
+    Century,
+    /// Zero padded two digit value.
+    TwoDigits(Lane),
+    /// Space padded two digit value.
+    SpacePadded(Lane),
+    /// Three digit day of the year.
+    DayOfYear,
+    /// Fractional seconds truncated to the given number of digits, optionally with a leading dot.
+    Fraction {
+        digits: u8,
+        dot: bool,
+    },
+    /// Fractional seconds with a leading dot and 3, 6 or 9 digits, omitted if the fractional seconds are zero.
+    FractionAuto,
+    WeekdayName {
+        abbreviated: bool,
+    },
+    MonthName {
+        abbreviated: bool,
+    },
+    /// Weekday number from 1 for monday to 7 for sunday.
+    WeekdayFromMonday,
+    /// Weekday number from 0 for sunday to 6 for saturday.
+    WeekdayFromSunday,
+    AmPm {
+        lowercase: bool,
+    },
+    /// Timezone offset as `+HHMM` or `+HH:MM`.
+    Offset {
+        colon: bool,
+    },
+}
+
+impl Item {
+    fn max_len(&self) -> usize {
+        match self {
+            Item::Literal { start, end } => (end - start) as usize,
+            Item::Year => 11,
+            Item::Century => 9,
+            Item::TwoDigits(_) | Item::SpacePadded(_) => 2,
+            Item::DayOfYear => 3,
+            Item::Fraction { digits, dot } => *digits as usize + *dot as usize,
+            Item::FractionAuto => 10,
+            Item::WeekdayName { .. } | Item::MonthName { .. } => 9,
+            Item::WeekdayFromMonday | Item::WeekdayFromSunday => 1,
+            Item::AmPm { .. } => 2,
+            Item::Offset { colon } => 5 + *colon as usize,
+        }
+    }
+
+    /// The number of input bytes consumed when parsing, if it does not depend on the input.
+    /// Signed years, full names, `Z` offsets and variable fractions are only handled by the generic parser.
+    fn fixed_parse_len(&self) -> Option<usize> {
+        match self {
+            Item::Year => Some(4),
+            Item::Century => Some(2),
+            Item::FractionAuto => None,
+            Item::WeekdayName { abbreviated } | Item::MonthName { abbreviated } => abbreviated.then_some(3),
+            _ => Some(self.max_len()),
+        }
+    }
+
+    fn needs_epoch_days(&self) -> bool {
+        matches!(
+            self,
+            Item::DayOfYear | Item::WeekdayName { .. } | Item::WeekdayFromMonday | Item::WeekdayFromSunday
+        )
+    }
+}
+
+/// A timestamp layout compiled from a strftime pattern, used for both formatting and parsing.
+///
+/// Supported specifiers are `%Y`, `%C`, `%y`, `%m`, `%d`, `%e`, `%H`, `%k`, `%I`, `%l`, `%M`, `%S`, `%j`,
+/// `%f`, `%3f`, `%6f`, `%9f`, `%.f`, `%.3f`, `%.6f`, `%.9f`, `%a`, `%A`, `%b`, `%h`, `%B`, `%u`, `%w`, `%p`, `%P`, `%z`, `%:z`,
+/// the composite specifiers `%F`, `%T`, `%D`, `%R`, `%r` and `%v`, and the escapes `%%`, `%n` and `%t`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Format {
+    items: Vec<Item>,
+    literals: Vec<u8>,
+    max_len: usize,
+    fixed_len: Option<usize>,
+    needs_epoch_days: bool,
+}
+
+impl Format {
+    /// Compiles a strftime pattern like `%Y-%m-%d %H:%M:%S%.3f %z`.
+    pub fn compile(pattern: &str) -> Result<Self, PatternError> {
+        let mut format = Self {
+            items: vec![],
+            literals: vec![],
+            max_len: 0,
+            fixed_len: Some(0),
+            needs_epoch_days: false,
+        };
+
+        let bytes = pattern.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] != b'%' {
+                let start = i;
+                while i < bytes.len() && bytes[i] != b'%' {
+                    i += 1;
+                }
+                format.push_literal(&bytes[start..i]);
+                continue;
+            }
+
+            let position = i;
+            let incomplete = PatternError::IncompleteSpecifier { position };
+            i += 1;
+
+            let mut prefix = [0_u8; 2];
+            let mut prefix_len = 0;
+            while i < bytes.len() && matches!(bytes[i], b'.' | b':' | b'3' | b'6' | b'9') && prefix_len < 2 {
+                prefix[prefix_len] = bytes[i];
+                prefix_len += 1;
+                i += 1;
+            }
+            let specifier = pattern[i..].chars().next().ok_or(incomplete)?;
+            i += specifier.len_utf8();
+            let unsupported = PatternError::UnsupportedSpecifier { position, specifier };
+
+            match (&prefix[..prefix_len], specifier) {
+                ([], _) => format.push_specifier(specifier).ok_or(unsupported)?,
+                ([b'.'], 'f') => format.push_item(Item::FractionAuto),
+                ([b'.', digits @ (b'3' | b'6' | b'9')], 'f') => format.push_item(Item::Fraction {
+                    digits: digits - b'0',
+                    dot: true,
+                }),
+                ([digits @ (b'3' | b'6' | b'9')], 'f') => format.push_item(Item::Fraction {
+                    digits: digits - b'0',
+                    dot: false,
+                }),
+                ([b':'], 'z') => format.push_item(Item::Offset { colon: true }),
+                _ => return Err(unsupported),
+            }
+        }
+
+        Ok(format)
+    }
+
+    fn push_item(&mut self, item: Item) {
+        self.max_len += item.max_len();
+        self.fixed_len = self
+            .fixed_len
+            .zip(item.fixed_parse_len())
+            .map(|(len, item_len)| len + item_len);
+        self.needs_epoch_days |= item.needs_epoch_days();
+        self.items.push(item);
+    }
+
+    fn push_literal(&mut self, literal: &[u8]) {
+        let start = self.literals.len() as u32;
+        self.literals.extend_from_slice(literal);
+        let end = self.literals.len() as u32;
+
+        // merge with a directly preceding literal, for example from an escaped `%%`
+        if let Some(Item::Literal { end: previous_end, .. }) = self.items.last_mut() {
+            if *previous_end == start {
+                *previous_end = end;
+                self.max_len += literal.len();
+                self.fixed_len = self.fixed_len.map(|len| len + literal.len());
+                return;
+            }
+        }
+        self.push_item(Item::Literal { start, end });
+    }
+
+    fn push_specifier(&mut self, specifier: char) -> Option<()> {
+        let item = match specifier {
+            'Y' => Item::Year,
+            'C' => Item::Century,
+            'y' => Item::TwoDigits(Lane::YearOfCentury),
+            'm' => Item::TwoDigits(Lane::Month),
+            'd' => Item::TwoDigits(Lane::Day),
+            'e' => Item::SpacePadded(Lane::Day),
+            'H' => Item::TwoDigits(Lane::Hour),
+            'k' => Item::SpacePadded(Lane::Hour),
+            'I' => Item::TwoDigits(Lane::Hour12),
+            'l' => Item::SpacePadded(Lane::Hour12),
+            'M' => Item::TwoDigits(Lane::Minute),
+            'S' => Item::TwoDigits(Lane::Second),
+            'j' => Item::DayOfYear,
+            'f' => Item::Fraction { digits: 9, dot: false },
+            'a' => Item::WeekdayName { abbreviated: true },
+            'A' => Item::WeekdayName { abbreviated: false },
+            'b' | 'h' => Item::MonthName { abbreviated: true },
+            'B' => Item::MonthName { abbreviated: false },
+            'u' => Item::WeekdayFromMonday,
+            'w' => Item::WeekdayFromSunday,
+            'p' => Item::AmPm { lowercase: false },
+            'P' => Item::AmPm { lowercase: true },
+            'z' => Item::Offset { colon: false },
+            '%' | 'n' | 't' => {
+                let escaped: &[u8] = match specifier {
+                    '%' => b"%",
+                    'n' => b"\n",
+                    _ => b"\t",
+                };
+                self.push_literal(escaped);
+                return Some(());
+            }
+            'F' => return self.push_composite("%Y-%m-%d"),
+            'T' => return self.push_composite("%H:%M:%S"),
+            'D' => return self.push_composite("%m/%d/%y"),
+            'R' => return self.push_composite("%H:%M"),
+            'r' => return self.push_composite("%I:%M:%S %p"),
+            'v' => return self.push_composite("%e-%b-%Y"),
+            _ => return None,
+        };
+        self.push_item(item);
+        Some(())
+    }
+
+    fn push_composite(&mut self, pattern: &str) -> Option<()> {
+        let composite = Self::compile(pattern).ok()?;
+        for item in composite.items {
+            match item {
+                Item::Literal { start, end } => self.push_literal(&composite.literals[start as usize..end as usize]),
+                _ => self.push_item(item),
+            }
+        }
+        Some(())
+    }
+
+    /// The maximum number of bytes of a single formatted timestamp.
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    /// The length of inputs that can be parsed using the fixed-width fast path,
+    /// or `None` if the pattern contains items of variable width.
+    pub fn fixed_width(&self) -> Option<usize> {
+        self.fixed_len
+    }
+
+    /// Appends the formatted timestamp including its timezone offset to the output.
+    pub fn append_packed_timestamp(&self, ts: &PackedTimestamp, output: &mut Vec<u8>) {
+        self.append_components(&packed_to_components(ts), output)
+    }
+
+    /// Appends the timestamp given in milliseconds since the unix epoch, formatted in utc, to the output.
+    pub fn append_timestamp_millis(&self, ts: i64, output: &mut Vec<u8>) {
+        self.append_components(&DateTimeComponents::from_timestamp_millis(ts), output)
+    }
+
+    pub fn format_packed_timestamp(&self, ts: &PackedTimestamp) -> String {
+        let mut output = Vec::with_capacity(self.max_len);
+        self.append_packed_timestamp(ts, &mut output);
+        into_string(output)
+    }
+
+    pub fn format_timestamp_millis(&self, ts: i64) -> String {
+        let mut output = Vec::with_capacity(self.max_len);
+        self.append_timestamp_millis(ts, &mut output);
+        into_string(output)
+    }
+
+    /// Formats a batch of timestamps in milliseconds since the unix epoch in utc.
+    /// The output layout is the same as for `format_timestamp_millis_batch`.
+    pub fn format_timestamp_millis_batch(&self, input: &[i64], values: &mut Vec<u8>, offsets: &mut Vec<i32>) {
+        self.format_rows(
+            input.len(),
+            |i| DateTimeComponents::from_timestamp_millis(input[i]),
+            values,
+            offsets,
+        )
+    }
+
+    /// Formats a batch of packed timestamps including their timezone offsets.
+    /// The output layout is the same as for `format_timestamp_millis_batch`.
+    pub fn format_packed_timestamp_batch(&self, input: &[PackedTimestamp], values: &mut Vec<u8>, offsets: &mut Vec<i32>) {
+        self.format_rows(input.len(), |i| packed_to_components(&input[i]), values, offsets)
+    }
+
+    fn format_rows(&self, len: usize, row: impl Fn(usize) -> DateTimeComponents, values: &mut Vec<u8>, offsets: &mut Vec<i32>) {
+        if offsets.is_empty() {
+            offsets.push(values.len().try_into().expect("offset overflow"));
+        }
+        offsets.reserve(len);
+        values.reserve(len * self.max_len);
+
+        for i in 0..len {
+            self.append_components(&row(i), values);
+            offsets.push(values.len().try_into().expect("offset overflow"));
+        }
+    }
+
+    fn append_components(&self, ts: &DateTimeComponents, output: &mut Vec<u8>) {
+        let year = ts.year;
+        let four_digit_year = year >= 0 && year <= 9999;
+        let hour12 = (ts.hour as u16 + 11) % 12 + 1;
+        let digits = two_digits_x8([
+            if four_digit_year { (year / 100) as u16 } else { 0 },
+            year.rem_euclid(100) as u16,
+            ts.month as u16,
+            ts.day as u16,
+            ts.hour as u16,
+            ts.minute as u16,
+            ts.second as u16,
+            hour12,
+        ]);
+
+        let (epoch_days, weekday) = if self.needs_epoch_days {
+            let date = EpochDays::from_ymd(year, ts.month as i32, ts.day as i32);
+            (date.days(), date.weekday().days_since_sunday() as usize)
+        } else {
+            (0, 0)
+        };
+
+        for item in &self.items {
+            match *item {
+                Item::Literal { start, end } => output.extend_from_slice(&self.literals[start as usize..end as usize]),
+                Item::Year if four_digit_year => {
+                    output.extend_from_slice(&digits[Lane::Century as usize]);
+                    output.extend_from_slice(&digits[Lane::YearOfCentury as usize]);
+                }
+                Item::Year => {
+                    let mut buffer = [0_u8; 11];
+                    let (len, last_digits) = format_expanded_year_prefix_to_slice(&mut buffer, year);
+                    let last_digits = two_digits_x8([(last_digits / 100) as u16, (last_digits % 100) as u16, 0, 0, 0, 0, 0, 0]);
+                    output.extend_from_slice(&buffer[..len]);
+                    output.extend_from_slice(&last_digits[0]);
+                    output.extend_from_slice(&last_digits[1]);
+                }
+                Item::Century if four_digit_year => output.extend_from_slice(&digits[Lane::Century as usize]),
+                Item::Century => output.extend_from_slice(year.div_euclid(100).to_string().as_bytes()),
+                Item::TwoDigits(lane) => output.extend_from_slice(&digits[lane as usize]),
+                Item::SpacePadded(lane) => {
+                    let [tens, ones] = digits[lane as usize];
+                    output.extend_from_slice(&[if tens == b'0' { b' ' } else { tens }, ones]);
+                }
+                Item::DayOfYear => {
+                    let day_of_year = (epoch_days - EpochDays::from_ymd(year, 1, 1).days() + 1) as u16;
+                    let last_digits = two_digits_x8([day_of_year % 100, 0, 0, 0, 0, 0, 0, 0]);
+                    output.push(b'0' + (day_of_year / 100) as u8);
+                    output.extend_from_slice(&last_digits[0]);
+                }
+                Item::Fraction { digits, dot } => {
+                    if dot {
+                        output.push(b'.');
+                    }
+                    output.extend_from_slice(&fraction_digits(ts.nanosecond)[..digits as usize]);
+                }
+                Item::FractionAuto => {
+                    let nanosecond = ts.nanosecond;
+                    if nanosecond != 0 {
+                        let digits = if nanosecond % 1_000_000 == 0 {
+                            3
+                        } else if nanosecond % 1_000 == 0 {
+                            6
+                        } else {
+                            9
+                        };
+                        output.push(b'.');
+                        output.extend_from_slice(&fraction_digits(nanosecond)[..digits]);
+                    }
+                }
+                Item::WeekdayName { abbreviated } => {
+                    let name = WEEKDAY_NAMES[weekday].as_bytes();
+                    output.extend_from_slice(if abbreviated { &name[..3] } else { name });
+                }
+                Item::MonthName { abbreviated } => {
+                    let name = MONTH_NAMES[ts.month as usize - 1].as_bytes();
+                    output.extend_from_slice(if abbreviated { &name[..3] } else { name });
+                }
+                Item::WeekdayFromMonday => output.push(b'0' + ((weekday + 6) % 7 + 1) as u8),
+                Item::WeekdayFromSunday => output.push(b'0' + weekday as u8),
+                Item::AmPm { lowercase } => output.extend_from_slice(match (ts.hour < 12, lowercase) {
+                    (true, false) => b"AM",
+                    (false, false) => b"PM",
+                    (true, true) => b"am",
+                    (false, true) => b"pm",
+                }),
+                Item::Offset { colon } => {
+                    let offset = ts.offset_minute.unsigned_abs();
+                    let offset_digits = two_digits_x8([(offset / 60) as u16, (offset % 60) as u16, 0, 0, 0, 0, 0, 0]);
+                    output.push(if ts.offset_minute < 0 { b'-' } else { b'+' });
+                    output.extend_from_slice(&offset_digits[0]);
+                    if colon {
+                        output.push(b':');
+                    }
+                    output.extend_from_slice(&offset_digits[1]);
+                }
+            }
+        }
+    }
+}
+
+impl Format {
+    /// Parses a timestamp according to the pattern, keeping its timezone offset.
+    ///
+    /// Components that are not part of the pattern default to 1970-01-01T00:00:00 in utc. A two digit year
+    /// without century is mapped to the years 1969 to 2068. Weekday names and numbers are parsed but not checked.
+    pub fn parse_packed_timestamp(&self, input: &str) -> ParseResult<PackedTimestamp> {
+        Ok(self.parse_components(input.as_bytes())?.to_packed())
+    }
+
+    /// Parses a timestamp with nanosecond precision according to the pattern, keeping its timezone offset.
+    pub fn parse_packed_timestamp_nanos(&self, input: &str) -> ParseResult<PackedTimestampNanos> {
+        Ok(self.parse_components(input.as_bytes())?.to_packed_nanos())
+    }
+
+    /// Parses a timestamp according to the pattern into milliseconds since the unix epoch.
+    pub fn parse_timestamp_millis(&self, input: &str) -> ParseResult<i64> {
+        Ok(ts_to_epoch_millis(&self.parse_components(input.as_bytes())?))
+    }
+
+    /// Parses a batch of timestamps according to the pattern into milliseconds since the unix epoch.
+    /// The handling of errors and the validity bitmap is the same as for `parse_to_timestamp_millis_batch`.
+    pub fn parse_timestamp_millis_batch(&self, input: &[&str], output: &mut [i64], validity: &mut [u8]) -> Vec<RowParseError> {
+        assert_eq!(input.len(), output.len());
+        self.parse_rows(|i| Some(input[i].as_bytes()), output, validity)
+    }
+
+    /// Parses a batch of timestamps stored in a single buffer with separate offsets.
+    /// The handling of nulls is the same as for `parse_to_timestamp_millis_batch_offsets`.
+    pub fn parse_timestamp_millis_batch_offsets<O: Copy + Into<i64>>(
+        &self,
+        offsets: &[O],
+        values: &[u8],
+        nulls: Option<&[u8]>,
+        output: &mut [i64],
+        validity: &mut [u8],
+    ) -> Vec<RowParseError> {
+        self.parse_rows(offset_rows(offsets, values, nulls, output.len()), output, validity)
+    }
+
+    fn parse_rows<'a>(
+        &self,
+        row: impl Fn(usize) -> Option<&'a [u8]>,
+        output: &mut [i64],
+        validity: &mut [u8],
+    ) -> Vec<RowParseError> {
+        let len = output.len();
+        let mut writer = RowWriter::new(output, validity);
+        for i in 0..len {
+            writer.set(
+                i,
+                row(i).map(|bytes| self.parse_components(bytes).map(|ts| ts_to_epoch_millis(&ts))),
+            );
+        }
+        writer.errors
+    }
+
+    fn parse_components(&self, bytes: &[u8]) -> ParseResult<DateTimeComponents> {
+        if self.fixed_len == Some(bytes.len()) {
+            if let Some(fields) = self.parse_fixed_width(bytes) {
+                return fields.resolve();
+            }
+            // fall through to the generic parser to report the exact error
+        }
+        self.parse_generic(bytes)
+    }
+
+    fn parse_generic(&self, bytes: &[u8]) -> ParseResult<DateTimeComponents> {
+        let mut fields = ParsedFields::default();
+        let mut index = 0;
+        for item in &self.items {
+            self.parse_item(*item, bytes, &mut index, &mut fields)?;
+        }
+        if index != bytes.len() {
+            return Err(ParseError::TrailingChar {
+                position: index,
+                byte: bytes[index],
+            });
+        }
+
+        fields.resolve()
+    }
+
+    /// Fast path for inputs matching the fixed width of the pattern, where every item starts at a known offset.
+    /// Returns `None` for any invalid input, so that the generic parser can report the error.
+    #[inline]
+    fn parse_fixed_width(&self, bytes: &[u8]) -> Option<ParsedFields> {
+        let mut fields = ParsedFields::default();
+        let mut offset = 0;
+        for item in &self.items {
+            match *item {
+                Item::Literal { start, end } => {
+                    let literal = &self.literals[start as usize..end as usize];
+                    if &bytes[offset..offset + literal.len()] != literal {
+                        return None;
+                    }
+                }
+                Item::Year => fields.year = Some(fixed_digits(bytes, offset, 4)? as i32),
+                Item::Century => fields.century = Some(fixed_digits(bytes, offset, 2)? as i32),
+                Item::TwoDigits(lane) => fields.set_lane(lane, fixed_digits(bytes, offset, 2)?),
+                Item::SpacePadded(lane) if bytes[offset] == b' ' => fields.set_lane(lane, fixed_digits(bytes, offset + 1, 1)?),
+                Item::SpacePadded(lane) => fields.set_lane(lane, fixed_digits(bytes, offset, 2)?),
+                Item::DayOfYear => fields.day_of_year = Some(fixed_digits(bytes, offset, 3)?),
+                Item::Fraction { digits, dot } => {
+                    if dot && bytes[offset] != b'.' {
+                        return None;
+                    }
+                    let value = fixed_digits(bytes, offset + dot as usize, digits as usize)?;
+                    fields.nanosecond = value * 10_u32.pow(9 - digits as u32);
+                }
+                Item::WeekdayName { .. } => {
+                    find_name(&bytes[offset..offset + 3], &WEEKDAY_NAMES, true)?;
+                }
+                Item::MonthName { .. } => {
+                    fields.month = Some(find_name(&bytes[offset..offset + 3], &MONTH_NAMES, true)? as u32 + 1)
+                }
+                Item::WeekdayFromMonday | Item::WeekdayFromSunday => {
+                    fixed_digits(bytes, offset, 1)?;
+                }
+                Item::AmPm { .. } => fields.pm = Some(find_am_pm(&bytes[offset..offset + 2])?),
+                Item::Offset { colon } => {
+                    let sign = match bytes[offset] {
+                        b'+' => 1,
+                        b'-' => -1,
+                        _ => return None,
+                    };
+                    if colon && bytes[offset + 3] != b':' {
+                        return None;
+                    }
+                    let hour = fixed_digits(bytes, offset + 1, 2)?;
+                    let minute = fixed_digits(bytes, offset + 3 + colon as usize, 2)?;
+                    if minute > 59 {
+                        return None;
+                    }
+                    fields.offset_minute = sign * (hour * 60 + minute) as i32;
+                }
+                Item::FractionAuto => return None,
+            }
+            offset += item.fixed_parse_len()?;
+        }
+        Some(fields)
+    }
+
+    fn parse_item(&self, item: Item, bytes: &[u8], index: &mut usize, fields: &mut ParsedFields) -> ParseResult<()> {
+        match item {
+            Item::Literal { start, end } => {
+                for expected in &self.literals[start as usize..end as usize] {
+                    expect(bytes, index, *expected)?;
+                }
+            }
+            Item::Year => fields.year = Some(parse_year(bytes, index)?),
+            Item::Century => fields.century = Some(parse_num2(bytes, index)? as i32),
+            Item::TwoDigits(lane) => fields.set_lane(lane, parse_num2(bytes, index)?),
+            Item::SpacePadded(lane) => {
+                let value = if bytes.get(*index) == Some(&b' ') {
+                    *index += 1;
+                    digit(bytes, index)?
+                } else {
+                    parse_num2(bytes, index)?
+                };
+                fields.set_lane(lane, value);
+            }
+            Item::DayOfYear => {
+                let hundreds = digit(bytes, index)?;
+                fields.day_of_year = Some(hundreds * 100 + parse_num2(bytes, index)?);
+            }
+            Item::Fraction { digits, dot } => {
+                if dot {
+                    expect(bytes, index, b'.')?;
+                }
+                let mut value = 0;
+                for _ in 0..digits {
+                    value = value * 10 + digit(bytes, index)?;
+                }
+                fields.nanosecond = value * 10_u32.pow(9 - digits as u32);
+            }
+            Item::FractionAuto => {
+                if bytes.get(*index) == Some(&b'.') {
+                    *index += 1;
+                    fields.nanosecond = parse_nano(bytes, index)?;
+                }
+            }
+            Item::WeekdayName { abbreviated } => {
+                parse_name(bytes, index, &WEEKDAY_NAMES, abbreviated, Expected::WeekdayName)?;
+            }
+            Item::MonthName { abbreviated } => {
+                fields.month = Some(parse_name(bytes, index, &MONTH_NAMES, abbreviated, Expected::MonthName)? as u32 + 1);
+            }
+            Item::WeekdayFromMonday | Item::WeekdayFromSunday => {
+                digit(bytes, index)?;
+            }
+            Item::AmPm { .. } => {
+                let pm = bytes.get(*index..*index + 2).and_then(find_am_pm);
+                match pm {
+                    Some(pm) => {
+                        fields.pm = Some(pm);
+                        *index += 2;
+                    }
+                    None => return Err(unexpected(bytes, *index, Expected::AmPm)),
+                }
+            }
+            Item::Offset { colon } => {
+                let sign = match bytes.get(*index) {
+                    Some(b'Z') => {
+                        *index += 1;
+                        fields.offset_minute = 0;
+                        return Ok(());
+                    }
+                    Some(b'+') => 1,
+                    Some(b'-') => -1,
+                    _ => return Err(unexpected(bytes, *index, Expected::OffsetSign)),
+                };
+                *index += 1;
+                let hour = parse_num2(bytes, index)?;
+                if colon || bytes.get(*index) == Some(&b':') {
+                    expect(bytes, index, b':')?;
+                }
+                let minute = parse_num2(bytes, index)?;
+                if minute > 59 {
+                    return Err(ParseError::InvalidValue(DateTimeField::OffsetMinutes));
+                }
+                fields.offset_minute = sign * (hour * 60 + minute) as i32;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Components collected while parsing, which are combined and validated after the whole input was consumed.
+#[derive(Default)]
+struct ParsedFields {
+    year: Option<i32>,
+    century: Option<i32>,
+    year_of_century: Option<u32>,
+    month: Option<u32>,
+    day: Option<u32>,
+    day_of_year: Option<u32>,
+    hour: u32,
+    hour12: Option<u32>,
+    pm: Option<bool>,
+    minute: u32,
+    second: u32,
+    nanosecond: u32,
+    offset_minute: i32,
+}
+
+impl ParsedFields {
+    #[inline]
+    fn set_lane(&mut self, lane: Lane, value: u32) {
+        match lane {
+            Lane::Century => self.century = Some(value as i32),
+            Lane::YearOfCentury => self.year_of_century = Some(value),
+            Lane::Month => self.month = Some(value),
+            Lane::Day => self.day = Some(value),
+            Lane::Hour => self.hour = value,
+            Lane::Minute => self.minute = value,
+            Lane::Second => self.second = value,
+            Lane::Hour12 => self.hour12 = Some(value),
+        }
+    }
+
+    fn resolve(&self) -> ParseResult<DateTimeComponents> {
+        let year = match (self.year, self.century, self.year_of_century) {
+            (Some(year), _, _) => year,
+            (None, Some(century), year_of_century) => century * 100 + year_of_century.unwrap_or(0) as i32,
+            (None, None, Some(year_of_century)) if year_of_century < 69 => 2000 + year_of_century as i32,
+            (None, None, Some(year_of_century)) => 1900 + year_of_century as i32,
+            (None, None, None) => 1970,
+        };
+        // checked before any date arithmetic, which would overflow for huge expanded years
+        if year < MIN_YEAR || year > MAX_YEAR {
+            return Err(ParseError::InvalidValue(DateTimeField::Year));
+        }
+
+        let hour = match self.hour12 {
+            Some(hour12) if hour12 < 1 || hour12 > 12 => return Err(ParseError::InvalidValue(DateTimeField::Hour)),
+            Some(hour12) => hour12 % 12 + if self.pm == Some(true) { 12 } else { 0 },
+            None => self.hour,
+        };
+
+        let (month, day) = match self.day_of_year {
+            Some(day_of_year) => {
+                if day_of_year < 1 || day_of_year > 365 + is_leap_year(year) as u32 {
+                    return Err(ParseError::InvalidValue(DateTimeField::Day));
+                }
+                let (_, month, day) = EpochDays::new(EpochDays::from_ymd(year, 1, 1).days() + day_of_year as i32 - 1).to_ymd();
+                (month as u32, day as u32)
+            }
+            None => (self.month.unwrap_or(1), self.day.unwrap_or(1)),
+        };
+
+        let ts = DateTimeComponents::new_with_nanosecond(
+            year,
+            saturating_u8(month),
+            saturating_u8(day),
+            saturating_u8(hour),
+            saturating_u8(self.minute),
+            saturating_u8(self.second),
+            self.nanosecond,
+            self.offset_minute,
+        );
+        ts.validate()?;
+        Ok(ts)
+    }
+}
+
+#[inline(always)]
+fn fixed_digits(bytes: &[u8], offset: usize, len: usize) -> Option<u32> {
+    let mut value = 0;
+    for byte in &bytes[offset..offset + len] {
+        let digit = byte.wrapping_sub(b'0');
+        if digit > 9 {
+            return None;
+        }
+        value = value * 10 + digit as u32;
+    }
+    Some(value)
+}
+
+#[inline]
+fn find_am_pm(bytes: &[u8]) -> Option<bool> {
+    if bytes.eq_ignore_ascii_case(b"am") {
+        Some(false)
+    } else if bytes.eq_ignore_ascii_case(b"pm") {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+/// Returns the index of the name matching the input, ignoring case.
+#[inline]
+fn find_name(input: &[u8], names: &[&str], abbreviated: bool) -> Option<usize> {
+    names.iter().position(|name| {
+        let name = if abbreviated { &name.as_bytes()[..3] } else { name.as_bytes() };
+        input.len() >= name.len() && input[..name.len()].eq_ignore_ascii_case(name)
+    })
+}
+
+fn parse_name(bytes: &[u8], index: &mut usize, names: &[&str], abbreviated: bool, expected: Expected) -> ParseResult<usize> {
+    match find_name(&bytes[*index..], names, abbreviated) {
+        Some(position) => {
+            *index += if abbreviated { 3 } else { names[position].len() };
+            Ok(position)
+        }
+        None => Err(unexpected(bytes, *index, expected)),
+    }
+}
+
+#[cold]
+fn unexpected(bytes: &[u8], index: usize, expected: Expected) -> ParseError {
+    match bytes.get(index) {
+        Some(byte) => ParseError::InvalidChar {
+            position: index,
+            byte: *byte,
+            expected,
+        },
+        None => ParseError::InvalidLen(index),
+    }
+}
+
+impl FromStr for Format {
+    type Err = PatternError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Format::compile(s)
+    }
+}
+
+#[inline]
+fn packed_to_components(ts: &PackedTimestamp) -> DateTimeComponents {
+    DateTimeComponents::new_with_offset_minute(
+        ts.year(),
+        ts.month() as u8,
+        ts.day() as u8,
+        ts.hour() as u8,
+        ts.minute() as u8,
+        ts.second() as u8,
+        ts.millisecond(),
+        ts.offset_minutes(),
+    )
+}
+
+#[inline]
+fn into_string(output: Vec<u8>) -> String {
+    // literals come from a valid utf8 pattern and all other items are ascii
+    #[cfg(not(debug_assertions))]
+    {
+        unsafe { String::from_utf8_unchecked(output) }
+    }
+    #[cfg(debug_assertions)]
+    {
+        String::from_utf8(output).expect("utf8 string")
+    }
+}
+
+/// Converts 8 values below 100 into two ascii digits each. Like `format_simd_mul_to_slice` this divides by 10
+/// using a multiplication with the reciprocal, so that the compiler can vectorize the loop.
+#[inline(always)]
+fn two_digits_x8(values: [u16; 8]) -> [[u8; 2]; 8] {
+    let mut result = [[0_u8; 2]; 8];
+    for i in 0..8 {
+        let tens = ((values[i] as u32 * 52429) >> 19) as u16;
+        let ones = values[i] - tens * 10;
+        result[i] = [b'0' + tens as u8, b'0' + ones as u8];
+    }
+    result
+}
+
+#[inline]
+fn fraction_digits(nanosecond: u32) -> [u8; 9] {
+    let mut buffer = [b'0'; 9];
+    let mut remaining = nanosecond;
+    for digit in buffer.iter_mut().rev() {
+        *digit = b'0' + (remaining % 10) as u8;
+        remaining /= 10;
+    }
+    buffer
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pattern::two_digits_x8;
+    use crate::{DateTimeField, Expected, Format, PackedTimestamp, PackedTimestampNanos, ParseError, PatternError, RowParseError};
+
+    #[test]
+    fn test_two_digits() {
+        for i in 0..100_u16 {
+            assert_eq!(two_digits_x8([i; 8])[0], format!("{:02}", i).as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_format_pattern() {
+        let ts = PackedTimestamp::new(2022, 8, 21, 17, 30, 15, 250, 120);
+        let format = |pattern: &str| Format::compile(pattern).unwrap().format_packed_timestamp(&ts);
+
+        assert_eq!(format("%Y-%m-%d %H:%M:%S%.3f %z"), "2022-08-21 17:30:15.250 +0200");
+        assert_eq!(format("%d/%b/%Y:%H:%M:%S %z"), "21/Aug/2022:17:30:15 +0200");
+        assert_eq!(format("%j"), "233");
+        assert_eq!(format("%a %A %u %w"), "Sun Sunday 7 0");
+        assert_eq!(format("%B %h %C %y"), "August Aug 20 22");
+        assert_eq!(format("%I:%M %p %P|%l|%k|%e"), "05:30 PM pm| 5|17|21");
+        assert_eq!(format("%F %T%:z"), "2022-08-21 17:30:15+02:00");
+        assert_eq!(format("%D %R %r %v"), "08/21/22 17:30 05:30:15 PM 21-Aug-2022");
+        assert_eq!(format("%f|%3f|%6f|%.9f|%.f"), "250000000|250|250000|.250000000|.250");
+        assert_eq!(format("100%% done%n%tü"), "100% done\n\tü");
+        assert_eq!(format(""), "");
+    }
+
+    #[test]
+    fn test_format_pattern_edge_cases() {
+        let format = Format::compile("%Y %C %y %j %a %I%p %z %.f").unwrap();
+        assert_eq!(
+            format.format_packed_timestamp(&PackedTimestamp::new(-44, 3, 15, 0, 0, 0, 0, -330)),
+            "-0044 -1 56 075 Thu 12AM -0530 "
+        );
+        assert_eq!(
+            format.format_packed_timestamp(&PackedTimestamp::new_utc(2024, 12, 31, 12, 0, 0, 1)),
+            "2024 20 24 366 Tue 12PM +0000 .001"
+        );
+        assert_eq!(format.format_timestamp_millis(0), "1970 19 70 001 Thu 12AM +0000 ");
+        assert!(format.max_len() >= format.format_timestamp_millis(-62167219200001).len());
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        assert_eq!(
+            Format::compile("%Y-%Q"),
+            Err(PatternError::UnsupportedSpecifier {
+                position: 3,
+                specifier: 'Q'
+            })
+        );
+        assert_eq!(
+            Format::compile("%.4f"),
+            Err(PatternError::UnsupportedSpecifier {
+                position: 0,
+                specifier: '4'
+            })
+        );
+        assert_eq!(
+            Format::compile("%H:%"),
+            Err(PatternError::IncompleteSpecifier { position: 3 })
+        );
+        assert_eq!(Format::compile("%:"), Err(PatternError::IncompleteSpecifier { position: 0 }));
+        assert_eq!(
+            "%ä".parse::<Format>(),
+            Err(PatternError::UnsupportedSpecifier {
+                position: 0,
+                specifier: 'ä'
+            })
+        );
+    }
+
+    #[test]
+    fn test_format_batch() {
+        let format = Format::compile("%d/%b/%Y %H:%M:%S%.3f").unwrap();
+        let input = [1661103015250, 0, -1];
+
+        let mut values = vec![];
+        let mut offsets = vec![];
+        format.format_timestamp_millis_batch(&input, &mut values, &mut offsets);
+        assert_eq!(offsets, vec![0, 24, 48, 72]);
+        assert_eq!(
+            std::str::from_utf8(&values).unwrap(),
+            "21/Aug/2022 17:30:15.25001/Jan/1970 00:00:00.00031/Dec/1969 23:59:59.999"
+        );
+
+        let packed = input.map(PackedTimestamp::from_timestamp_millis);
+        let mut packed_values = vec![];
+        let mut packed_offsets = vec![];
+        format.format_packed_timestamp_batch(&packed, &mut packed_values, &mut packed_offsets);
+        assert_eq!(packed_values, values);
+        assert_eq!(packed_offsets, offsets);
+    }
+
+    #[test]
+    fn test_parse_pattern() {
+        let parse = |pattern: &str, input: &str| Format::compile(pattern).unwrap().parse_packed_timestamp(input);
+
+        let expected = Ok(PackedTimestamp::new_utc(2022, 8, 21, 17, 30, 15, 0));
+        assert_eq!(parse("%d/%m/%Y %H:%M:%S", "21/08/2022 17:30:15"), expected);
+        assert_eq!(parse("%Y%m%d-%H%M%S", "20220821-173015"), expected);
+        assert_eq!(parse("%d/%b/%Y:%T %z", "21/aug/2022:17:30:15 +0000"), expected);
+        assert_eq!(
+            parse("%A, %B %e, %Y %l:%M:%S %p", "Sunday, August 21, 2022  5:30:15 PM"),
+            expected
+        );
+        assert_eq!(parse("%y-%j %I%M%S%P", "22-233 053015pm"), expected);
+        assert_eq!(parse("%C%y-%m-%d %R:%S", "2022-08-21 17:30:15"), expected);
+
+        assert_eq!(
+            parse("%F %T%.f%:z", "2022-08-21 17:30:15.25+02:00"),
+            Ok(PackedTimestamp::new(2022, 8, 21, 17, 30, 15, 250, 120))
+        );
+        assert_eq!(
+            parse("%F %T%.f%z", "2022-08-21 17:30:15-0530"),
+            Ok(PackedTimestamp::new(2022, 8, 21, 17, 30, 15, 0, -330))
+        );
+        assert_eq!(
+            parse("%F %T%.3f%z", "2022-08-21 17:30:15.250Z"),
+            Ok(PackedTimestamp::new_utc(2022, 8, 21, 17, 30, 15, 250))
+        );
+        assert_eq!(parse("%Y-%m-%d", "-0044-03-15"), Ok(PackedTimestamp::new_ymd_utc(-44, 3, 15)));
+        assert_eq!(
+            parse("%H:%M", "17:30"),
+            Ok(PackedTimestamp::new_utc(1970, 1, 1, 17, 30, 0, 0))
+        );
+        assert_eq!(parse("%y", "69"), Ok(PackedTimestamp::new_ymd_utc(1969, 1, 1)));
+        assert_eq!(parse("%y", "68"), Ok(PackedTimestamp::new_ymd_utc(2068, 1, 1)));
+        assert_eq!(parse("%I %p", "12 AM"), Ok(PackedTimestamp::new_utc(1970, 1, 1, 0, 0, 0, 0)));
+        assert_eq!(parse("%I %p", "12 PM"), Ok(PackedTimestamp::new_utc(1970, 1, 1, 12, 0, 0, 0)));
+        assert_eq!(parse("%Y %j", "2024 366"), Ok(PackedTimestamp::new_ymd_utc(2024, 12, 31)));
+
+        assert_eq!(
+            Format::compile("%Y-%m-%dT%H:%M:%S%.9f")
+                .unwrap()
+                .parse_packed_timestamp_nanos("2022-08-21T17:30:15.123456789"),
+            Ok(PackedTimestampNanos::new_utc(2022, 8, 21, 17, 30, 15, 123_456_789))
+        );
+        assert_eq!(
+            Format::compile("%d/%m/%Y").unwrap().parse_timestamp_millis("02/01/1970"),
+            Ok(86_400_000)
+        );
+    }
+
+    #[test]
+    fn test_parse_pattern_errors() {
+        let parse = |pattern: &str, input: &str| Format::compile(pattern).unwrap().parse_packed_timestamp(input);
+
+        assert_eq!(
+            parse("%d/%m/%Y", "21-08-2022"),
+            Err(ParseError::InvalidChar {
+                position: 2,
+                byte: b'-',
+                expected: Expected::Char(b'/')
+            })
+        );
+        assert_eq!(parse("%d/%m/%Y", "21/08/20"), Err(ParseError::InvalidLen(8)));
+        assert_eq!(
+            parse("%d/%m/%Y", "21/08/2022 "),
+            Err(ParseError::TrailingChar {
+                position: 10,
+                byte: b' '
+            })
+        );
+        assert_eq!(
+            parse("%d/%m/%Y", "30/02/2022"),
+            Err(ParseError::InvalidValue(DateTimeField::Day))
+        );
+        assert_eq!(parse("%H:%M", "24:00"), Err(ParseError::InvalidValue(DateTimeField::Hour)));
+        assert_eq!(parse("%I %p", "13 PM"), Err(ParseError::InvalidValue(DateTimeField::Hour)));
+        assert_eq!(parse("%Y %j", "2023 366"), Err(ParseError::InvalidValue(DateTimeField::Day)));
+        assert_eq!(
+            parse("%Y %j", "+999999999 001"),
+            Err(ParseError::InvalidValue(DateTimeField::Year))
+        );
+        assert_eq!(
+            parse("%Y %j", "-10000 001"),
+            Err(ParseError::InvalidValue(DateTimeField::Year))
+        );
+        assert_eq!(
+            parse("%z", "+0160"),
+            Err(ParseError::InvalidValue(DateTimeField::OffsetMinutes))
+        );
+        assert_eq!(
+            parse("%d %b", "21 Foo"),
+            Err(ParseError::InvalidChar {
+                position: 3,
+                byte: b'F',
+                expected: Expected::MonthName
+            })
+        );
+        assert_eq!(
+            parse("%a %d", "XYZ 21"),
+            Err(ParseError::InvalidChar {
+                position: 0,
+                byte: b'X',
+                expected: Expected::WeekdayName
+            })
+        );
+        assert_eq!(
+            parse("%I%p", "05XM"),
+            Err(ParseError::InvalidChar {
+                position: 2,
+                byte: b'X',
+                expected: Expected::AmPm
+            })
+        );
+        assert_eq!(
+            parse("%I%p", "05P"),
+            Err(ParseError::InvalidChar {
+                position: 2,
+                byte: b'P',
+                expected: Expected::AmPm
+            })
+        );
+        assert_eq!(
+            parse("%z", "0100"),
+            Err(ParseError::InvalidChar {
+                position: 0,
+                byte: b'0',
+                expected: Expected::OffsetSign
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_fixed_width_matches_generic() {
+        let patterns = [
+            "%d/%m/%Y %H:%M:%S",
+            "%Y%m%d-%H%M%S",
+            "%d/%b/%Y:%T %z",
+            "%a %e %b %Y %I:%M:%S.%3f %p %:z",
+            "%F %T%.6f",
+            "%y-%j %u %w",
+        ];
+        let inputs = [
+            "21/08/2022 17:30:15",
+            "20220821-173015",
+            "21/Aug/2022:17:30:15 +0200",
+            "21/Aug/2022:17:30:15 +0260",
+            "21/Aug/2022:17:30:15 Z0000",
+            "Sun 21 Aug 2022 05:30:15.250 PM -05:30",
+            "Sun  1 Aug 2022 05:30:15.250 pm -05:30",
+            "Sun  1 Xyz 2022 05:30:15.250 pm -05:30",
+            "2022-08-21 17:30:15.123456",
+            "2022-08-21 17:30:15.12345X",
+            "2022-02-29 17:30:15.123456",
+            "22-233 7 0",
+            "22-366 7 0",
+            "2X/08/2022 17:30:15",
+        ];
+        for pattern in patterns {
+            let format = Format::compile(pattern).unwrap();
+            let fixed_width = format.fixed_width().unwrap();
+            for input in inputs.iter().filter(|input| input.len() == fixed_width) {
+                let fast = format.parse_fixed_width(input.as_bytes()).map(|fields| fields.resolve());
+                let generic = format.parse_generic(input.as_bytes());
+                match fast {
+                    Some(fast) => assert_eq!(fast, generic, "{} {}", pattern, input),
+                    None => assert!(generic.is_err(), "{} {}", pattern, input),
+                }
+            }
+        }
+        assert_eq!(Format::compile("%F %T%.f").unwrap().fixed_width(), None);
+        assert_eq!(Format::compile("%B %d").unwrap().fixed_width(), None);
+        assert_eq!(Format::compile("%d/%m/%Y %H:%M:%S").unwrap().fixed_width(), Some(19));
+    }
+
+    #[test]
+    fn test_format_parse_roundtrip() {
+        let patterns = [
+            "%Y-%m-%dT%H:%M:%S%.3f%:z",
+            "%d/%b/%Y:%H:%M:%S%.3f %z",
+            "%a, %d %B %Y %I:%M:%S.%3f %p %z",
+            "%Y %j %T%.f %z",
+        ];
+        for pattern in patterns {
+            let format = Format::compile(pattern).unwrap();
+            for i in 0..2000_i64 {
+                let ts = PackedTimestamp::new(
+                    (1900 + i * 7 % 200) as i32,
+                    (i % 12 + 1) as u32,
+                    (i % 28 + 1) as u32,
+                    (i % 24) as u32,
+                    (i * 7 % 60) as u32,
+                    (i * 13 % 60) as u32,
+                    (i * 97 % 1000) as u32,
+                    (i % 37 - 18) as i32 * 45,
+                );
+                let formatted = format.format_packed_timestamp(&ts);
+                assert_eq!(format.parse_packed_timestamp(&formatted), Ok(ts), "{} {}", pattern, formatted);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_batch() {
+        let format = Format::compile("%d/%m/%Y %H:%M:%S").unwrap();
+        let input = ["21/08/2022 17:30:15", "01/01/1970 00:00:00", "31/12/1969 23:59:5X"];
+        let mut output = [-1; 3];
+        let mut validity = [0];
+
+        let errors = format.parse_timestamp_millis_batch(&input, &mut output, &mut validity);
+
+        assert_eq!(
+            errors,
+            vec![RowParseError {
+                row: 2,
+                error: ParseError::InvalidChar {
+                    position: 18,
+                    byte: b'X',
+                    expected: Expected::Digit
+                }
+            }]
+        );
+        assert_eq!(output, [1661103015000, 0, 0]);
+        assert_eq!(validity, [0b0000_0011]);
+
+        let values = input.concat();
+        let offsets = [0_i32, 19, 38, 57];
+        let nulls = [0b0000_0110];
+        let errors =
+            format.parse_timestamp_millis_batch_offsets(&offsets, values.as_bytes(), Some(&nulls), &mut output, &mut validity);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(output, [0, 0, 0]);
+        assert_eq!(validity, [0b0000_0010]);
+    }
+}
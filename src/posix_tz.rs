@@ -0,0 +1,513 @@
+use crate::epoch_days::{days_per_month, is_leap_year};
+use crate::error::TimeZoneError;
+use crate::packed::{MAX_OFFSET_MINUTES, MIN_OFFSET_MINUTES};
+use crate::timezone::packed_timestamp_at;
+use crate::{EpochDays, LocalTimeType, PackedTimestamp, MILLIS_PER_DAY};
+use std::str::FromStr;
+
+/// The day of the year on which a daylight saving time transition happens.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RuleDate {
+    /// `Jn`, the one-based day of the year from 1 to 365, never counting February 29.
+    Julian1(u16),
+    /// `n`, the zero-based day of the year from 0 to 365, counting February 29 in leap years.
+    Julian0(u16),
+    /// `Mm.w.d`, day `d` (0 is sunday) of week `w` of month `m`, where week 5 means the last such day of the month.
+    MonthWeekDay { month: u8, week: u8, weekday: u8 },
+}
+
+impl RuleDate {
+    fn epoch_days(&self, year: i32) -> i32 {
+        match *self {
+            RuleDate::Julian1(day) => {
+                let jan1 = EpochDays::from_ymd(year, 1, 1).days();
+                jan1 + day as i32 - 1 + (is_leap_year(year) && day >= 60) as i32
+            }
+            RuleDate::Julian0(day) => EpochDays::from_ymd(year, 1, 1).days() + day as i32,
+            RuleDate::MonthWeekDay { month, week, weekday } => {
+                let first_date = EpochDays::from_ymd(year, month as i32, 1);
+                let (first, first_weekday) = (first_date.days(), first_date.weekday().days_since_sunday());
+                let day = first + (weekday as i32 - first_weekday).rem_euclid(7) + (week as i32 - 1) * 7;
+                if day >= first + days_per_month(year, month as i32 - 1) {
+                    day - 7
+                } else {
+                    day
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct TransitionRule {
+    date: RuleDate,
+    /// Local time of the transition in seconds, which can be negative or exceed one day.
+    time_seconds: i32,
+}
+
+impl TransitionRule {
+    /// The instant of the transition in the given year, for a transition happening while `offset_seconds` is in effect.
+    fn timestamp_millis(&self, year: i32, offset_seconds: i32) -> i64 {
+        self.date.epoch_days(year) as i64 * MILLIS_PER_DAY + (self.time_seconds as i64 - offset_seconds as i64) * 1000
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct DaylightSaving {
+    time_type: LocalTimeType,
+    start: TransitionRule,
+    end: TransitionRule,
+}
+
+/// A time zone described by a POSIX TZ rule string like `CET-1CEST,M3.5.0,M10.5.0/3`,
+/// as used in the `TZ` environment variable and in the footer of TZif files.
+///
+/// Offsets in the rule string are positive west of Greenwich, while all accessors return offsets
+/// that are positive east of Greenwich. The extensions of RFC 8536 for transition times that
+/// are negative or exceed 24 hours are supported. If a rule string names a daylight saving time
+/// without transition rules, the US rules `M3.2.0,M11.1.0` are used.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PosixTimeZone {
+    standard: LocalTimeType,
+    daylight_saving: Option<DaylightSaving>,
+}
+
+impl PosixTimeZone {
+    pub fn parse(rule: &str) -> Result<Self, TimeZoneError> {
+        let mut parser = RuleParser {
+            bytes: rule.as_bytes(),
+            position: 0,
+        };
+        let zone = parser.zone()?;
+        if parser.position != parser.bytes.len() {
+            return Err(parser.error());
+        }
+        Ok(zone)
+    }
+
+    /// The local time type outside of daylight saving time.
+    #[inline]
+    pub fn standard(&self) -> &LocalTimeType {
+        &self.standard
+    }
+
+    /// The local time type during daylight saving time, if the rule has one.
+    #[inline]
+    pub fn daylight_saving(&self) -> Option<&LocalTimeType> {
+        self.daylight_saving.as_ref().map(|dst| &dst.time_type)
+    }
+
+    /// The local time type in effect at the given instant in milliseconds since the epoch.
+    pub fn local_time_type_at(&self, ts_millis: i64) -> &LocalTimeType {
+        let Some(dst) = &self.daylight_saving else {
+            return &self.standard;
+        };
+        let std_offset = self.standard.offset_seconds();
+        let year = EpochDays::from_timestamp_millis(ts_millis.saturating_add(std_offset as i64 * 1000)).extract_year();
+        let start = dst.start.timestamp_millis(year, std_offset);
+        let end = dst.end.timestamp_millis(year, dst.time_type.offset_seconds());
+
+        // on the southern hemisphere daylight saving time spans the end of the year
+        let is_dst = if start <= end {
+            ts_millis >= start && ts_millis < end
+        } else {
+            ts_millis < end || ts_millis >= start
+        };
+
+        if is_dst {
+            &dst.time_type
+        } else {
+            &self.standard
+        }
+    }
+
+    #[inline]
+    pub fn offset_seconds_at(&self, ts_millis: i64) -> i32 {
+        self.local_time_type_at(ts_millis).offset_seconds()
+    }
+
+    #[inline]
+    pub fn offset_minutes_at(&self, ts_millis: i64) -> i32 {
+        self.local_time_type_at(ts_millis).offset_minutes()
+    }
+
+    /// Converts an instant into a `PackedTimestamp` with the local date, time and offset of this zone.
+    pub fn to_packed_timestamp(&self, ts_millis: i64) -> PackedTimestamp {
+        packed_timestamp_at(ts_millis, |ts| self.offset_minutes_at(ts))
+    }
+}
+
+impl FromStr for PosixTimeZone {
+    type Err = TimeZoneError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Transitions happen at 02:00 local time if the rule does not specify a time.
+const DEFAULT_TRANSITION_TIME_SECONDS: i32 = 2 * 60 * 60;
+
+struct RuleParser<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> RuleParser<'a> {
+    fn error(&self) -> TimeZoneError {
+        TimeZoneError::InvalidRule { position: self.position }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.position).copied()
+    }
+
+    fn expect(&mut self, ch: u8) -> Result<(), TimeZoneError> {
+        if self.peek() != Some(ch) {
+            return Err(self.error());
+        }
+        self.position += 1;
+        Ok(())
+    }
+
+    fn zone(&mut self) -> Result<PosixTimeZone, TimeZoneError> {
+        let std_abbreviation = self.abbreviation()?;
+        let std_offset = self.offset()?;
+        let standard = LocalTimeType::new(std_offset, false, std_abbreviation);
+
+        if self.peek().is_none() {
+            return Ok(PosixTimeZone {
+                standard,
+                daylight_saving: None,
+            });
+        }
+
+        let dst_abbreviation = self.abbreviation()?;
+        let dst_offset = match self.peek() {
+            Some(b'+' | b'-' | b'0'..=b'9') => self.offset()?,
+            _ => {
+                // the implicit daylight saving offset can be out of range even if the standard offset is not
+                let offset = std_offset + 60 * 60;
+                if offset > MAX_OFFSET_MINUTES * 60 {
+                    return Err(self.error());
+                }
+                offset
+            }
+        };
+        let time_type = LocalTimeType::new(dst_offset, true, dst_abbreviation);
+
+        let (start, end) = if self.peek().is_none() {
+            (
+                TransitionRule {
+                    date: RuleDate::MonthWeekDay {
+                        month: 3,
+                        week: 2,
+                        weekday: 0,
+                    },
+                    time_seconds: DEFAULT_TRANSITION_TIME_SECONDS,
+                },
+                TransitionRule {
+                    date: RuleDate::MonthWeekDay {
+                        month: 11,
+                        week: 1,
+                        weekday: 0,
+                    },
+                    time_seconds: DEFAULT_TRANSITION_TIME_SECONDS,
+                },
+            )
+        } else {
+            self.expect(b',')?;
+            let start = self.transition()?;
+            self.expect(b',')?;
+            let end = self.transition()?;
+            (start, end)
+        };
+
+        Ok(PosixTimeZone {
+            standard,
+            daylight_saving: Some(DaylightSaving { time_type, start, end }),
+        })
+    }
+
+    /// Either at least three alphabetic characters, or at least three alphanumeric characters, `+` or `-` in angle brackets.
+    fn abbreviation(&mut self) -> Result<&'a str, TimeZoneError> {
+        let quoted = self.peek() == Some(b'<');
+        if quoted {
+            self.position += 1;
+        }
+        let start = self.position;
+        while let Some(ch) = self.peek() {
+            let valid = if quoted {
+                ch.is_ascii_alphanumeric() || ch == b'+' || ch == b'-'
+            } else {
+                ch.is_ascii_alphabetic()
+            };
+            if !valid {
+                break;
+            }
+            self.position += 1;
+        }
+        if self.position - start < 3 {
+            return Err(self.error());
+        }
+        let end = self.position;
+        if quoted {
+            self.expect(b'>')?;
+        }
+        // only ascii characters were accepted
+        Ok(std::str::from_utf8(&self.bytes[start..end]).unwrap())
+    }
+
+    /// An offset west of Greenwich, returned as seconds east of Greenwich.
+    fn offset(&mut self) -> Result<i32, TimeZoneError> {
+        let start = self.position;
+        let offset = -self.time(24)?;
+        if offset < MIN_OFFSET_MINUTES * 60 || offset > MAX_OFFSET_MINUTES * 60 {
+            return Err(TimeZoneError::InvalidRule { position: start });
+        }
+        Ok(offset)
+    }
+
+    /// `[+-]hh[:mm[:ss]]` in seconds.
+    fn time(&mut self, max_hours: i32) -> Result<i32, TimeZoneError> {
+        let negative = match self.peek() {
+            Some(b'-') => {
+                self.position += 1;
+                true
+            }
+            Some(b'+') => {
+                self.position += 1;
+                false
+            }
+            _ => false,
+        };
+        let mut seconds = self.number(3, max_hours)? * 60 * 60;
+        if self.peek() == Some(b':') {
+            self.position += 1;
+            seconds += self.number(2, 59)? * 60;
+            if self.peek() == Some(b':') {
+                self.position += 1;
+                seconds += self.number(2, 59)?;
+            }
+        }
+        Ok(if negative { -seconds } else { seconds })
+    }
+
+    fn transition(&mut self) -> Result<TransitionRule, TimeZoneError> {
+        let date = self.date()?;
+        let time_seconds = if self.peek() == Some(b'/') {
+            self.position += 1;
+            self.time(167)?
+        } else {
+            DEFAULT_TRANSITION_TIME_SECONDS
+        };
+        Ok(TransitionRule { date, time_seconds })
+    }
+
+    fn date(&mut self) -> Result<RuleDate, TimeZoneError> {
+        match self.peek() {
+            Some(b'J') => {
+                self.position += 1;
+                let start = self.position;
+                let day = self.number(3, 365)?;
+                if day == 0 {
+                    return Err(TimeZoneError::InvalidRule { position: start });
+                }
+                Ok(RuleDate::Julian1(day as u16))
+            }
+            Some(b'M') => {
+                self.position += 1;
+                let start = self.position;
+                let month = self.number(2, 12)?;
+                if month == 0 {
+                    return Err(TimeZoneError::InvalidRule { position: start });
+                }
+                self.expect(b'.')?;
+                let start = self.position;
+                let week = self.number(1, 5)?;
+                if week == 0 {
+                    return Err(TimeZoneError::InvalidRule { position: start });
+                }
+                self.expect(b'.')?;
+                let weekday = self.number(1, 6)?;
+                Ok(RuleDate::MonthWeekDay {
+                    month: month as u8,
+                    week: week as u8,
+                    weekday: weekday as u8,
+                })
+            }
+            _ => Ok(RuleDate::Julian0(self.number(3, 365)? as u16)),
+        }
+    }
+
+    /// Between one and `max_digits` ascii digits with a value of at most `max`.
+    fn number(&mut self, max_digits: usize, max: i32) -> Result<i32, TimeZoneError> {
+        let start = self.position;
+        let mut value = 0;
+        while let Some(ch @ b'0'..=b'9') = self.peek() {
+            if self.position - start == max_digits {
+                return Err(self.error());
+            }
+            value = value * 10 + (ch - b'0') as i32;
+            self.position += 1;
+        }
+        if self.position == start {
+            return Err(self.error());
+        }
+        if value > max {
+            return Err(TimeZoneError::InvalidRule { position: start });
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::{BERLIN, LORD_HOWE, NEW_YORK, SAO_PAULO};
+    use crate::{PackedTimestamp, PosixTimeZone, TimeZone, TimeZoneError};
+
+    #[test]
+    fn test_parse() {
+        let zone = PosixTimeZone::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+        assert_eq!(zone.standard().offset_minutes(), 60);
+        assert_eq!(zone.standard().abbreviation(), "CET");
+        assert!(!zone.standard().is_dst());
+        let dst = zone.daylight_saving().unwrap();
+        assert_eq!(dst.offset_minutes(), 120);
+        assert_eq!(dst.abbreviation(), "CEST");
+        assert!(dst.is_dst());
+
+        let zone: PosixTimeZone = "<+1030>-10:30<+11>-11,M10.1.0,M4.1.0".parse().unwrap();
+        assert_eq!(zone.standard().offset_minutes(), 630);
+        assert_eq!(zone.standard().abbreviation(), "+1030");
+        assert_eq!(zone.daylight_saving().unwrap().offset_minutes(), 660);
+
+        let zone = PosixTimeZone::parse("<-03>3").unwrap();
+        assert_eq!(zone.standard().offset_minutes(), -180);
+        assert_eq!(zone.daylight_saving(), None);
+
+        let zone = PosixTimeZone::parse("UTC0").unwrap();
+        assert_eq!(zone.offset_minutes_at(1648342800000), 0);
+    }
+
+    #[test]
+    fn test_offset_at() {
+        let zone = PosixTimeZone::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+        assert_eq!(zone.offset_minutes_at(1648342799000), 60);
+        assert_eq!(zone.offset_minutes_at(1648342800000), 120);
+        assert_eq!(zone.offset_minutes_at(1667091599000), 120);
+        assert_eq!(zone.offset_minutes_at(1667091600000), 60);
+        // 2100-03-28T01:00:00Z and 2100-10-31T01:00:00Z
+        assert_eq!(zone.offset_minutes_at(4109878799000), 60);
+        assert_eq!(zone.offset_minutes_at(4109878800000), 120);
+        assert_eq!(zone.offset_minutes_at(4128627599000), 120);
+        assert_eq!(zone.offset_minutes_at(4128627600000), 60);
+
+        assert_eq!(
+            zone.to_packed_timestamp(4109878800000),
+            PackedTimestamp::new(2100, 3, 28, 3, 0, 0, 0, 120)
+        );
+    }
+
+    #[test]
+    fn test_southern_hemisphere() {
+        let zone = PosixTimeZone::parse("AEST-10AEDT,M10.1.0,M4.1.0/3").unwrap();
+        // 2022-04-02T16:00:00Z and 2022-10-01T16:00:00Z
+        assert_eq!(zone.offset_minutes_at(1648915199000), 660);
+        assert_eq!(zone.offset_minutes_at(1648915200000), 600);
+        assert_eq!(zone.offset_minutes_at(1664639999000), 600);
+        assert_eq!(zone.offset_minutes_at(1664640000000), 660);
+        // 2022-01-01T00:00:00Z
+        assert_eq!(zone.offset_minutes_at(1640995200000), 660);
+    }
+
+    #[test]
+    fn test_extended_transition_times() {
+        // daylight saving time all year
+        let zone = PosixTimeZone::parse("EST5EDT,0/0,J365/25").unwrap();
+        for ts in (1640995200000_i64..1704067200000).step_by(3_600_000 * 13) {
+            assert_eq!(zone.offset_minutes_at(ts), -240, "{}", ts);
+        }
+
+        // daylight saving time starts at 23:00 on the day before the last sunday of march
+        let zone = PosixTimeZone::parse("<-02>2<-01>,M3.5.0/-1,M10.5.0/0").unwrap();
+        // 2023-03-26T00:59:59Z and 2023-03-26T01:00:00Z
+        assert_eq!(zone.offset_minutes_at(1679792399000), -120);
+        assert_eq!(zone.offset_minutes_at(1679792400000), -60);
+        // 2023-10-29T00:59:59Z and 2023-10-29T01:00:00Z
+        assert_eq!(zone.offset_minutes_at(1698541199000), -60);
+        assert_eq!(zone.offset_minutes_at(1698541200000), -120);
+    }
+
+    #[test]
+    fn test_julian_days() {
+        // J60 is march 1 even in leap years, 60 is march 1 in leap years only
+        let zone = PosixTimeZone::parse("AAA0BBB,J60/0,J61/0").unwrap();
+        // 2024-03-01T00:00:00Z
+        assert_eq!(zone.offset_minutes_at(1709251200000), 60);
+        assert_eq!(zone.offset_minutes_at(1709251200000 - 1), 0);
+        let zone = PosixTimeZone::parse("AAA0BBB,60/0,61/0").unwrap();
+        assert_eq!(zone.offset_minutes_at(1709251200000), 60);
+        assert_eq!(zone.offset_minutes_at(1709251200000 - 1), 0);
+        // 2023-03-02T00:00:00Z
+        assert_eq!(zone.offset_minutes_at(1677715200000), 60);
+        assert_eq!(zone.offset_minutes_at(1677715200000 - 1), 0);
+    }
+
+    #[test]
+    fn test_default_rules() {
+        let zone = PosixTimeZone::parse("EST5EDT").unwrap();
+        assert_eq!(zone.offset_minutes_at(1647154799000), -300);
+        assert_eq!(zone.offset_minutes_at(1647154800000), -240);
+        let zone = PosixTimeZone::parse("EST5EDT4,M3.2.0/2:00:00,M11.1.0/2:00:00").unwrap();
+        assert_eq!(zone.offset_minutes_at(1647154800000), -240);
+    }
+
+    #[test]
+    fn test_matches_tzif_transitions() {
+        for bytes in [BERLIN, NEW_YORK, LORD_HOWE, SAO_PAULO] {
+            let zone = TimeZone::from_tzif_bytes(bytes).unwrap();
+            let rule = PosixTimeZone::parse(zone.footer().unwrap()).unwrap();
+            // every hour from 2020 until 2037
+            for ts in (1577836800000_i64..2114380800000).step_by(3_600_000) {
+                assert_eq!(
+                    zone.offset_seconds_at(ts),
+                    rule.offset_seconds_at(ts),
+                    "{:?} {}",
+                    zone.footer(),
+                    ts
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        for (rule, position) in [
+            ("", 0),
+            ("CE-1", 2),
+            ("CET", 3),
+            ("CET-", 4),
+            ("CET-1CEST,M3.5.0", 16),
+            ("CET-1CEST,M13.5.0,M10.5.0/3", 11),
+            ("CET-1CEST,M3.6.0,M10.5.0/3", 13),
+            ("CET-1CEST,M3.5.7,M10.5.0/3", 15),
+            ("CET-1CEST,J0,M10.5.0/3", 11),
+            ("CET-1CEST,M3.5.0,M10.5.0/168", 25),
+            ("CET-1CEST,M3.5.0,M10.5.0/3x", 26),
+            ("CET-25", 4),
+            ("CET-18:30", 3),
+            ("XXX-17:59YYY", 12),
+            ("XXX-17:59YYY,M3.5.0,M10.5.0/3", 12),
+            ("CET-1:60", 6),
+            ("<+01-1", 6),
+        ] {
+            assert_eq!(
+                PosixTimeZone::parse(rule),
+                Err(TimeZoneError::InvalidRule { position }),
+                "{}",
+                rule
+            );
+        }
+    }
+}
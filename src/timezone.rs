@@ -0,0 +1,689 @@
+use crate::datetime::DateTimeComponents;
+use crate::error::TimeZoneError;
+use crate::packed::{MAX_OFFSET_MINUTES, MIN_OFFSET_MINUTES};
+use crate::{PackedTimestamp, PosixTimeZone, MILLIS_PER_DAY};
+use std::path::Path;
+
+const DEFAULT_ZONEINFO_DIR: &str = "/usr/share/zoneinfo";
+
+/// A local time type of a time zone, consisting of an offset from UTC, a daylight saving time flag and an abbreviation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocalTimeType {
+    offset_seconds: i32,
+    is_dst: bool,
+    abbreviation: String,
+}
+
+impl LocalTimeType {
+    pub(crate) fn new(offset_seconds: i32, is_dst: bool, abbreviation: &str) -> Self {
+        Self {
+            offset_seconds,
+            is_dst,
+            abbreviation: abbreviation.to_string(),
+        }
+    }
+
+    /// The offset from UTC in seconds, positive east of Greenwich.
+    #[inline]
+    pub fn offset_seconds(&self) -> i32 {
+        self.offset_seconds
+    }
+
+    /// The offset from UTC in whole minutes, truncating the seconds of historic local mean times.
+    #[inline]
+    pub fn offset_minutes(&self) -> i32 {
+        self.offset_seconds / 60
+    }
+
+    #[inline]
+    pub fn is_dst(&self) -> bool {
+        self.is_dst
+    }
+
+    /// The abbreviation of this local time type, like `CET` or `CEST`.
+    #[inline]
+    pub fn abbreviation(&self) -> &str {
+        &self.abbreviation
+    }
+}
+
+/// The result of converting a local date and time into an instant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LocalResult {
+    /// The local time maps to exactly one instant.
+    Single(i64),
+    /// The local time occurs twice, usually when clocks are turned back at the end of daylight saving time.
+    Ambiguous { earlier: i64, later: i64 },
+    /// The local time is skipped, usually when clocks are turned forward at the start of daylight saving time.
+    /// `earlier` and `later` are the instants obtained by applying the offset after and before the transition.
+    Gap { earlier: i64, later: i64 },
+}
+
+impl LocalResult {
+    /// The instant if the local time is neither ambiguous nor skipped.
+    #[inline]
+    pub fn single(&self) -> Option<i64> {
+        match self {
+            LocalResult::Single(ts) => Some(*ts),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn earlier(&self) -> i64 {
+        match self {
+            LocalResult::Single(ts) => *ts,
+            LocalResult::Ambiguous { earlier, .. } | LocalResult::Gap { earlier, .. } => *earlier,
+        }
+    }
+
+    #[inline]
+    pub fn later(&self) -> i64 {
+        match self {
+            LocalResult::Single(ts) => *ts,
+            LocalResult::Ambiguous { later, .. } | LocalResult::Gap { later, .. } => *later,
+        }
+    }
+
+    /// Resolves an overlap to the earlier instant and a gap to the later instant,
+    /// which shifts a skipped local time forward by the length of the gap.
+    #[inline]
+    pub fn compatible(&self) -> i64 {
+        match self {
+            LocalResult::Single(ts) => *ts,
+            LocalResult::Ambiguous { earlier, .. } => *earlier,
+            LocalResult::Gap { later, .. } => *later,
+        }
+    }
+}
+
+/// A time zone loaded from TZif data as described in [RFC 8536](https://www.rfc-editor.org/rfc/rfc8536).
+///
+/// Instants after the last transition use the POSIX TZ rule from the footer of TZif data of version 2 and later,
+/// or the local time type of the last transition if there is no footer. Leap second records are ignored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimeZone {
+    name: Option<String>,
+    /// Transition instants in milliseconds since the epoch, sorted in ascending order.
+    transitions: Vec<i64>,
+    /// Index into `types` for each transition.
+    transition_types: Vec<u8>,
+    types: Vec<LocalTimeType>,
+    footer: Option<String>,
+    rule: Option<PosixTimeZone>,
+}
+
+impl TimeZone {
+    pub fn utc() -> Self {
+        Self {
+            name: Some("UTC".to_string()),
+            transitions: vec![],
+            transition_types: vec![],
+            types: vec![LocalTimeType {
+                offset_seconds: 0,
+                is_dst: false,
+                abbreviation: "UTC".to_string(),
+            }],
+            footer: None,
+            rule: None,
+        }
+    }
+
+    /// A time zone without transitions, using the given offset for all instants.
+    ///
+    /// # Panics
+    ///
+    /// If the offset is outside the range supported by `PackedTimestamp`.
+    pub fn fixed(offset_minutes: i32) -> Self {
+        assert!(
+            offset_minutes >= MIN_OFFSET_MINUTES && offset_minutes <= MAX_OFFSET_MINUTES,
+            "offset out of range"
+        );
+        let sign = if offset_minutes < 0 { '-' } else { '+' };
+        let abs = offset_minutes.unsigned_abs();
+        Self {
+            name: None,
+            transitions: vec![],
+            transition_types: vec![],
+            types: vec![LocalTimeType {
+                offset_seconds: offset_minutes * 60,
+                is_dst: false,
+                abbreviation: format!("{}{:02}:{:02}", sign, abs / 60, abs % 60),
+            }],
+            footer: None,
+            rule: None,
+        }
+    }
+
+    /// Loads the zone with the given name, like `Europe/Berlin`, from the directory named by the `TZDIR`
+    /// environment variable or from `/usr/share/zoneinfo`.
+    pub fn from_zoneinfo(name: &str) -> Result<Self, TimeZoneError> {
+        let dir = std::env::var_os("TZDIR").unwrap_or_else(|| DEFAULT_ZONEINFO_DIR.into());
+        Self::from_zoneinfo_in(Path::new(&dir), name)
+    }
+
+    /// Loads the zone with the given name from the given zoneinfo directory.
+    fn from_zoneinfo_in(dir: &Path, name: &str) -> Result<Self, TimeZoneError> {
+        if name.is_empty()
+            || name.starts_with('/')
+            || name.contains('\\')
+            || name.contains('\0')
+            || name.split('/').any(|part| part.is_empty() || part == "." || part == "..")
+        {
+            return Err(TimeZoneError::InvalidName);
+        }
+        let mut zone = Self::from_tzif_file(dir.join(name))?;
+        zone.name = Some(name.to_string());
+        Ok(zone)
+    }
+
+    pub fn from_tzif_file<P: AsRef<Path>>(path: P) -> Result<Self, TimeZoneError> {
+        let bytes = std::fs::read(path)?;
+        Self::from_tzif_bytes(&bytes)
+    }
+
+    /// Parses TZif data of version 1 to 4. For version 2 and later the 64-bit data block and the footer are used.
+    pub fn from_tzif_bytes(bytes: &[u8]) -> Result<Self, TimeZoneError> {
+        let mut reader = Reader { bytes, position: 0 };
+        let header = Header::read(&mut reader)?;
+        if header.version == 0 {
+            return Self::read_data_block(&mut reader, &header, 4);
+        }
+
+        reader.take(header.data_block_len(4))?;
+        let header = Header::read(&mut reader)?;
+        let mut zone = Self::read_data_block(&mut reader, &header, 8)?;
+
+        if reader.u8()? != b'\n' {
+            return Err(TimeZoneError::InvalidData("footer does not start with a newline"));
+        }
+        let remaining = &reader.bytes[reader.position..];
+        let end = remaining
+            .iter()
+            .position(|b| *b == b'\n')
+            .ok_or(TimeZoneError::UnexpectedEnd)?;
+        let footer = std::str::from_utf8(&remaining[..end])
+            .ok()
+            .filter(|footer| footer.is_ascii())
+            .ok_or(TimeZoneError::InvalidData("footer is not ascii"))?;
+        if !footer.is_empty() {
+            zone.rule = Some(PosixTimeZone::parse(footer)?);
+            zone.footer = Some(footer.to_string());
+        }
+
+        Ok(zone)
+    }
+
+    /// A time zone without transitions whose offsets are computed from a POSIX TZ rule string like `CET-1CEST,M3.5.0,M10.5.0/3`.
+    pub fn from_posix_tz(rule: &str) -> Result<Self, TimeZoneError> {
+        let posix = PosixTimeZone::parse(rule)?;
+        Ok(Self {
+            name: None,
+            transitions: vec![],
+            transition_types: vec![],
+            types: vec![posix.standard().clone()],
+            footer: Some(rule.to_string()),
+            rule: Some(posix),
+        })
+    }
+
+    fn read_data_block(reader: &mut Reader, header: &Header, time_size: usize) -> Result<Self, TimeZoneError> {
+        if header.typecnt == 0 {
+            return Err(TimeZoneError::InvalidData("no local time types"));
+        }
+        if header.typecnt > 256 {
+            return Err(TimeZoneError::InvalidData("too many local time types"));
+        }
+        if reader.bytes.len() - reader.position < header.data_block_len(time_size) {
+            return Err(TimeZoneError::UnexpectedEnd);
+        }
+
+        let mut transitions = Vec::with_capacity(header.timecnt);
+        for _ in 0..header.timecnt {
+            let seconds = if time_size == 8 { reader.i64()? } else { reader.i32()? as i64 };
+            if transitions.last().is_some_and(|last| *last >= seconds.saturating_mul(1000)) {
+                return Err(TimeZoneError::InvalidData("transitions are not sorted"));
+            }
+            transitions.push(seconds.saturating_mul(1000));
+        }
+
+        let transition_types = reader.take(header.timecnt)?.to_vec();
+        if transition_types.iter().any(|idx| *idx as usize >= header.typecnt) {
+            return Err(TimeZoneError::InvalidData("local time type index out of range"));
+        }
+
+        let mut raw_types = Vec::with_capacity(header.typecnt);
+        for _ in 0..header.typecnt {
+            let offset_seconds = reader.i32()?;
+            let is_dst = match reader.u8()? {
+                0 => false,
+                1 => true,
+                _ => return Err(TimeZoneError::InvalidData("invalid dst flag")),
+            };
+            let abbreviation_index = reader.u8()? as usize;
+            if offset_seconds < MIN_OFFSET_MINUTES * 60 || offset_seconds > MAX_OFFSET_MINUTES * 60 {
+                return Err(TimeZoneError::InvalidData("offset out of range"));
+            }
+            raw_types.push((offset_seconds, is_dst, abbreviation_index));
+        }
+
+        let designations = reader.take(header.charcnt)?;
+        let types = raw_types
+            .into_iter()
+            .map(|(offset_seconds, is_dst, abbreviation_index)| {
+                let designation = designations
+                    .get(abbreviation_index..)
+                    .ok_or(TimeZoneError::InvalidData("abbreviation index out of range"))?;
+                let end = designation
+                    .iter()
+                    .position(|b| *b == 0)
+                    .ok_or(TimeZoneError::InvalidData("abbreviation is not terminated"))?;
+                let abbreviation = String::from_utf8_lossy(&designation[..end]).into_owned();
+                Ok(LocalTimeType {
+                    offset_seconds,
+                    is_dst,
+                    abbreviation,
+                })
+            })
+            .collect::<Result<Vec<_>, TimeZoneError>>()?;
+
+        // leap second records and the standard/wall and ut/local indicators are not needed
+        reader.take(header.leapcnt * (time_size + 4) + header.isstdcnt + header.isutcnt)?;
+
+        Ok(Self {
+            name: None,
+            transitions,
+            transition_types,
+            types,
+            footer: None,
+            rule: None,
+        })
+    }
+
+    /// The name this zone was loaded with, if any.
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The POSIX TZ rule string from the footer of TZif data of version 2 and later.
+    #[inline]
+    pub fn footer(&self) -> Option<&str> {
+        self.footer.as_deref()
+    }
+
+    /// The rule used for instants after the last transition.
+    #[inline]
+    pub fn rule(&self) -> Option<&PosixTimeZone> {
+        self.rule.as_ref()
+    }
+
+    /// The local time type in effect at the given instant in milliseconds since the epoch.
+    pub fn local_time_type_at(&self, ts_millis: i64) -> &LocalTimeType {
+        let idx = self.transitions.partition_point(|transition| *transition <= ts_millis);
+        if idx == self.transitions.len() {
+            if let Some(rule) = &self.rule {
+                return rule.local_time_type_at(ts_millis);
+            }
+        }
+        if idx == 0 {
+            &self.types[0]
+        } else {
+            &self.types[self.transition_types[idx - 1] as usize]
+        }
+    }
+
+    #[inline]
+    pub fn offset_seconds_at(&self, ts_millis: i64) -> i32 {
+        self.local_time_type_at(ts_millis).offset_seconds
+    }
+
+    #[inline]
+    pub fn offset_minutes_at(&self, ts_millis: i64) -> i32 {
+        self.local_time_type_at(ts_millis).offset_minutes()
+    }
+
+    /// Converts an instant into local milliseconds, that is milliseconds since `1970-01-01T00:00:00` in local time.
+    #[inline]
+    pub fn to_local_millis(&self, ts_millis: i64) -> i64 {
+        ts_millis + self.offset_seconds_at(ts_millis) as i64 * 1000
+    }
+
+    /// Converts local milliseconds into an instant, reporting whether the local time is skipped or occurs twice.
+    pub fn resolve_local(&self, local_millis: i64) -> LocalResult {
+        // transitions are assumed to be more than a day apart and change the offset by less than a day
+        let before = self.offset_seconds_at(local_millis.saturating_sub(MILLIS_PER_DAY)) as i64 * 1000;
+        let after = self.offset_seconds_at(local_millis.saturating_add(MILLIS_PER_DAY)) as i64 * 1000;
+
+        let valid = |offset: i64| {
+            let ts = local_millis - offset;
+            (self.offset_seconds_at(ts) as i64 * 1000 == offset).then_some(ts)
+        };
+
+        match (valid(before), valid(after)) {
+            (Some(first), Some(second)) if first != second => LocalResult::Ambiguous {
+                earlier: first.min(second),
+                later: first.max(second),
+            },
+            (Some(ts), _) | (None, Some(ts)) => LocalResult::Single(ts),
+            (None, None) => LocalResult::Gap {
+                earlier: local_millis - before.max(after),
+                later: local_millis - before.min(after),
+            },
+        }
+    }
+
+    /// Converts an instant into a `PackedTimestamp` with the local date, time and offset of this zone.
+    pub fn to_packed_timestamp(&self, ts_millis: i64) -> PackedTimestamp {
+        packed_timestamp_at(ts_millis, |ts| self.offset_minutes_at(ts))
+    }
+}
+
+/// Converts an instant into a `PackedTimestamp` with the local date and time for the offset returned by `offset_minutes_at`.
+pub(crate) fn packed_timestamp_at(ts_millis: i64, offset_minutes_at: impl FnOnce(i64) -> i32) -> PackedTimestamp {
+    let offset_minutes = offset_minutes_at(ts_millis);
+    let mut components = DateTimeComponents::from_timestamp_millis(ts_millis + offset_minutes as i64 * 60 * 1000);
+    components.offset_minute = offset_minutes;
+    components.to_packed()
+}
+
+struct Header {
+    version: u8,
+    isutcnt: usize,
+    isstdcnt: usize,
+    leapcnt: usize,
+    timecnt: usize,
+    typecnt: usize,
+    charcnt: usize,
+}
+
+impl Header {
+    fn read(reader: &mut Reader) -> Result<Self, TimeZoneError> {
+        if reader.take(4).map_err(|_| TimeZoneError::InvalidMagic)? != b"TZif" {
+            return Err(TimeZoneError::InvalidMagic);
+        }
+        let version = reader.u8()?;
+        if !matches!(version, 0 | b'2' | b'3' | b'4') {
+            return Err(TimeZoneError::UnsupportedVersion(version));
+        }
+        reader.take(15)?;
+        Ok(Self {
+            version,
+            isutcnt: reader.u32()? as usize,
+            isstdcnt: reader.u32()? as usize,
+            leapcnt: reader.u32()? as usize,
+            timecnt: reader.u32()? as usize,
+            typecnt: reader.u32()? as usize,
+            charcnt: reader.u32()? as usize,
+        })
+    }
+
+    fn data_block_len(&self, time_size: usize) -> usize {
+        self.timecnt * (time_size + 1)
+            + self.typecnt * 6
+            + self.charcnt
+            + self.leapcnt * (time_size + 4)
+            + self.isstdcnt
+            + self.isutcnt
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], TimeZoneError> {
+        let end = self.position.checked_add(len).ok_or(TimeZoneError::UnexpectedEnd)?;
+        let slice = self.bytes.get(self.position..end).ok_or(TimeZoneError::UnexpectedEnd)?;
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], TimeZoneError> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> Result<u8, TimeZoneError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, TimeZoneError> {
+        Ok(u32::from_be_bytes(self.array()?))
+    }
+
+    fn i32(&mut self) -> Result<i32, TimeZoneError> {
+        Ok(i32::from_be_bytes(self.array()?))
+    }
+
+    fn i64(&mut self) -> Result<i64, TimeZoneError> {
+        Ok(i64::from_be_bytes(self.array()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::{BERLIN, KOLKATA, LORD_HOWE, NEW_YORK};
+    use crate::{LocalResult, PackedTimestamp, TimeZone, TimeZoneError};
+    use std::path::Path;
+
+    #[test]
+    fn test_offset_at_transitions() {
+        let berlin = TimeZone::from_tzif_bytes(BERLIN).unwrap();
+        assert_eq!(berlin.footer(), Some("CET-1CEST,M3.5.0,M10.5.0/3"));
+
+        assert_eq!(berlin.offset_minutes_at(1648342799000), 60);
+        assert_eq!(berlin.local_time_type_at(1648342799000).abbreviation(), "CET");
+        assert_eq!(berlin.offset_minutes_at(1648342800000), 120);
+        assert_eq!(berlin.local_time_type_at(1648342800000).abbreviation(), "CEST");
+        assert!(berlin.local_time_type_at(1648342800000).is_dst());
+        assert_eq!(berlin.offset_minutes_at(1667091599000), 120);
+        assert_eq!(berlin.offset_minutes_at(1667091600000), 60);
+
+        let new_york = TimeZone::from_tzif_bytes(NEW_YORK).unwrap();
+        assert_eq!(new_york.offset_minutes_at(1647154799000), -300);
+        assert_eq!(new_york.offset_minutes_at(1647154800000), -240);
+        assert_eq!(new_york.local_time_type_at(1647154800000).abbreviation(), "EDT");
+
+        let lord_howe = TimeZone::from_tzif_bytes(LORD_HOWE).unwrap();
+        assert_eq!(lord_howe.offset_minutes_at(1648911599000), 660);
+        assert_eq!(lord_howe.offset_minutes_at(1648911600000), 630);
+        assert_eq!(lord_howe.local_time_type_at(1648911600000).abbreviation(), "+1030");
+
+        let kolkata = TimeZone::from_tzif_bytes(KOLKATA).unwrap();
+        assert_eq!(kolkata.offset_minutes_at(1640995200000), 330);
+    }
+
+    #[test]
+    fn test_local_mean_time_before_first_transition() {
+        let berlin = TimeZone::from_tzif_bytes(BERLIN).unwrap();
+        assert_eq!(berlin.offset_seconds_at(-5364662400000), 3208);
+        assert_eq!(berlin.offset_minutes_at(-5364662400000), 53);
+        assert_eq!(berlin.local_time_type_at(-5364662400000).abbreviation(), "LMT");
+
+        let kolkata = TimeZone::from_tzif_bytes(KOLKATA).unwrap();
+        assert_eq!(kolkata.offset_seconds_at(-3786825600000), 21208);
+        assert_eq!(kolkata.to_local_millis(-3786825600000), -3786825600000 + 21208000);
+    }
+
+    #[test]
+    fn test_resolve_local() {
+        let berlin = TimeZone::from_tzif_bytes(BERLIN).unwrap();
+
+        // 2022-07-01T12:00 local
+        assert_eq!(berlin.resolve_local(1656676800000), LocalResult::Single(1656669600000));
+
+        // 2022-03-27T02:30 local is skipped
+        let gap = berlin.resolve_local(1648348200000);
+        assert_eq!(
+            gap,
+            LocalResult::Gap {
+                earlier: 1648341000000,
+                later: 1648344600000
+            }
+        );
+        assert_eq!(gap.single(), None);
+        assert_eq!(gap.compatible(), 1648344600000);
+
+        // 2022-10-30T02:30 local occurs twice
+        let overlap = berlin.resolve_local(1667097000000);
+        assert_eq!(
+            overlap,
+            LocalResult::Ambiguous {
+                earlier: 1667089800000,
+                later: 1667093400000
+            }
+        );
+        assert_eq!(overlap.compatible(), 1667089800000);
+        assert_eq!(overlap.later(), 1667093400000);
+    }
+
+    #[test]
+    fn test_resolve_local_roundtrip() {
+        let zones = [BERLIN, NEW_YORK, LORD_HOWE, KOLKATA].map(|bytes| TimeZone::from_tzif_bytes(bytes).unwrap());
+        for zone in &zones {
+            // every 15 minutes from 2020 to 2024
+            for ts in (1577836800000_i64..1704067200000).step_by(15 * 60 * 1000) {
+                let resolved = zone.resolve_local(zone.to_local_millis(ts));
+                assert!(
+                    resolved.earlier() == ts || resolved.later() == ts,
+                    "{:?} {} {:?}",
+                    zone.footer(),
+                    ts,
+                    resolved
+                );
+                assert!(!matches!(resolved, LocalResult::Gap { .. }));
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_packed_timestamp() {
+        let berlin = TimeZone::from_tzif_bytes(BERLIN).unwrap();
+        assert_eq!(
+            berlin.to_packed_timestamp(1648342800000),
+            PackedTimestamp::new(2022, 3, 27, 3, 0, 0, 0, 120)
+        );
+        assert_eq!(
+            berlin.to_packed_timestamp(1648342800000).to_rfc3339_string(),
+            "2022-03-27T03:00:00.000+02:00"
+        );
+
+        let new_york = TimeZone::from_tzif_bytes(NEW_YORK).unwrap();
+        assert_eq!(
+            new_york.to_packed_timestamp(1647154799000),
+            PackedTimestamp::new(2022, 3, 13, 1, 59, 59, 0, -300)
+        );
+        assert_eq!(
+            new_york.to_packed_timestamp(1647154799000).to_timestamp_millis(),
+            1647154799000
+        );
+    }
+
+    #[test]
+    fn test_utc_and_fixed() {
+        let utc = TimeZone::utc();
+        assert_eq!(utc.offset_minutes_at(1647154799000), 0);
+        assert_eq!(utc.resolve_local(1647154799000), LocalResult::Single(1647154799000));
+
+        let fixed = TimeZone::fixed(-150);
+        assert_eq!(fixed.local_time_type_at(0).abbreviation(), "-02:30");
+        assert_eq!(fixed.to_local_millis(0), -150 * 60 * 1000);
+        assert_eq!(fixed.resolve_local(0), LocalResult::Single(150 * 60 * 1000));
+    }
+
+    #[test]
+    fn test_rule_after_last_transition() {
+        let berlin = TimeZone::from_tzif_bytes(BERLIN).unwrap();
+        // 2100-03-28T01:00:00Z
+        assert_eq!(berlin.offset_minutes_at(4109878799000), 60);
+        assert_eq!(berlin.offset_minutes_at(4109878800000), 120);
+        assert_eq!(berlin.local_time_type_at(4109878800000).abbreviation(), "CEST");
+        // 2100-03-28T02:30 local is skipped
+        assert_eq!(
+            berlin.resolve_local(4109878800000 + 5400000),
+            LocalResult::Gap {
+                earlier: 4109878800000 - 1800000,
+                later: 4109878800000 + 1800000
+            }
+        );
+
+        let zone = TimeZone::from_posix_tz("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+        assert_eq!(zone.offset_minutes_at(4109878799000), 60);
+        assert_eq!(zone.offset_minutes_at(4109878800000), 120);
+        assert_eq!(
+            zone.to_packed_timestamp(4109878800000),
+            PackedTimestamp::new(2100, 3, 28, 3, 0, 0, 0, 120)
+        );
+    }
+
+    #[test]
+    fn test_version_1() {
+        let mut bytes = BERLIN.to_vec();
+        bytes[4] = 0;
+        let zone = TimeZone::from_tzif_bytes(&bytes).unwrap();
+        assert_eq!(zone.footer(), None);
+        assert_eq!(zone.offset_minutes_at(1648342799000), 60);
+        assert_eq!(zone.offset_minutes_at(1648342800000), 120);
+    }
+
+    #[test]
+    fn test_invalid_data() {
+        assert_eq!(TimeZone::from_tzif_bytes(b"TZ"), Err(TimeZoneError::InvalidMagic));
+        assert_eq!(TimeZone::from_tzif_bytes(b"TZxf2"), Err(TimeZoneError::InvalidMagic));
+
+        let mut bytes = BERLIN.to_vec();
+        bytes[4] = b'9';
+        assert_eq!(
+            TimeZone::from_tzif_bytes(&bytes),
+            Err(TimeZoneError::UnsupportedVersion(b'9'))
+        );
+
+        assert_eq!(TimeZone::from_tzif_bytes(&BERLIN[..100]), Err(TimeZoneError::UnexpectedEnd));
+        assert_eq!(
+            TimeZone::from_tzif_bytes(&BERLIN[..BERLIN.len() - 1]),
+            Err(TimeZoneError::UnexpectedEnd)
+        );
+
+        // first transition type index of the 64-bit block
+        let mut bytes = BERLIN.to_vec();
+        let v1_len = 44 + 143 * 5 + 9 * 6 + 18 + 9 + 9;
+        bytes[v1_len + 44 + 143 * 8] = 9;
+        assert_eq!(
+            TimeZone::from_tzif_bytes(&bytes),
+            Err(TimeZoneError::InvalidData("local time type index out of range"))
+        );
+
+        let mut bytes = BERLIN.to_vec();
+        let footer_start = bytes.len() - "CET-1CEST,M3.5.0,M10.5.0/3\n".len();
+        bytes[footer_start + 1] = b'1';
+        assert_eq!(
+            TimeZone::from_tzif_bytes(&bytes),
+            Err(TimeZoneError::InvalidRule { position: 1 })
+        );
+    }
+
+    #[test]
+    fn test_invalid_name() {
+        for name in ["", "/etc/passwd", "../etc/passwd", "Europe/../../etc", "Europe//Berlin"] {
+            assert_eq!(TimeZone::from_zoneinfo(name), Err(TimeZoneError::InvalidName), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_from_zoneinfo() {
+        let dir = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/zoneinfo"));
+        let zone = TimeZone::from_zoneinfo_in(dir, "Europe/Berlin").unwrap();
+        assert_eq!(zone.name(), Some("Europe/Berlin"));
+        assert_eq!(zone, {
+            let mut expected = TimeZone::from_tzif_bytes(BERLIN).unwrap();
+            expected.name = Some("Europe/Berlin".to_string());
+            expected
+        });
+        assert_eq!(
+            TimeZone::from_zoneinfo_in(dir, "Europe/Atlantis"),
+            Err(TimeZoneError::Io(std::io::ErrorKind::NotFound))
+        );
+    }
+}